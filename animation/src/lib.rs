@@ -0,0 +1,188 @@
+//! Frames recorded from a running simulation, and a terminal player for them.
+//!
+//! A simulation offers every intermediate state to a [`Recorder`], which
+//! keeps at most a fixed number of evenly spaced frames, so a long run does
+//! not fill the memory. Drawing a frame is deferred until the recorder keeps
+//! it:
+//!
+//! ```
+//! use animation::{Frame, Recorder};
+//!
+//! let mut recorder = Recorder::new(100);
+//! for step in 0..1000 {
+//!     recorder.record(|| Frame::new(format!("step {}", step), "#".repeat(step % 10)));
+//! }
+//! recorder.keep(Frame::new("done", "#########"));
+//! let frames = recorder.into_frames();
+//! assert!(frames.len() <= 101);
+//! assert_eq!(frames[0].caption, "step 0");
+//! assert_eq!(frames.last().unwrap().caption, "done");
+//! ```
+//!
+//! The player needs the `tui` feature.
+
+#[cfg(feature = "tui")]
+mod player;
+
+use std::fmt::Display;
+
+use common::Solution;
+
+#[cfg(feature = "tui")]
+pub use player::play;
+
+/// A snapshot of the simulation as text, with a line telling where it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub text: String,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, text: impl Display) -> Self {
+        Self {
+            caption: caption.into(),
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Collects frames, thinning them out to stay within its limit.
+///
+/// Once the limit is reached, every other frame is dropped and only every
+/// second step from then on is drawn, so the frames stay evenly spaced
+/// however long the simulation runs.
+#[derive(Debug)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    /// Step number of every frame, `None` for the ones never dropped.
+    steps: Vec<Option<usize>>,
+    limit: usize,
+    /// How many of the frames may be dropped again.
+    droppable: usize,
+    stride: usize,
+    step: usize,
+}
+
+impl Recorder {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            frames: vec![],
+            steps: vec![],
+            limit: limit.max(2),
+            droppable: 0,
+            stride: 1,
+            step: 0,
+        }
+    }
+
+    /// Offer the state after the next step, drawn with `draw` if it is kept.
+    pub fn record<F: FnOnce() -> Frame>(&mut self, draw: F) {
+        let step = self.step;
+        self.step += 1;
+        if !step.is_multiple_of(self.stride) {
+            return;
+        }
+
+        self.frames.push(draw());
+        self.steps.push(Some(step));
+        self.droppable += 1;
+        if self.droppable > self.limit {
+            self.stride *= 2;
+            let keep =
+                |step: &Option<usize>| step.is_none_or(|step| step.is_multiple_of(self.stride));
+            let mut steps = self.steps.iter();
+            self.frames.retain(|_| keep(steps.next().unwrap()));
+            self.steps.retain(keep);
+            self.droppable = self.steps.iter().flatten().count();
+        }
+    }
+
+    /// Add a frame that is never dropped and does not count towards the
+    /// limit, like the final state.
+    pub fn keep(&mut self, frame: Frame) {
+        self.frames.push(frame);
+        self.steps.push(None);
+    }
+
+    /// How many steps were recorded so far, kept or not.
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames
+    }
+}
+
+/// A solver whose simulation can be recorded frame by frame.
+pub trait Animate: Solution {
+    fn animate(
+        input: &Self::Input<'_>,
+        params: &Self::Params,
+        recorder: &mut Recorder,
+    ) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(steps: usize, limit: usize) -> Vec<String> {
+        let mut recorder = Recorder::new(limit);
+        for step in 0..steps {
+            recorder.record(|| Frame::new(step.to_string(), ""));
+        }
+        assert_eq!(recorder.steps(), steps);
+        recorder
+            .into_frames()
+            .into_iter()
+            .map(|frame| frame.caption)
+            .collect()
+    }
+
+    #[test]
+    fn keep_everything_below_the_limit() {
+        assert_eq!(record(3, 10), vec!["0", "1", "2"]);
+        assert_eq!(record(0, 10), Vec::<String>::new());
+    }
+
+    #[test]
+    fn thin_out_evenly() {
+        assert_eq!(record(10, 4), vec!["0", "4", "8"]);
+        assert_eq!(record(7, 4), vec!["0", "2", "4", "6"]);
+        let frames = record(1000, 50);
+        assert!(frames.len() <= 50, "{} frames", frames.len());
+        assert_eq!(frames[1], "32");
+        assert_eq!(frames.last().unwrap(), "992");
+    }
+
+    #[test]
+    fn never_drop_kept_frames() {
+        let mut recorder = Recorder::new(2);
+        recorder.keep(Frame::new("start", ""));
+        for step in 0..10 {
+            recorder.record(|| Frame::new(step.to_string(), ""));
+        }
+        recorder.keep(Frame::new("end", ""));
+        let captions: Vec<String> = recorder
+            .into_frames()
+            .into_iter()
+            .map(|frame| frame.caption)
+            .collect();
+        assert_eq!(captions, vec!["start", "0", "8", "end"]);
+    }
+
+    #[test]
+    fn draw_only_kept_frames() {
+        let mut recorder = Recorder::new(4);
+        let mut drawn = 0;
+        for step in 0..100 {
+            recorder.record(|| {
+                drawn += 1;
+                Frame::new(step.to_string(), "")
+            });
+        }
+        assert!(drawn < 100);
+    }
+}
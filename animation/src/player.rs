@@ -0,0 +1,257 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    style::Print,
+    terminal::{self, ClearType},
+    QueueableCommand,
+};
+
+use crate::Frame;
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 240.0;
+const HELP: &str = "space pause  \u{2190}\u{2192} step  +/- speed  \u{2191}\u{2193} scroll  q quit";
+
+/// Where the playback is, apart from the terminal.
+#[derive(Debug, PartialEq)]
+struct Playback {
+    frame: usize,
+    frames: usize,
+    fps: f64,
+    paused: bool,
+    /// First line of the frame shown at the top of the terminal.
+    scroll: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Control {
+    Continue,
+    Quit,
+}
+
+impl Playback {
+    fn new(frames: usize, fps: f64) -> Self {
+        Self {
+            frame: 0,
+            frames,
+            fps: fps.clamp(MIN_FPS, MAX_FPS),
+            paused: false,
+            scroll: 0,
+        }
+    }
+
+    fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+
+    /// Move on to the next frame, pausing on the last one.
+    fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        if self.frame + 1 < self.frames {
+            self.frame += 1;
+        } else {
+            self.paused = true;
+        }
+    }
+
+    fn step(&mut self, forward: bool) {
+        self.paused = true;
+        self.frame = if forward {
+            (self.frame + 1).min(self.frames.saturating_sub(1))
+        } else {
+            self.frame.saturating_sub(1)
+        };
+    }
+
+    fn handle(&mut self, key: KeyEvent, page: usize) -> Control {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Control::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Control::Quit
+            }
+            KeyCode::Char(' ') => {
+                // replay from the start once the end is reached
+                if self.paused && self.frame + 1 == self.frames {
+                    self.frame = 0;
+                }
+                self.paused = !self.paused;
+            }
+            KeyCode::Right | KeyCode::Char('l') => self.step(true),
+            KeyCode::Left | KeyCode::Char('h') => self.step(false),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.fps = (self.fps * 2.0).min(MAX_FPS),
+            KeyCode::Char('-') => self.fps = (self.fps / 2.0).max(MIN_FPS),
+            KeyCode::Home | KeyCode::Char('g') => self.frame = 0,
+            KeyCode::End | KeyCode::Char('G') => self.frame = self.frames.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll += page,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            _ => {}
+        }
+        Control::Continue
+    }
+
+    fn status(&self, caption: &str) -> String {
+        format!(
+            "{}/{}  {}  {} fps{}  |  {}",
+            self.frame + 1,
+            self.frames,
+            caption,
+            self.fps,
+            if self.paused { "  paused" } else { "" },
+            HELP
+        )
+    }
+}
+
+/// Puts the terminal back into its normal state, also after an error.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        out.queue(terminal::EnterAlternateScreen)?
+            .queue(cursor::Hide)?
+            .flush()?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = out
+            .queue(cursor::Show)
+            .and_then(|out| out.queue(terminal::LeaveAlternateScreen))
+            .and_then(|out| out.flush());
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(out: &mut impl Write, frame: &Frame, playback: &Playback) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, rows) = (width as usize, height.saturating_sub(1) as usize);
+
+    out.queue(terminal::Clear(ClearType::All))?;
+    for (row, line) in frame
+        .text
+        .lines()
+        .skip(playback.scroll)
+        .take(rows)
+        .enumerate()
+    {
+        let line: String = line.chars().take(width).collect();
+        out.queue(cursor::MoveTo(0, row as u16))?
+            .queue(Print(line))?;
+    }
+    let status: String = playback
+        .status(&frame.caption)
+        .chars()
+        .take(width)
+        .collect();
+    out.queue(cursor::MoveTo(0, rows as u16))?
+        .queue(Print(status))?
+        .flush()
+}
+
+/// Play the frames in the terminal at `fps` frames per second until the
+/// viewer quits.
+pub fn play(frames: &[Frame], fps: f64) -> io::Result<()> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let mut out = io::stdout();
+    let _raw = RawTerminal::enter(&mut out)?;
+    let mut playback = Playback::new(frames.len(), fps);
+    let mut next_frame = Instant::now() + playback.frame_time();
+
+    loop {
+        draw(&mut out, &frames[playback.frame], &playback)?;
+
+        let timeout = if playback.paused {
+            Duration::from_secs(60)
+        } else {
+            next_frame.saturating_duration_since(Instant::now())
+        };
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    let page = terminal::size()?.1.saturating_sub(1) as usize;
+                    if playback.handle(key, page) == Control::Quit {
+                        return Ok(());
+                    }
+                    next_frame = Instant::now() + playback.frame_time();
+                }
+            }
+        } else if Instant::now() >= next_frame {
+            playback.tick();
+            next_frame += playback.frame_time();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(playback: &mut Playback, code: KeyCode) -> Control {
+        playback.handle(KeyEvent::new(code, KeyModifiers::NONE), 10)
+    }
+
+    #[test]
+    fn play_to_the_end() {
+        let mut playback = Playback::new(3, 10.0);
+        playback.tick();
+        playback.tick();
+        assert_eq!(playback.frame, 2);
+        assert!(!playback.paused);
+        playback.tick();
+        assert_eq!(playback.frame, 2);
+        assert!(playback.paused);
+
+        // space at the end starts over
+        press(&mut playback, KeyCode::Char(' '));
+        assert_eq!((playback.frame, playback.paused), (0, false));
+    }
+
+    #[test]
+    fn step_and_seek() {
+        let mut playback = Playback::new(5, 10.0);
+        press(&mut playback, KeyCode::Right);
+        assert_eq!((playback.frame, playback.paused), (1, true));
+        playback.tick();
+        assert_eq!(playback.frame, 1);
+        press(&mut playback, KeyCode::Left);
+        press(&mut playback, KeyCode::Left);
+        assert_eq!(playback.frame, 0);
+        press(&mut playback, KeyCode::End);
+        press(&mut playback, KeyCode::Right);
+        assert_eq!(playback.frame, 4);
+        press(&mut playback, KeyCode::Home);
+        assert_eq!(playback.frame, 0);
+    }
+
+    #[test]
+    fn change_speed_and_scroll() {
+        let mut playback = Playback::new(5, 10.0);
+        press(&mut playback, KeyCode::Char('+'));
+        assert_eq!(playback.fps, 20.0);
+        for _ in 0..20 {
+            press(&mut playback, KeyCode::Char('-'));
+        }
+        assert_eq!(playback.fps, MIN_FPS);
+
+        press(&mut playback, KeyCode::PageDown);
+        press(&mut playback, KeyCode::Up);
+        assert_eq!(playback.scroll, 9);
+        assert_eq!(press(&mut playback, KeyCode::Char('q')), Control::Quit);
+    }
+}
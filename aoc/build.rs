@@ -0,0 +1,146 @@
+//! Generate one test per example in the `examples.toml` of every day crate.
+//!
+//! ```toml
+//! [[example]]
+//! part = 2
+//! input = "src/input1_test.txt"
+//! params = { steps = 10 }
+//! answer = "50"
+//! ```
+//!
+//! `input` is relative to the day crate. An example can set `name` to name
+//! its test and `ignore` to a reason for ignoring it.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+/// Days only built with the cargo feature of the same name.
+const OPTIONAL_DAYS: &[u32] = &[24];
+
+struct Example {
+    day: u32,
+    part: i64,
+    name: String,
+    input: PathBuf,
+    params: Vec<(String, String)>,
+    answer: String,
+    ignore: Option<String>,
+}
+
+fn param_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Turn any text into a part of a function name.
+fn identifier(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn read_examples(day: u32, dir: &Path, manifest: &Path) -> Vec<Example> {
+    let text = fs::read_to_string(manifest).expect("readable examples manifest");
+    let table: Table = text
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid {}: {}", manifest.display(), e));
+
+    let examples = match table.get("example") {
+        Some(Value::Array(examples)) => examples.as_slice(),
+        _ => panic!("{} has no [[example]] tables", manifest.display()),
+    };
+    examples
+        .iter()
+        .map(|example| {
+            let field = |name: &str| {
+                example
+                    .get(name)
+                    .unwrap_or_else(|| panic!("example in {} has no {}", manifest.display(), name))
+            };
+            let part = field("part").as_integer().expect("integer part");
+            let input = field("input").as_str().expect("input path");
+            let params: Vec<(String, String)> = match example.get("params") {
+                Some(Value::Table(params)) => params
+                    .iter()
+                    .map(|(name, value)| (name.clone(), param_value(value)))
+                    .collect(),
+                Some(_) => panic!("params in {} is not a table", manifest.display()),
+                None => vec![],
+            };
+
+            let name = match example.get("name").and_then(Value::as_str) {
+                Some(name) => identifier(name),
+                None => {
+                    let stem = Path::new(input).file_stem().expect("input file name");
+                    let mut name = identifier(&stem.to_string_lossy());
+                    for (param, value) in &params {
+                        write!(name, "_{}_{}", identifier(param), identifier(value)).unwrap();
+                    }
+                    name
+                }
+            };
+
+            Example {
+                day,
+                part,
+                name: format!("day{}_part{}_{}", day, part, name),
+                input: dir.join(input),
+                params,
+                answer: param_value(field("answer")),
+                ignore: example
+                    .get("ignore")
+                    .map(|reason| reason.as_str().expect("reason to ignore").to_owned()),
+            }
+        })
+        .collect()
+}
+
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .expect("runner lives inside the workspace")
+        .to_owned();
+
+    let mut examples = vec![];
+    for day in 1..=25 {
+        let dir = root.join(format!("day{}", day));
+        let manifest = dir.join("examples.toml");
+        println!("cargo:rerun-if-changed={}", manifest.display());
+        if manifest.exists() {
+            examples.extend(read_examples(day, &dir, &manifest));
+        }
+    }
+
+    let mut tests = String::new();
+    for example in &examples {
+        if examples.iter().filter(|e| e.name == example.name).count() > 1 {
+            panic!("two examples would be named {}, set a name", example.name);
+        }
+        println!("cargo:rerun-if-changed={}", example.input.display());
+
+        writeln!(tests, "#[test]").unwrap();
+        if OPTIONAL_DAYS.contains(&example.day) {
+            writeln!(tests, "#[cfg(feature = \"day{}\")]", example.day).unwrap();
+        }
+        if let Some(reason) = &example.ignore {
+            writeln!(tests, "#[ignore = {:?}]", reason).unwrap();
+        }
+        writeln!(
+            tests,
+            "fn {}() {{\n    check({}, {}, include_str!({:?}), &{:?}, {:?});\n}}\n",
+            example.name, example.day, example.part, example.input, example.params, example.answer
+        )
+        .unwrap();
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).expect("writable OUT_DIR");
+}
@@ -0,0 +1,288 @@
+//! Known answers for the real puzzle inputs.
+//!
+//! The answers are checked in as `answers.toml` in the workspace root, one
+//! `[[answer]]` table per day, part, input and parameter set:
+//!
+//! ```toml
+//! [[answer]]
+//! day = 11
+//! part = 2
+//! params = { scale_factor = 1000000 }
+//! answer = "569052586852"
+//! ```
+//!
+//! Answers of a day behind a Cargo feature of the runner, like `day24`, name
+//! it with `feature = "day24"` and are skipped when it is off.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use thiserror::Error;
+use toml::Value;
+
+use crate::find;
+
+/// File name of the manifest in the workspace root.
+pub const MANIFEST: &str = "answers.toml";
+
+/// The features of the runner that answers can be behind, and whether they
+/// are on.
+const FEATURES: &[(&str, bool)] = &[("day24", cfg!(feature = "day24"))];
+
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<KnownAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnswer {
+    pub day: u8,
+    pub part: u8,
+    /// Input file relative to the workspace root, defaults to the day's
+    /// `src/input1.txt` whatever `AOC_INPUT_DIR` says, as the answers are
+    /// the ones of those inputs.
+    pub input: Option<PathBuf>,
+    /// Solver parameters, the same as passing `--param name=value` to the runner.
+    #[serde(default)]
+    pub params: BTreeMap<String, Value>,
+    pub answer: String,
+    /// Takes too long for a debug build, so only the ignored test checks it.
+    #[serde(default)]
+    pub slow: bool,
+    /// Feature of the runner the puzzle needs.
+    pub feature: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("could not read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("invalid manifest {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("unknown feature {feature:?} in {}", path.display())]
+    UnknownFeature { path: PathBuf, feature: String },
+}
+
+/// Why a known answer was not reproduced.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Mismatch {
+    #[error("no such puzzle in the runner")]
+    UnknownPuzzle,
+    #[error("could not read {0}")]
+    Input(String),
+    #[error("{0}")]
+    Failed(String),
+    #[error("expected {expected}, got {found}")]
+    Wrong { expected: String, found: String },
+}
+
+impl Manifest {
+    /// Read the manifest from the workspace `root`.
+    pub fn load(root: &Path) -> Result<Self, ManifestError> {
+        let path = root.join(MANIFEST);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(source) => return Err(ManifestError::Read { path, source }),
+        };
+        let manifest: Manifest = match toml::from_str(&text) {
+            Ok(manifest) => manifest,
+            Err(source) => return Err(ManifestError::Parse { path, source }),
+        };
+        let unknown = manifest
+            .answers
+            .iter()
+            .filter_map(|known| known.feature.as_ref())
+            .find(|feature| !FEATURES.iter().any(|(name, _)| name == feature));
+        if let Some(feature) = unknown {
+            return Err(ManifestError::UnknownFeature {
+                path,
+                feature: feature.clone(),
+            });
+        }
+        Ok(manifest)
+    }
+}
+
+impl KnownAnswer {
+    /// Whether the runner was built with the feature the puzzle needs.
+    pub fn enabled(&self) -> bool {
+        self.feature
+            .as_ref()
+            .is_none_or(|feature| FEATURES.iter().any(|&(name, on)| name == feature && on))
+    }
+
+    /// Run the solver on the input and compare its answer with the known one.
+    pub fn check(&self, root: &Path) -> Result<(), Mismatch> {
+        let puzzle = find(self.day, self.part).ok_or(Mismatch::UnknownPuzzle)?;
+        let path = match &self.input {
+            Some(input) => root.join(input),
+            None => puzzle.bundled_input(root),
+        };
+        let input = fs::read_to_string(&path)
+            .map_err(|e| Mismatch::Input(format!("{}: {}", path.display(), e)))?;
+
+        let params: Vec<(&str, String)> = self
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_str(), param_value(value)))
+            .collect();
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+
+        let found = puzzle
+            .run(&input, &params)
+            .map_err(Mismatch::Failed)?
+            .answer;
+        if found == self.answer {
+            Ok(())
+        } else {
+            Err(Mismatch::Wrong {
+                expected: self.answer.clone(),
+                found,
+            })
+        }
+    }
+}
+
+/// `steps = 64` and `steps = "64"` both mean `--param steps=64`.
+fn param_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace_root;
+    use std::thread;
+
+    fn check_all(slow: bool) {
+        let root = workspace_root();
+        let manifest = Manifest::load(root).expect("valid manifest");
+        let check = move || {
+            manifest
+                .answers
+                .iter()
+                .filter(|known| known.slow == slow && known.enabled())
+                .filter_map(|known| {
+                    let mismatch = known.check(root).err()?;
+                    Some(format!(
+                        "day {} part {}: {}",
+                        known.day, known.part, mismatch
+                    ))
+                })
+                .collect::<Vec<String>>()
+        };
+        // the recursive solvers need more stack than a test thread gets
+        let failures = thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(check)
+            .expect("spawned checker thread")
+            .join()
+            .expect("checker thread finished");
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn known_answers() {
+        check_all(false);
+    }
+
+    #[test]
+    #[ignore = "takes minutes without optimisations, run with --release"]
+    fn slow_known_answers() {
+        check_all(true);
+    }
+
+    #[test]
+    fn parse_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[answer]]
+            day = 21
+            part = 1
+            input = "day21/src/input1_test.txt"
+            params = { steps = 6 }
+            answer = "16"
+
+            [[answer]]
+            day = 1
+            part = 1
+            answer = "55386"
+            slow = true
+            "#,
+        )
+        .unwrap();
+
+        let [test, real] = &manifest.answers[..] else {
+            panic!("expected two answers, got {:?}", manifest.answers);
+        };
+        assert_eq!(param_value(&test.params["steps"]), "6");
+        assert_eq!(test.check(workspace_root()), Ok(()));
+        assert_eq!(real.input, None);
+        assert!(real.slow);
+    }
+
+    #[test]
+    fn behind_a_feature() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[answer]]
+            day = 24
+            part = 1
+            answer = "16665"
+            feature = "day24"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.answers[0].enabled(), cfg!(feature = "day24"));
+
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(MANIFEST),
+            "[[answer]]\nday = 24\npart = 1\nanswer = \"1\"\nfeature = \"blas\"\n",
+        )
+        .unwrap();
+        let loaded = Manifest::load(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(
+            loaded,
+            Err(ManifestError::UnknownFeature { feature, .. }) if feature == "blas"
+        ));
+    }
+
+    #[test]
+    fn report_wrong_answer() {
+        let known: KnownAnswer = toml::from_str(
+            r#"
+            day = 21
+            part = 1
+            input = "day21/src/input1_test.txt"
+            params = { steps = "6" }
+            answer = "17"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            known.check(workspace_root()),
+            Err(Mismatch::Wrong {
+                expected: "17".to_owned(),
+                found: "16".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Solving the puzzles of a day on every input in a directory, like the
+//! personal inputs of several people, to compare their answers.
+//!
+//! The inputs and parts are solved in parallel, and the results are
+//! summarised as CSV or JSON, one row per input and part:
+//!
+//! ```text
+//! input,day,part,answer,error,parse_ns,solve_ns
+//! alice.txt,6,1,512295,,17407,1382
+//! bob.txt,6,1,,"line 2, column 1: expected ""Distance:"", found 'S'",,
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use rayon::prelude::*;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::Puzzle;
+
+#[derive(Debug, Error)]
+pub enum BatchError {
+    #[error("could not list {}: {source}", path.display())]
+    List { path: PathBuf, source: io::Error },
+}
+
+/// The result of one part on one input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Row {
+    /// Name of the input file inside the directory.
+    pub input: String,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+}
+
+impl Row {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The files in `dir`, sorted by name, leaving out subdirectories and hidden
+/// files.
+pub fn inputs(dir: &Path) -> Result<Vec<PathBuf>, BatchError> {
+    let list = |source| BatchError::List {
+        path: dir.to_owned(),
+        source,
+    };
+    let mut inputs = vec![];
+    for entry in fs::read_dir(dir).map_err(list)? {
+        let entry = entry.map_err(list)?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.file_type().map_err(list)?.is_file() {
+            inputs.push(entry.path());
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Solve every puzzle on every input in parallel, with the rows in the order
+/// of the inputs and then the puzzles. An input that can't be read, or a
+/// solver that fails or panics on it, gives a row with an error.
+pub fn run(puzzles: &[&Puzzle], inputs: &[PathBuf], params: &[(&str, &str)]) -> Vec<Row> {
+    inputs
+        .par_iter()
+        .flat_map(|path| {
+            let text = fs::read_to_string(path).map_err(|e| e.to_string());
+            puzzles.par_iter().map(move |puzzle| {
+                let solved = text
+                    .as_ref()
+                    .map_err(Clone::clone)
+                    .and_then(|text| puzzle.run(text, params));
+                let name = path.file_name().unwrap_or(path.as_os_str());
+                Row {
+                    input: name.to_string_lossy().into_owned(),
+                    day: puzzle.day,
+                    part: puzzle.part,
+                    answer: solved.as_ref().ok().map(|s| s.answer.clone()),
+                    error: solved.as_ref().err().cloned(),
+                    parse_ns: solved.as_ref().ok().map(|s| s.parse_time.as_nanos() as u64),
+                    solve_ns: solved.as_ref().ok().map(|s| s.solve_time.as_nanos() as u64),
+                }
+            })
+        })
+        .collect()
+}
+
+/// The rows as CSV with a header, leaving out what a row doesn't have.
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut csv = "input,day,part,answer,error,parse_ns,solve_ns\n".to_owned();
+    for row in rows {
+        let fields = [
+            row.input.clone(),
+            row.day.to_string(),
+            row.part.to_string(),
+            row.answer.clone().unwrap_or_default(),
+            row.error.clone().unwrap_or_default(),
+            row.parse_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            row.solve_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// The field quoted if it has a comma, quote or line break in it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// The rows as a pretty-printed JSON array.
+pub fn to_json(rows: &[Row]) -> String {
+    serde_json::to_string_pretty(rows).expect("rows as JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    #[test]
+    fn quoted_fields() {
+        assert_eq!(csv_field("288"), "288");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn directory_of_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(
+            dir.join("b.txt"),
+            include_str!("../../day6/src/input1_test.txt"),
+        )
+        .unwrap();
+        fs::write(dir.join("a.txt"), "Time: 7\nSpeed: 9\n").unwrap();
+        fs::write(dir.join(".notes"), "not an input").unwrap();
+
+        let inputs = inputs(&dir).unwrap();
+        assert_eq!(inputs, [dir.join("a.txt"), dir.join("b.txt")]);
+        let puzzles = [find(6, 1).unwrap(), find(6, 2).unwrap()];
+        let rows = run(&puzzles, &inputs, &[]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let summary: Vec<(&str, u8, Option<&str>)> = rows
+            .iter()
+            .map(|row| (row.input.as_str(), row.part, row.answer.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("a.txt", 1, None),
+                ("a.txt", 2, None),
+                ("b.txt", 1, Some("288")),
+                ("b.txt", 2, Some("71503")),
+            ]
+        );
+        assert!(!rows[0].is_ok() && rows[0].parse_ns.is_none());
+        assert!(rows[3].is_ok() && rows[3].solve_ns.is_some());
+
+        let csv = to_csv(&rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "input,day,part,answer,error,parse_ns,solve_ns");
+        assert!(lines[3].starts_with("b.txt,6,1,288,,"));
+        let json: serde_json::Value = serde_json::from_str(&to_json(&rows)).unwrap();
+        assert_eq!(json[3]["answer"], "71503");
+        assert_eq!(json[0]["answer"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn missing_directory() {
+        let dir = std::env::temp_dir().join("aoc-batch-does-not-exist");
+        assert!(matches!(inputs(&dir), Err(BatchError::List { .. })));
+    }
+}
@@ -0,0 +1,359 @@
+//! Timing of the parse and solve steps of every puzzle.
+//!
+//! Timings are compared with a baseline saved by an earlier run, and with the
+//! time budgets in `bench.toml` in the workspace root:
+//!
+//! ```toml
+//! # allowed slowdown against the baseline, in percent
+//! threshold = 20
+//! # budget for parsing and solving a puzzle
+//! budget = "1s"
+//!
+//! [[puzzle]]
+//! day = 5
+//! part = 2
+//! budget = "15m"
+//! ```
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::Puzzle;
+
+/// File name of the budgets in the workspace root.
+pub const CONFIG: &str = "bench.toml";
+/// Default file name of the baseline in the workspace root.
+pub const BASELINE: &str = "bench-baseline.toml";
+
+/// Slowdowns smaller than this are noise, whatever the threshold says.
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+/// How long the parse and solve steps of a puzzle take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub part: u8,
+    #[serde(rename = "parse_ns", with = "nanos")]
+    pub parse: Duration,
+    #[serde(rename = "solve_ns", with = "nanos")]
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Solve the puzzle `runs` times and keep the fastest time of each step,
+/// which is the one least disturbed by whatever else the machine is doing.
+pub fn measure(
+    puzzle: &Puzzle,
+    input: &str,
+    params: &[(&str, &str)],
+    runs: usize,
+) -> Result<Timing, String> {
+    let mut timing = Timing {
+        day: puzzle.day,
+        part: puzzle.part,
+        parse: Duration::MAX,
+        solve: Duration::MAX,
+    };
+    for _ in 0..runs.max(1) {
+        let solved = puzzle.run(input, params)?;
+        timing.parse = timing.parse.min(solved.parse_time);
+        timing.solve = timing.solve.min(solved.solve_time);
+    }
+    Ok(timing)
+}
+
+#[derive(Debug, Error)]
+pub enum BenchError {
+    #[error("could not read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("could not write {}: {source}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+    #[error("invalid {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+fn read_toml<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, BenchError> {
+    let text = fs::read_to_string(path).map_err(|source| BenchError::Read {
+        path: path.to_owned(),
+        source,
+    })?;
+    toml::from_str(&text).map_err(|source| BenchError::Parse {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Timings saved by an earlier run to compare against.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(rename = "timing", default)]
+    pub timings: Vec<Timing>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, BenchError> {
+        read_toml(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        let text = toml::to_string(self).expect("timings serialize to TOML");
+        fs::write(path, text).map_err(|source| BenchError::Write {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Timing> {
+        self.timings.iter().find(|t| t.day == day && t.part == part)
+    }
+
+    /// Replace the timings of the measured puzzles, keeping all others.
+    pub fn update(&mut self, timings: &[Timing]) {
+        for timing in timings {
+            match self
+                .timings
+                .iter_mut()
+                .find(|t| t.day == timing.day && t.part == timing.part)
+            {
+                Some(old) => *old = *timing,
+                None => self.timings.push(*timing),
+            }
+        }
+        self.timings.sort_by_key(|t| (t.day, t.part));
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Allowed slowdown against the baseline, in percent.
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    /// Budget of every puzzle without one of its own.
+    #[serde(default, deserialize_with = "optional_duration")]
+    pub budget: Option<Duration>,
+    #[serde(rename = "puzzle", default)]
+    pub puzzles: Vec<PuzzleBudget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PuzzleBudget {
+    pub day: u8,
+    pub part: u8,
+    #[serde(deserialize_with = "duration")]
+    pub budget: Duration,
+}
+
+fn default_threshold() -> f64 {
+    20.0
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            threshold: default_threshold(),
+            budget: None,
+            puzzles: vec![],
+        }
+    }
+}
+
+impl Config {
+    /// Read the config from the workspace `root`, all defaults if there is none.
+    pub fn load(root: &Path) -> Result<Self, BenchError> {
+        let path = root.join(CONFIG);
+        if path.exists() {
+            read_toml(&path)
+        } else {
+            Ok(Config::default())
+        }
+    }
+
+    pub fn budget(&self, day: u8, part: u8) -> Option<Duration> {
+        self.puzzles
+            .iter()
+            .find(|p| p.day == day && p.part == part)
+            .map(|p| p.budget)
+            .or(self.budget)
+    }
+
+    /// Everything wrong with the timing of a puzzle.
+    pub fn judge(&self, timing: &Timing, baseline: Option<&Timing>) -> Vec<Problem> {
+        let mut problems = vec![];
+        if let Some(baseline) = baseline {
+            let (now, before) = (timing.total(), baseline.total());
+            let percent = (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+            if percent > self.threshold && now - before >= MIN_REGRESSION {
+                problems.push(Problem::Regression {
+                    baseline: before,
+                    percent,
+                });
+            }
+        }
+        if let Some(budget) = self.budget(timing.day, timing.part) {
+            if timing.total() > budget {
+                problems.push(Problem::OverBudget { budget });
+            }
+        }
+        problems
+    }
+}
+
+/// Why a timing is not acceptable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    Regression { baseline: Duration, percent: f64 },
+    OverBudget { budget: Duration },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Regression { baseline, percent } => {
+                write!(f, "{:.0}% slower than {:.2?}", percent, baseline)
+            }
+            Problem::OverBudget { budget } => write!(f, "over budget of {:.2?}", budget),
+        }
+    }
+}
+
+/// Parse a duration like `250ms`, `1.5s` or `15m`.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("expected a number in {:?}", text))?;
+    let seconds = match unit.trim() {
+        "ns" => number / 1e9,
+        "us" | "µs" => number / 1e6,
+        "ms" => number / 1e3,
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        unit => return Err(format!("unknown unit {:?} in {:?}", unit, text)),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse_duration(&text).map_err(serde::de::Error::custom)
+}
+
+fn optional_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    duration(deserializer).map(Some)
+}
+
+mod nanos {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, part: u8, parse_ms: u64, solve_ms: u64) -> Timing {
+        Timing {
+            day,
+            part,
+            parse: Duration::from_millis(parse_ms),
+            solve: Duration::from_millis(solve_ms),
+        }
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_duration("40 us"), Ok(Duration::from_micros(40)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("3 weeks").is_err());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[timing(2, 1, 1, 2), timing(1, 2, 3, 4)]);
+        baseline.update(&[timing(2, 1, 5, 6)]);
+
+        let text = toml::to_string(&baseline).unwrap();
+        let loaded: Baseline = toml::from_str(&text).unwrap();
+        assert_eq!(loaded.timings, vec![timing(1, 2, 3, 4), timing(2, 1, 5, 6)]);
+        assert_eq!(loaded.get(2, 1), Some(&timing(2, 1, 5, 6)));
+        assert_eq!(loaded.get(2, 2), None);
+    }
+
+    #[test]
+    fn judge_timings() {
+        let config: Config = toml::from_str(
+            r#"
+            threshold = 50
+            budget = "100ms"
+
+            [[puzzle]]
+            day = 5
+            part = 2
+            budget = "1s"
+            "#,
+        )
+        .unwrap();
+
+        let before = timing(1, 1, 10, 20);
+        assert_eq!(config.judge(&timing(1, 1, 10, 30), Some(&before)), vec![]);
+        assert_eq!(
+            config.judge(&timing(1, 1, 10, 50), Some(&before)),
+            vec![Problem::Regression {
+                baseline: Duration::from_millis(30),
+                percent: 100.0
+            }]
+        );
+        assert_eq!(
+            config.judge(&timing(1, 1, 50, 60), None),
+            vec![Problem::OverBudget {
+                budget: Duration::from_millis(100)
+            }]
+        );
+        assert_eq!(config.judge(&timing(5, 2, 50, 60), None), vec![]);
+
+        // tiny timings jitter by more than any sensible threshold
+        let before = Timing {
+            solve: Duration::from_micros(20),
+            ..timing(1, 1, 0, 0)
+        };
+        let now = Timing {
+            solve: Duration::from_micros(60),
+            ..before
+        };
+        assert_eq!(config.judge(&now, Some(&before)), vec![]);
+    }
+}
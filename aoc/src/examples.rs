@@ -0,0 +1,11 @@
+//! Tests generated from the `examples.toml` of every day, see `build.rs`.
+
+use crate::find;
+
+fn check(day: u8, part: u8, input: &str, params: &[(&str, &str)], answer: &str) {
+    let puzzle = find(day, part).expect("puzzle in the runner");
+    let solved = puzzle.run(input, params);
+    assert_eq!(solved.map(|solved| solved.answer), Ok(answer.to_owned()));
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
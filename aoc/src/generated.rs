@@ -0,0 +1,69 @@
+//! Solve small generated inputs of every day with both parts, which catches
+//! solvers that only work on inputs like the real one.
+
+use crate::find;
+
+const SEEDS: u64 = 5;
+
+/// Parameters of each day's generator that keep the inputs small.
+const SMALL: &[(u8, &[(&str, &str)])] = &[
+    (1, &[("lines", "50")]),
+    (2, &[("games", "20")]),
+    (3, &[("width", "30"), ("height", "20")]),
+    (4, &[("cards", "30")]),
+    (
+        5,
+        &[
+            ("seed_ranges", "4"),
+            ("seed_range", "1000"),
+            ("ranges", "8"),
+        ],
+    ),
+    (6, &[("races", "3")]),
+    (7, &[("hands", "100")]),
+    (
+        8,
+        &[("instructions", "20"), ("ghosts", "3"), ("cycle", "20")],
+    ),
+    (9, &[("sequences", "20")]),
+    (10, &[("width", "20"), ("height", "15")]),
+    (
+        11,
+        &[("width", "30"), ("height", "30"), ("galaxies", "0.05")],
+    ),
+    (12, &[("rows", "50"), ("length", "14")]),
+    (13, &[("patterns", "20"), ("size", "11")]),
+    (14, &[("width", "20"), ("height", "20")]),
+    (15, &[("steps", "200"), ("labels", "30")]),
+    (16, &[("width", "20"), ("height", "20")]),
+    (17, &[("width", "20"), ("height", "20")]),
+    (18, &[("width", "5"), ("height", "4")]),
+    (19, &[("workflows", "30"), ("parts", "20")]),
+    (20, &[("counters", "3"), ("bits", "6")]),
+    (21, &[("size", "21")]),
+    (22, &[("bricks", "60"), ("width", "4"), ("depth", "4")]),
+    (23, &[("width", "21"), ("height", "21"), ("loops", "4")]),
+    (24, &[("stones", "30")]),
+];
+
+#[test]
+fn solve_generated_inputs() {
+    for &(day, params) in SMALL {
+        let generator = generate::find(day).expect("generator for every day");
+        for seed in 0..SEEDS {
+            let input = generator.generate(seed, params).expect("valid parameters");
+            for part in 1..=2 {
+                // day 24 is only there with its feature
+                let Some(puzzle) = find(day, part) else {
+                    continue;
+                };
+                if let Err(e) = puzzle.run(&input, &[]) {
+                    panic!(
+                        "day {} part {} failed on seed {}: {}\n{}",
+                        day, part, seed, e, input
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    error::Error,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use animation::{Animate, Frame, Recorder};
+use common::{
+    diagnostics::{self, Diagnostic},
+    input::normalize,
+    Params, Solution,
+};
+use raster::{Image, Render};
+use serde::Serialize;
+
+pub mod answers;
+pub mod batch;
+pub mod bench;
+#[cfg(test)]
+mod examples;
+#[cfg(test)]
+mod generated;
+pub mod scaffold;
+
+/// Solve the puzzle text with the given `name=value` parameter overrides.
+pub type SolveFn = fn(&str, &[(&str, &str)]) -> Result<Solved, Box<dyn Error>>;
+
+/// Draw the state the puzzle was solved in, with the same parameters as [`SolveFn`].
+pub type RenderFn = fn(&str, &[(&str, &str)]) -> Result<Image, Box<dyn Error>>;
+
+/// Record the simulation of the puzzle, keeping at most the given number of frames.
+pub type AnimateFn = fn(&str, &[(&str, &str)], usize) -> Result<Vec<Frame>, Box<dyn Error>>;
+
+/// Parse the puzzle text into its model as pretty-printed JSON, with the same
+/// parameters as [`SolveFn`].
+pub type DumpFn = fn(&str, &[(&str, &str)]) -> Result<String, Box<dyn Error>>;
+
+/// Answer of a puzzle and how long its parse and solve steps took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solved {
+    pub answer: String,
+    /// All parameters of the solver with the values used, in their text form.
+    pub params: Vec<(&'static str, String)>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// What the solver reported on the way, see [`common::diagnostics`].
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A single puzzle (one part of one day) that the runner can dispatch to.
+pub struct Puzzle {
+    pub day: u8,
+    pub part: u8,
+    pub solve: SolveFn,
+    /// Only set for the puzzles with a grid worth looking at.
+    pub render: Option<RenderFn>,
+    /// Only set for the puzzles that simulate something step by step.
+    pub animate: Option<AnimateFn>,
+    /// Only set for the puzzles whose parsed model can be serialized.
+    pub dump: Option<DumpFn>,
+}
+
+impl Puzzle {
+    /// Path of the puzzle input inside the workspace, e.g. `day17/src/input1.txt`.
+    pub fn bundled_input(&self, root: &Path) -> PathBuf {
+        root.join(format!("day{}", self.day))
+            .join("src")
+            .join("input1.txt")
+    }
+
+    /// Path of the input to use when none is named: the day's file in the
+    /// directory of `AOC_INPUT_DIR` if that is set, the bundled one otherwise.
+    pub fn default_input(&self, root: &Path) -> PathBuf {
+        common::input::default_path(self.day, &root.join(format!("day{}", self.day)))
+    }
+
+    /// Solve the puzzle text, turning a panicking solver into an error as well.
+    pub fn run(&self, input: &str, params: &[(&str, &str)]) -> Result<Solved, String> {
+        let input = normalize(input);
+        panic::catch_unwind(AssertUnwindSafe(|| (self.solve)(&input, params)))
+            .map_err(|_| "solver panicked".to_owned())
+            .and_then(|result| result.map_err(|e| e.to_string()))
+    }
+
+    /// Render the puzzle text, or `None` if the puzzle has no renderer.
+    pub fn render(&self, input: &str, params: &[(&str, &str)]) -> Option<Result<Image, String>> {
+        let render = self.render?;
+        let input = normalize(input);
+        Some(
+            panic::catch_unwind(AssertUnwindSafe(|| render(&input, params)))
+                .map_err(|_| "renderer panicked".to_owned())
+                .and_then(|result| result.map_err(|e| e.to_string())),
+        )
+    }
+
+    /// Record the frames of the simulation, or `None` if the puzzle has none.
+    pub fn animate(
+        &self,
+        input: &str,
+        params: &[(&str, &str)],
+        limit: usize,
+    ) -> Option<Result<Vec<Frame>, String>> {
+        let animate = self.animate?;
+        let input = normalize(input);
+        Some(
+            panic::catch_unwind(AssertUnwindSafe(|| animate(&input, params, limit)))
+                .map_err(|_| "simulation panicked".to_owned())
+                .and_then(|result| result.map_err(|e| e.to_string())),
+        )
+    }
+
+    /// The parsed model of the puzzle text as JSON, or `None` if the puzzle
+    /// can't be dumped.
+    pub fn dump(&self, input: &str, params: &[(&str, &str)]) -> Option<Result<String, String>> {
+        let dump = self.dump?;
+        let input = normalize(input);
+        Some(
+            panic::catch_unwind(AssertUnwindSafe(|| dump(&input, params)))
+                .map_err(|_| "parser panicked".to_owned())
+                .and_then(|result| result.map_err(|e| e.to_string())),
+        )
+    }
+}
+
+/// Root of the workspace, which the default inputs and manifests are relative to.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the workspace")
+}
+
+fn solve<S>(input: &str, params: &[(&str, &str)]) -> Result<Solved, Box<dyn Error>>
+where
+    S: Solution,
+    S::Error: Error + 'static,
+{
+    let params = S::Params::from_pairs(params.iter().copied())?;
+
+    let (solved, diagnostics) = diagnostics::collect(|| {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        let answer = S::solve(&parsed, &params)?;
+        let solve_time = start.elapsed();
+        Ok::<_, S::Error>((answer, parse_time, solve_time))
+    });
+    let (answer, parse_time, solve_time) = solved?;
+
+    Ok(Solved {
+        answer: answer.to_string(),
+        params: params.values(),
+        parse_time,
+        solve_time,
+        diagnostics,
+    })
+}
+
+fn render<S>(input: &str, params: &[(&str, &str)]) -> Result<Image, Box<dyn Error>>
+where
+    S: Render,
+    S::Error: Error + 'static,
+{
+    let params = S::Params::from_pairs(params.iter().copied())?;
+    let parsed = S::parse(input)?;
+    Ok(S::render(&parsed, &params)?)
+}
+
+fn animate<S>(
+    input: &str,
+    params: &[(&str, &str)],
+    limit: usize,
+) -> Result<Vec<Frame>, Box<dyn Error>>
+where
+    S: Animate,
+    S::Error: Error + 'static,
+{
+    let params = S::Params::from_pairs(params.iter().copied())?;
+    let parsed = S::parse(input)?;
+    let mut recorder = Recorder::new(limit);
+    S::animate(&parsed, &params, &mut recorder)?;
+    Ok(recorder.into_frames())
+}
+
+fn dump<S>(input: &str, params: &[(&str, &str)]) -> Result<String, Box<dyn Error>>
+where
+    S: Solution,
+    S::Error: Error + 'static,
+    for<'a> S::Input<'a>: Serialize,
+{
+    // reject unknown parameters like the other steps do
+    S::Params::from_pairs(params.iter().copied())?;
+    let parsed = S::parse(input)?;
+    Ok(serde_json::to_string_pretty(&parsed)?)
+}
+
+/// A puzzle with its solver, followed by `render`, `animate` and `dump` for
+/// the solvers that also implement those.
+macro_rules! puzzle {
+    ($day:literal, $part:literal, $solution:ty $(, $extra:ident)*) => {{
+        #[allow(unused_mut)]
+        let mut puzzle = Puzzle {
+            day: $day,
+            part: $part,
+            solve: solve::<$solution>,
+            render: None,
+            animate: None,
+            dump: None,
+        };
+        $(puzzle.$extra = Some($extra::<$solution>);)*
+        puzzle
+    }};
+}
+
+/// All puzzles in the workspace, ordered by day and part.
+pub static PUZZLES: &[Puzzle] = &[
+    puzzle!(1, 1, day1::part1::Part1),
+    puzzle!(1, 2, day1::part2::Part2),
+    puzzle!(2, 1, day2::part1::Part1, dump),
+    puzzle!(2, 2, day2::part2::Part2, dump),
+    puzzle!(3, 1, day3::part1::Part1),
+    puzzle!(3, 2, day3::part2::Part2),
+    puzzle!(4, 1, day4::part1::Part1, dump),
+    puzzle!(4, 2, day4::part2::Part2, dump),
+    puzzle!(5, 1, day5::part1::Part1, dump),
+    puzzle!(5, 2, day5::part2::Part2, dump),
+    puzzle!(6, 1, day6::part1::Part1),
+    puzzle!(6, 2, day6::part2::Part2),
+    puzzle!(7, 1, day7::part1::Part1),
+    puzzle!(7, 2, day7::part2::Part2),
+    puzzle!(8, 1, day8::part1::Part1),
+    puzzle!(8, 2, day8::part2::Part2),
+    puzzle!(9, 1, day9::part1::Part1),
+    puzzle!(9, 2, day9::part2::Part2),
+    puzzle!(10, 1, day10::part1::Part1),
+    puzzle!(10, 2, day10::part2::Part2, render),
+    puzzle!(11, 1, day11::part1::Part1),
+    puzzle!(11, 2, day11::part2::Part2),
+    puzzle!(12, 1, day12::part1::Part1),
+    puzzle!(12, 2, day12::part2::Part2),
+    puzzle!(13, 1, day13::part1::Part1),
+    puzzle!(13, 2, day13::part2::Part2),
+    puzzle!(14, 1, day14::part1::Part1),
+    puzzle!(14, 2, day14::part2::Part2, render, animate),
+    puzzle!(15, 1, day15::part1::Part1),
+    puzzle!(15, 2, day15::part2::Part2),
+    puzzle!(16, 1, day16::part1::Part1, render, animate),
+    puzzle!(16, 2, day16::part2::Part2),
+    puzzle!(17, 1, day17::part1::Part1, render),
+    puzzle!(17, 2, day17::part2::Part2, render),
+    puzzle!(18, 1, day18::part1::Part1),
+    puzzle!(18, 2, day18::part2::Part2),
+    puzzle!(19, 1, day19::part1::Part1, dump),
+    puzzle!(19, 2, day19::part2::Part2, dump),
+    puzzle!(20, 1, day20::part1::Part1, animate, dump),
+    puzzle!(20, 2, day20::part2::Part2, dump),
+    puzzle!(21, 1, day21::part1::Part1, render, animate),
+    puzzle!(21, 2, day21::part2::Part2),
+    puzzle!(22, 1, day22::part1::Part1, animate, dump),
+    puzzle!(22, 2, day22::part2::Part2, dump),
+    puzzle!(23, 1, day23::part1::Part1, render),
+    puzzle!(23, 2, day23::part2::Part2),
+    #[cfg(feature = "day24")]
+    puzzle!(24, 1, day24::part1::Part1),
+    #[cfg(feature = "day24")]
+    puzzle!(24, 2, day24::part2::Part2),
+];
+
+/// Find the puzzle for the given day and part.
+pub fn find(day: u8, part: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day && p.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::diagnostics::Value;
+
+    use super::*;
+
+    #[test]
+    fn report_params_and_diagnostics() {
+        let example = include_str!("../../day14/src/input1_test.txt");
+        let solved = find(14, 2).unwrap().run(example, &[]).unwrap();
+        assert_eq!(solved.answer, "64");
+        assert!(solved
+            .diagnostics
+            .contains(&("cycle_length", Value::Integer(7))));
+
+        let example = include_str!("../../day11/src/input1_test.txt");
+        let solved = find(11, 2)
+            .unwrap()
+            .run(example, &[("scale_factor", "10")])
+            .unwrap();
+        assert_eq!(solved.params, vec![("scale_factor", "10".to_owned())]);
+        assert!(solved.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn dump_parsed_models() {
+        let example = include_str!("../../day22/src/input1_test.txt");
+        let json = find(22, 1).unwrap().dump(example, &[]).unwrap().unwrap();
+        let bricks: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(bricks.as_array().unwrap().len(), 7);
+        assert_eq!(
+            bricks[0]["start"],
+            serde_json::json!({"x": 1, "y": 0, "z": 1})
+        );
+
+        assert!(find(3, 1).unwrap().dump(example, &[]).is_none());
+        assert!(find(2, 1)
+            .unwrap()
+            .dump("not a game\n", &[])
+            .unwrap()
+            .is_err());
+    }
+}
@@ -0,0 +1,869 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    answers::Manifest,
+    batch,
+    bench::{self, Baseline, Config, Timing},
+    find, scaffold, workspace_root, Puzzle, Solved, PUZZLES,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use common::{
+    diagnostics::Value,
+    init_logging,
+    input::{InputError, Source},
+    Verbosity,
+};
+use raster::Format;
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// How much the solvers log: quiet, info, debug or trace
+    #[arg(long, global = true, default_value_t = Verbosity::Quiet)]
+    log: Verbosity,
+
+    /// Write the log to this file instead of stderr
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or more puzzles and print a table of results
+    Run(RunArgs),
+    /// Run the puzzles of a day on every input in a directory and summarise
+    /// the answers as CSV or JSON
+    Batch(BatchArgs),
+    /// Compare the answers on the real inputs with the ones in `answers.toml`
+    Check(CheckArgs),
+    /// Time the parse and solve steps, comparing them with the baseline and `bench.toml`
+    Bench(BenchArgs),
+    /// Draw the grid of a puzzle with its solution as a PNG or PPM image
+    Render(RenderArgs),
+    /// Play the simulation of a puzzle step by step in the terminal
+    #[cfg(feature = "tui")]
+    Animate(AnimateArgs),
+    /// Write a random puzzle input in the format of a day
+    Generate(GenerateArgs),
+    /// Print the parsed model of a puzzle input as JSON
+    Dump(DumpArgs),
+    /// Create the crate of a new day and register it with the runner
+    New(NewArgs),
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct Selection {
+    /// Day to run
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Run every day and part
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Part to run, defaults to both parts
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file or `-` for stdin, defaults to the day's file in
+    /// `$AOC_INPUT_DIR` or else its `src/input1.txt`
+    #[arg(long, value_parser = parse_source, requires = "day")]
+    input: Option<Source>,
+
+    /// Solver parameter as `name=value`, e.g. `steps=500` on day 21, which
+    /// defaults to 64
+    #[arg(long = "param", requires = "day", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Print the results as JSON, with the parameters, timings and
+    /// diagnostics of every puzzle
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Day to run
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part to run, defaults to both parts
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Directory of the inputs, every file in it is one
+    #[arg(long)]
+    dir: PathBuf,
+
+    /// Solver parameter as `name=value`, e.g. `steps=500` on day 21, which
+    /// defaults to 64
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Format of the summary
+    #[arg(long, value_enum, default_value_t = SummaryFormat::Csv)]
+    format: SummaryFormat,
+
+    /// File to write the summary to, defaults to stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SummaryFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Skip the answers marked as slow
+    #[arg(long)]
+    skip_slow: bool,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Part to time, defaults to both parts
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// How often to solve every puzzle, the fastest run counts
+    #[arg(long, default_value_t = 5)]
+    runs: usize,
+
+    /// Baseline file, defaults to `bench-baseline.toml` in the workspace root
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Save the timings to the baseline file
+    #[arg(long)]
+    save: bool,
+
+    /// Allowed slowdown against the baseline in percent, overrides `bench.toml`
+    #[arg(long)]
+    threshold: Option<f64>,
+}
+
+#[derive(Args)]
+struct RenderArgs {
+    /// Day to render
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part to render
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Input file or `-` for stdin, defaults to the day's file in
+    /// `$AOC_INPUT_DIR` or else its `src/input1.txt`
+    #[arg(long, value_parser = parse_source)]
+    input: Option<Source>,
+
+    /// Solver parameter as `name=value`, e.g. `steps=500` on day 21, which
+    /// defaults to 64
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Image to write, the format follows the extension: `.png` or `.ppm`
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// Width and height of a tile in pixels
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..=64))]
+    scale: u64,
+}
+
+#[cfg(feature = "tui")]
+#[derive(Args)]
+struct AnimateArgs {
+    /// Day to animate
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part to animate
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Input file or `-` for stdin, defaults to the day's file in
+    /// `$AOC_INPUT_DIR` or else its `src/input1.txt`
+    #[arg(long, value_parser = parse_source)]
+    input: Option<Source>,
+
+    /// Solver parameter as `name=value`, e.g. `steps=500` on day 21, which
+    /// defaults to 64
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Frames per second to start with
+    #[arg(long, default_value_t = 10.0)]
+    fps: f64,
+
+    /// Most frames to keep, longer simulations skip evenly spaced steps
+    #[arg(long, default_value_t = 2000)]
+    frames: usize,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to generate an input for
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Seed of the random numbers, the same seed gives the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Generator parameter as `name=value`, e.g. `width=20` on day 10
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// File to write, defaults to stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct DumpArgs {
+    /// Day to dump
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part whose parser to use
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Input file or `-` for stdin, defaults to the day's file in
+    /// `$AOC_INPUT_DIR` or else its `src/input1.txt`
+    #[arg(long, value_parser = parse_source)]
+    input: Option<Source>,
+
+    /// Solver parameter as `name=value`, e.g. `steps=500` on day 21, which
+    /// defaults to 64
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day to create
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Workspace to create it in, defaults to this one
+    #[arg(long)]
+    root: Option<PathBuf>,
+}
+
+fn parse_source(arg: &str) -> Result<Source, String> {
+    Ok(Source::from_arg(arg))
+}
+
+/// Read the named input, or the default input of the puzzle.
+fn read_input(puzzle: &Puzzle, input: Option<&Source>) -> Result<String, InputError> {
+    match input {
+        Some(source) => source.read(),
+        None => Source::File(puzzle.default_input(workspace_root())).read(),
+    }
+}
+
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    param
+        .split_once('=')
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("expected name=value, got {:?}", param))
+}
+
+struct PuzzleResult {
+    day: u8,
+    part: u8,
+    solved: Result<Solved, String>,
+    time: Duration,
+}
+
+/// A puzzle in the output of `run --json`.
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    day: u8,
+    part: u8,
+    answer: Option<&'a str>,
+    error: Option<&'a str>,
+    params: BTreeMap<&'a str, &'a str>,
+    parse_ns: Option<u64>,
+    solve_ns: Option<u64>,
+    diagnostics: BTreeMap<&'a str, serde_json::Value>,
+}
+
+impl<'a> From<&'a PuzzleResult> for JsonResult<'a> {
+    fn from(result: &'a PuzzleResult) -> Self {
+        let solved = result.solved.as_ref().ok();
+        JsonResult {
+            day: result.day,
+            part: result.part,
+            answer: solved.map(|solved| solved.answer.as_str()),
+            error: result.solved.as_ref().err().map(String::as_str),
+            params: solved
+                .iter()
+                .flat_map(|solved| &solved.params)
+                .map(|(name, value)| (*name, value.as_str()))
+                .collect(),
+            parse_ns: solved.map(|solved| solved.parse_time.as_nanos() as u64),
+            solve_ns: solved.map(|solved| solved.solve_time.as_nanos() as u64),
+            diagnostics: solved
+                .iter()
+                .flat_map(|solved| &solved.diagnostics)
+                .map(|(name, value)| (*name, json_value(value)))
+                .collect(),
+        }
+    }
+}
+
+fn json_value(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Integer(n) => i64::try_from(*n)
+            .map(serde_json::Value::from)
+            .or_else(|_| u64::try_from(*n).map(serde_json::Value::from))
+            // beyond the numbers JSON readers are sure to handle
+            .unwrap_or_else(|_| n.to_string().into()),
+        Value::Float(x) => (*x).into(),
+        Value::Text(text) => text.as_str().into(),
+        Value::List(values) => values.iter().map(json_value).collect(),
+    }
+}
+
+fn run_puzzle(
+    puzzle: &Puzzle,
+    input: Result<String, String>,
+    params: &[(&str, &str)],
+) -> PuzzleResult {
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            return PuzzleResult {
+                day: puzzle.day,
+                part: puzzle.part,
+                solved: Err(e),
+                time: Duration::ZERO,
+            }
+        }
+    };
+
+    let start = Instant::now();
+    // keep going with the remaining puzzles if a solver panics on its input
+    let solved = puzzle.run(&input, params);
+
+    PuzzleResult {
+        day: puzzle.day,
+        part: puzzle.part,
+        solved,
+        time: start.elapsed(),
+    }
+}
+
+fn print_table(results: &[PuzzleResult]) {
+    let answers: Vec<String> = results
+        .iter()
+        .map(|r| match &r.solved {
+            Ok(solved) => solved.answer.clone(),
+            Err(e) => format!("error: {}", e),
+        })
+        .collect();
+    let width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max(6);
+
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}",
+        "Day", "Part", "Answer", "Time"
+    );
+    for (result, answer) in results.iter().zip(answers) {
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>12}",
+            result.day,
+            result.part,
+            answer,
+            format!("{:.2?}", result.time)
+        );
+    }
+
+    let total: Duration = results.iter().map(|r| r.time).sum();
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>12}",
+        "",
+        "",
+        "Total",
+        format!("{:.2?}", total)
+    );
+}
+
+fn select(selection: &Selection, part: Option<u8>) -> Vec<&'static Puzzle> {
+    PUZZLES
+        .iter()
+        .filter(|p| selection.all || selection.day == Some(p.day))
+        .filter(|p| part.is_none_or(|part| part == p.part))
+        .collect()
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let puzzles = select(&args.selection, args.part);
+    if puzzles.is_empty() {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    }
+
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    // read a named input once, stdin can't be read again for the second part
+    let named_input = args
+        .input
+        .map(|source| source.read().map_err(|e| e.to_string()));
+    let results: Vec<PuzzleResult> = puzzles
+        .into_iter()
+        .map(|puzzle| {
+            let input = match &named_input {
+                Some(input) => input.clone(),
+                None => read_input(puzzle, None).map_err(|e| e.to_string()),
+            };
+            run_puzzle(puzzle, input, &params)
+        })
+        .collect();
+
+    if args.json {
+        let json: Vec<JsonResult> = results.iter().map(JsonResult::from).collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json).expect("results as JSON")
+        );
+    } else {
+        print_table(&results);
+    }
+
+    if results.iter().any(|r| r.solved.is_err()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_batch(args: BatchArgs) -> ExitCode {
+    let puzzles: Vec<&Puzzle> = PUZZLES
+        .iter()
+        .filter(|p| p.day == args.day && args.part.is_none_or(|part| part == p.part))
+        .collect();
+    if puzzles.is_empty() {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    }
+    let inputs = match batch::inputs(&args.dir) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    let start = Instant::now();
+    let rows = batch::run(&puzzles, &inputs, &params);
+    let summary = match args.format {
+        SummaryFormat::Csv => batch::to_csv(&rows),
+        SummaryFormat::Json => batch::to_json(&rows) + "\n",
+    };
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, summary) {
+                eprintln!("could not write {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", summary),
+    }
+
+    let failures = rows.iter().filter(|row| !row.is_ok()).count();
+    eprintln!(
+        "Solved {} of {} puzzles on {} inputs in {:.2?}",
+        rows.len() - failures,
+        rows.len(),
+        inputs.len(),
+        start.elapsed()
+    );
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn check(args: CheckArgs) -> ExitCode {
+    let root = workspace_root();
+    let manifest = match Manifest::load(root) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answers: Vec<_> = manifest
+        .answers
+        .iter()
+        .filter(|known| known.enabled() && !(args.skip_slow && known.slow))
+        .collect();
+
+    let mut mismatches = 0;
+    for known in &answers {
+        let status = match known.check(root) {
+            Ok(()) => "ok".to_owned(),
+            Err(mismatch) => {
+                mismatches += 1;
+                mismatch.to_string()
+            }
+        };
+        println!("{:>3}  {:>4}  {}", known.day, known.part, status);
+    }
+
+    if mismatches > 0 {
+        eprintln!("{} of {} answers differ", mismatches, answers.len());
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let puzzles = select(&args.selection, args.part);
+    if puzzles.is_empty() {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    }
+
+    let root = workspace_root();
+    let mut config = match Config::load(root) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(threshold) = args.threshold {
+        config.threshold = threshold;
+    }
+    let baseline_path = args.baseline.unwrap_or_else(|| root.join(bench::BASELINE));
+    let mut baseline = if baseline_path.exists() {
+        match Baseline::load(&baseline_path) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Baseline::default()
+    };
+
+    let mut timings: Vec<Timing> = vec![];
+    let mut failures = 0;
+    println!(
+        "{:>3}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}  Status",
+        "Day", "Part", "Parse", "Solve", "Total", "Baseline"
+    );
+    for puzzle in puzzles {
+        let timing = read_input(puzzle, None)
+            .map_err(|e| e.to_string())
+            .and_then(|input| bench::measure(puzzle, &input, &[], args.runs));
+        let timing = match timing {
+            Ok(timing) => timing,
+            Err(e) => {
+                failures += 1;
+                println!("{:>3}  {:>4}  error: {}", puzzle.day, puzzle.part, e);
+                continue;
+            }
+        };
+
+        let before = baseline.get(puzzle.day, puzzle.part);
+        let problems = config.judge(&timing, before);
+        let status = if problems.is_empty() {
+            "ok".to_owned()
+        } else {
+            failures += 1;
+            problems
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        println!(
+            "{:>3}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {}",
+            puzzle.day,
+            puzzle.part,
+            format!("{:.2?}", timing.parse),
+            format!("{:.2?}", timing.solve),
+            format!("{:.2?}", timing.total()),
+            before.map_or("-".to_owned(), |b| format!("{:.2?}", b.total())),
+            status
+        );
+        timings.push(timing);
+    }
+
+    if args.save {
+        baseline.update(&timings);
+        if let Err(e) = baseline.save(&baseline_path) {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn render(args: RenderArgs) -> ExitCode {
+    let Some(puzzle) = find(args.day, args.part) else {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    };
+    if Format::from_path(&args.output).is_none() {
+        eprintln!("{} is neither .png nor .ppm", args.output.display());
+        return ExitCode::FAILURE;
+    }
+
+    let input = match read_input(puzzle, args.input.as_ref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    let image = match puzzle.render(&input, &params) {
+        Some(Ok(image)) => raster::scale(&image, args.scale as usize),
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        None => {
+            let renderable: Vec<String> = PUZZLES
+                .iter()
+                .filter(|p| p.render.is_some())
+                .map(|p| format!("{}/{}", p.day, p.part))
+                .collect();
+            eprintln!(
+                "Day {} part {} has no renderer, try one of {}",
+                args.day,
+                args.part,
+                renderable.join(", ")
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = raster::save(&image, &args.output) {
+        eprintln!("could not write {}: {}", args.output.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "Wrote {}x{} image to {}",
+        image.width(),
+        image.height(),
+        args.output.display()
+    );
+    ExitCode::SUCCESS
+}
+
+#[cfg(feature = "tui")]
+fn animate(args: AnimateArgs) -> ExitCode {
+    let Some(puzzle) = find(args.day, args.part) else {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    };
+
+    let input = match read_input(puzzle, args.input.as_ref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    let frames = match puzzle.animate(&input, &params, args.frames) {
+        Some(Ok(frames)) => frames,
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        None => {
+            let animated: Vec<String> = PUZZLES
+                .iter()
+                .filter(|p| p.animate.is_some())
+                .map(|p| format!("{}/{}", p.day, p.part))
+                .collect();
+            eprintln!(
+                "Day {} part {} has no animation, try one of {}",
+                args.day,
+                args.part,
+                animated.join(", ")
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = animation::play(&frames, args.fps) {
+        eprintln!("could not play the animation: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn generate(args: GenerateArgs) -> ExitCode {
+    let Some(generator) = generate::find(args.day) else {
+        eprintln!("No generator found for day {}", args.day);
+        return ExitCode::FAILURE;
+    };
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    let input = match generator.generate(args.seed, &params) {
+        Ok(input) => input,
+        Err(e) => {
+            let defaults: Vec<String> = generator
+                .params()
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            eprintln!("{}, day {} takes {}", e, args.day, defaults.join(", "));
+            return ExitCode::FAILURE;
+        }
+    };
+    match args.output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, input) {
+                eprintln!("could not write {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", input),
+    }
+    ExitCode::SUCCESS
+}
+
+fn dump(args: DumpArgs) -> ExitCode {
+    let Some(puzzle) = find(args.day, args.part) else {
+        eprintln!("No puzzle found for the given day and part");
+        return ExitCode::FAILURE;
+    };
+
+    let input = match read_input(puzzle, args.input.as_ref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let params: Vec<(&str, &str)> = args
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    match puzzle.dump(&input, &params) {
+        Some(Ok(json)) => println!("{}", json),
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        None => {
+            let dumpable: Vec<String> = PUZZLES
+                .iter()
+                .filter(|p| p.dump.is_some())
+                .map(|p| format!("{}/{}", p.day, p.part))
+                .collect();
+            eprintln!(
+                "Day {} part {} has no serializable model, try one of {}",
+                args.day,
+                args.part,
+                dumpable.join(", ")
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn new_day(args: NewArgs) -> ExitCode {
+    let root = args.root.as_deref().unwrap_or(workspace_root());
+    match scaffold::create_day(root, args.day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(e) = init_logging(cli.log, cli.log_file.as_deref()) {
+        eprintln!("could not set up logging: {}", e);
+        return ExitCode::FAILURE;
+    }
+    let code = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Batch(args) => run_batch(args),
+        Command::Check(args) => check(args),
+        Command::Bench(args) => bench(args),
+        Command::Render(args) => render(args),
+        #[cfg(feature = "tui")]
+        Command::Animate(args) => animate(args),
+        Command::Generate(args) => generate(args),
+        Command::Dump(args) => dump(args),
+        Command::New(args) => new_day(args),
+    };
+    log::logger().flush();
+    code
+}
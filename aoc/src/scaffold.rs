@@ -0,0 +1,371 @@
+//! Creating the crate of a new day from the template in `aoc/templates/day`.
+//!
+//! A new `dayN` crate gets the same layout as the others: a library with a
+//! module per part, each implementing [`Solution`](common::Solution) with a
+//! line parser stub and an example test, a binary per part, empty inputs and
+//! an `examples.toml` whose examples are ignored until their answers are
+//! filled in. The workspace picks the crate up by its name, and the day is
+//! added to the runner's dependencies and [`PUZZLES`](crate::PUZZLES).
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+/// Files of a day crate, relative to its directory, with their templates and
+/// the part they are for. `{{day}}` and `{{part}}` stand for the numbers of
+/// the day and part.
+const TEMPLATES: &[(&str, &str, Option<u8>)] = &[
+    (
+        "Cargo.toml",
+        include_str!("../templates/day/Cargo.toml.tmpl"),
+        None,
+    ),
+    (
+        "examples.toml",
+        include_str!("../templates/day/examples.toml.tmpl"),
+        None,
+    ),
+    (
+        "src/lib.rs",
+        include_str!("../templates/day/lib.rs.tmpl"),
+        None,
+    ),
+    (
+        "src/error.rs",
+        include_str!("../templates/day/error.rs.tmpl"),
+        None,
+    ),
+    (
+        "src/part1.rs",
+        include_str!("../templates/day/part.rs.tmpl"),
+        Some(1),
+    ),
+    (
+        "src/part2.rs",
+        include_str!("../templates/day/part.rs.tmpl"),
+        Some(2),
+    ),
+    (
+        "src/bin/part1.rs",
+        include_str!("../templates/day/bin.rs.tmpl"),
+        Some(1),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../templates/day/bin.rs.tmpl"),
+        Some(2),
+    ),
+    ("src/input1.txt", "", None),
+    ("src/input1_test.txt", "", None),
+];
+
+/// Manifest of the runner, relative to the workspace root.
+const RUNNER_MANIFEST: &str = "aoc/Cargo.toml";
+/// Source listing the runner's puzzles, relative to the workspace root.
+const RUNNER_PUZZLES: &str = "aoc/src/lib.rs";
+
+#[derive(Debug, Error)]
+pub enum ScaffoldError {
+    #[error("{} already exists", .0.display())]
+    Exists(PathBuf),
+    #[error("day {day} is already registered in {}", path.display())]
+    Registered { day: u8, path: PathBuf },
+    #[error("no {what} in {}", path.display())]
+    NoRunner { what: &'static str, path: PathBuf },
+    #[error("could not read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("could not write {}: {source}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+}
+
+/// Create the crate of `day` in the workspace `root` and register it with
+/// the runner, returning the files written.
+///
+/// Nothing is written if the crate exists or the runner can't take the day.
+pub fn create_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    let manifest_path = root.join(RUNNER_MANIFEST);
+    let manifest = register_dependency(&read(&manifest_path)?, day, &manifest_path)?;
+    let puzzles_path = root.join(RUNNER_PUZZLES);
+    let puzzles = register_puzzles(&read(&puzzles_path)?, day, &puzzles_path)?;
+
+    let mut written = vec![];
+    for (file, template, part) in TEMPLATES {
+        let path = dir.join(file);
+        let parent = path.parent().expect("files lie in the day directory");
+        fs::create_dir_all(parent).map_err(|source| ScaffoldError::Write {
+            path: parent.to_owned(),
+            source,
+        })?;
+        write(&path, &fill(template, day, *part))?;
+        written.push(path);
+    }
+    write(&manifest_path, &manifest)?;
+    write(&puzzles_path, &puzzles)?;
+    written.extend([manifest_path, puzzles_path]);
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|source| ScaffoldError::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn fill(template: &str, day: u8, part: Option<u8>) -> String {
+    let text = template.replace("{{day}}", &day.to_string());
+    match part {
+        Some(part) => text.replace("{{part}}", &part.to_string()),
+        None => text,
+    }
+}
+
+/// The day a line is about, given what comes right before the number.
+fn day_of(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+    rest[..digits].parse().ok()
+}
+
+/// Insert the lines in order of the days of `lines[range]`, with `day_of`
+/// giving the day of a line if it has one, before the first later day or
+/// else after the last day, or at `fallback` if there are none. Attributes
+/// stay with the line they belong to.
+fn insert_in_order(
+    lines: &mut Vec<String>,
+    range: std::ops::Range<usize>,
+    day: u8,
+    day_of: impl Fn(&str) -> Option<u8>,
+    fallback: usize,
+    new: &[String],
+) -> Result<(), u8> {
+    let days: Vec<(usize, u8)> = range
+        .filter_map(|i| day_of(&lines[i]).map(|d| (i, d)))
+        .collect();
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(day);
+    }
+    let index = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(mut index, _)) => {
+            while index > 0 && lines[index - 1].trim_start().starts_with("#[") {
+                index -= 1;
+            }
+            index
+        }
+        None => days.last().map_or(fallback, |&(index, _)| index + 1),
+    };
+    lines.splice(index..index, new.iter().cloned());
+    Ok(())
+}
+
+/// The runner's manifest with a path dependency on the day crate.
+fn register_dependency(manifest: &str, day: u8, path: &Path) -> Result<String, ScaffoldError> {
+    let mut lines: Vec<String> = manifest.lines().map(str::to_owned).collect();
+    let start = lines
+        .iter()
+        .position(|line| line == "[dependencies]")
+        .ok_or_else(|| ScaffoldError::NoRunner {
+            what: "[dependencies] table",
+            path: path.to_owned(),
+        })?;
+    let end = (start + 1..lines.len())
+        .find(|&i| lines[i].starts_with('['))
+        .unwrap_or(lines.len());
+    // after the last entry of the table, not the blank lines before the next
+    let last = (start + 1..end)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())
+        .map_or(start + 1, |i| i + 1);
+    let new = [format!("day{0} = {{ path = \"../day{0}\" }}", day)];
+    insert_in_order(
+        &mut lines,
+        start + 1..end,
+        day,
+        |line| day_of(line, "day").filter(|_| line.contains('=')),
+        last,
+        &new,
+    )
+    .map_err(|day| ScaffoldError::Registered {
+        day,
+        path: path.to_owned(),
+    })?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The runner's source with both parts of the day in its list of puzzles.
+fn register_puzzles(source: &str, day: u8, path: &Path) -> Result<String, ScaffoldError> {
+    let mut lines: Vec<String> = source.lines().map(str::to_owned).collect();
+    let no_list = || ScaffoldError::NoRunner {
+        what: "list of puzzles",
+        path: path.to_owned(),
+    };
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static PUZZLES"))
+        .ok_or_else(no_list)?;
+    let end = (start + 1..lines.len())
+        .find(|&i| lines[i] == "];")
+        .ok_or_else(no_list)?;
+    let new: Vec<String> = (1..=2)
+        .map(|part| {
+            format!(
+                "    puzzle!({0}, {1}, day{0}::part{1}::Part{1}),",
+                day, part
+            )
+        })
+        .collect();
+    insert_in_order(
+        &mut lines,
+        start + 1..end,
+        day,
+        |line| day_of(line, "puzzle!("),
+        end,
+        &new,
+    )
+    .map_err(|day| ScaffoldError::Registered {
+        day,
+        path: path.to_owned(),
+    })?;
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[package]
+name = \"aoc\"
+
+[dependencies]
+clap = { workspace = true }
+day1 = { path = \"../day1\" }
+day3 = { path = \"../day3\", features = [\"serde\"] }
+day24 = { path = \"../day24\", optional = true }
+
+[build-dependencies]
+toml = { workspace = true }
+";
+
+    const PUZZLES: &str = "/// All puzzles in the workspace, ordered by day and part.
+pub static PUZZLES: &[Puzzle] = &[
+    puzzle!(1, 1, day1::part1::Part1),
+    puzzle!(1, 2, day1::part2::Part2),
+    puzzle!(3, 1, day3::part1::Part1, dump),
+    puzzle!(3, 2, day3::part2::Part2, dump),
+    #[cfg(feature = \"day24\")]
+    puzzle!(24, 1, day24::part1::Part1),
+    #[cfg(feature = \"day24\")]
+    puzzle!(24, 2, day24::part2::Part2),
+];
+";
+
+    #[test]
+    fn dependencies_in_order() {
+        let path = Path::new(RUNNER_MANIFEST);
+        let manifest = register_dependency(MANIFEST, 2, path).unwrap();
+        assert!(manifest
+            .contains("day1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\nday3 = {"));
+        let manifest = register_dependency(MANIFEST, 25, path).unwrap();
+        assert!(manifest.contains("optional = true }\nday25 = { path = \"../day25\" }\n\n"));
+        assert!(matches!(
+            register_dependency(MANIFEST, 3, path),
+            Err(ScaffoldError::Registered { day: 3, .. })
+        ));
+
+        let first = register_dependency("[dependencies]\nclap = \"4\"\n\n[features]\n", 1, path);
+        assert_eq!(
+            first.unwrap(),
+            "[dependencies]\nclap = \"4\"\nday1 = { path = \"../day1\" }\n\n[features]\n"
+        );
+        assert!(matches!(
+            register_dependency("[package]\n", 1, path),
+            Err(ScaffoldError::NoRunner { .. })
+        ));
+    }
+
+    #[test]
+    fn puzzles_in_order() {
+        let path = Path::new(RUNNER_PUZZLES);
+        let puzzles = register_puzzles(PUZZLES, 2, path).unwrap();
+        assert!(puzzles.contains(
+            "Part2),
+    puzzle!(2, 1, day2::part1::Part1),
+    puzzle!(2, 2, day2::part2::Part2),
+    puzzle!(3, 1,"
+        ));
+        // before the attribute of the optional day, not between the two
+        let puzzles = register_puzzles(PUZZLES, 23, path).unwrap();
+        assert!(puzzles.contains(
+            "dump),
+    puzzle!(23, 1, day23::part1::Part1),
+    puzzle!(23, 2, day23::part2::Part2),
+    #[cfg(feature = \"day24\")]
+    puzzle!(24, 1,"
+        ));
+        let puzzles = register_puzzles(PUZZLES, 25, path).unwrap();
+        assert!(puzzles.ends_with(
+            "day24::part2::Part2),
+    puzzle!(25, 1, day25::part1::Part1),
+    puzzle!(25, 2, day25::part2::Part2),
+];
+"
+        ));
+        assert!(matches!(
+            register_puzzles(PUZZLES, 24, path),
+            Err(ScaffoldError::Registered { day: 24, .. })
+        ));
+    }
+
+    #[test]
+    fn filled_templates() {
+        for (file, template, part) in TEMPLATES {
+            let text = fill(template, 25, *part);
+            assert!(!text.contains("{{"), "{} is not filled in", file);
+        }
+        let bin = fill(TEMPLATES[7].1, 25, Some(2));
+        assert!(bin.contains("day25::part2::part2(&input)"));
+    }
+
+    #[test]
+    fn new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join(RUNNER_MANIFEST), MANIFEST).unwrap();
+        fs::write(root.join(RUNNER_PUZZLES), PUZZLES).unwrap();
+
+        let written = create_day(&root, 25).unwrap();
+        assert_eq!(written.len(), TEMPLATES.len() + 2);
+        let part1 = fs::read_to_string(root.join("day25/src/part1.rs")).unwrap();
+        assert!(part1.contains("impl Solution for Part1"));
+        assert!(root.join("day25/src/input1_test.txt").exists());
+        let manifest = fs::read_to_string(root.join(RUNNER_MANIFEST)).unwrap();
+        assert!(manifest.contains("day25 = { path = \"../day25\" }"));
+
+        assert!(matches!(
+            create_day(&root, 25),
+            Err(ScaffoldError::Exists(_))
+        ));
+        // a registered day without a crate leaves nothing behind
+        assert!(matches!(
+            create_day(&root, 1),
+            Err(ScaffoldError::Registered { day: 1, .. })
+        ));
+        assert!(!root.join("day1").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
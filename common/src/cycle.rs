@@ -0,0 +1,324 @@
+//! Find where a simulation starts repeating itself, to skip ahead to a step
+//! far beyond what could be simulated.
+//!
+//! A simulation is a sequence of states `x0`, `x1 = step(x0)`, ... that
+//! repeats as soon as one state equals an earlier one. Its [`Cycle`] is the
+//! number of steps before the first repeating state and the length of the
+//! repetition. [`History`] records every state and finds the cycle after
+//! the fewest steps, [`brent`] and [`floyd`] step further but only keep a
+//! couple of states:
+//!
+//! ```
+//! use common::cycle::{self, Cycle, History};
+//!
+//! // 1, 3, 9, 27, 81, 43, 29, 87, 61, 83, 49, 47, 41, 23, 69, 7, 21, 63, 89, 67, 1, ...
+//! let step = |x: &u64| x * 3 % 100;
+//! assert_eq!(cycle::brent(&1, step), Cycle { start: 0, length: 20 });
+//!
+//! // 2, 6, 18, 54, 62, 86, 58, 74, 22, 66, 98, 94, 82, 46, 38, 14, 42, 26, 78, 34, 2, ...
+//! let history = History::until_cycle(2, step);
+//! assert_eq!(history.cycle(), Some(Cycle { start: 0, length: 20 }));
+//! assert_eq!(history.state_after(1_000_000_000_000_000_003), Some(&54));
+//! ```
+
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash, RandomState},
+};
+
+/// Where a sequence of states starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state that repeats, the pre-period.
+    pub start: u64,
+    /// Steps until a state repeats, the period.
+    pub length: u64,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`.
+    pub fn equivalent(&self, n: u64) -> u64 {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state after `n` steps from `start`, stepping no further than the
+    /// end of the first repetition.
+    pub fn state_after<T, F>(&self, start: &T, mut step: F, n: u64) -> T
+    where
+        T: Clone,
+        F: FnMut(&T) -> T,
+    {
+        let mut state = start.clone();
+        for _ in 0..self.equivalent(n) {
+            state = step(&state);
+        }
+        state
+    }
+}
+
+/// Brent's algorithm: the cycle of the states from `start`, comparing the
+/// latest state with the one at the last power of two.
+///
+/// Takes up to `start + 2 * length` steps for the length and
+/// `start + length` more for the start. Never returns if the states don't
+/// repeat.
+pub fn brent<T, F>(start: &T, mut step: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Floyd's algorithm: the cycle of the states from `start`, with a hare
+/// stepping twice as fast as a tortoise until they meet.
+///
+/// Takes about three times as many steps as [`brent`]. Never returns if the
+/// states don't repeat.
+pub fn floyd<T, F>(start: &T, mut step: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut tortoise = step(start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut tortoise = start.clone();
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    let mut hare = step(&tortoise);
+    let mut length = 1;
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Every state of a simulation in order, indexed by their hash to notice the
+/// first one that repeats.
+///
+/// States are compared in full, so two states with the same hash don't make
+/// a cycle.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    states: Vec<T>,
+    /// Steps of the recorded states by their hash.
+    steps: HashMap<u64, Vec<usize>>,
+    hasher: RandomState,
+    cycle: Option<Cycle>,
+}
+
+impl<T: Hash + Eq> Default for History<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> History<T> {
+    pub fn new() -> Self {
+        History {
+            states: vec![],
+            steps: HashMap::new(),
+            hasher: RandomState::new(),
+            cycle: None,
+        }
+    }
+
+    /// Record the states from `start` until one repeats. Never returns if
+    /// the states don't repeat.
+    pub fn until_cycle<F>(start: T, mut step: F) -> Self
+    where
+        F: FnMut(&T) -> T,
+    {
+        let mut history = History::new();
+        let mut state = start;
+        loop {
+            let next = step(&state);
+            if history.push(state).is_some() {
+                return history;
+            }
+            state = next;
+        }
+    }
+
+    /// Record the state of the next step, and return the cycle once it is
+    /// equal to an earlier one. The repeating state and any state after it
+    /// are not recorded.
+    pub fn push(&mut self, state: T) -> Option<Cycle> {
+        if self.cycle.is_some() {
+            return self.cycle;
+        }
+        let step = self.states.len();
+        let same_hash = self.steps.entry(self.hasher.hash_one(&state)).or_default();
+        if let Some(&earlier) = same_hash.iter().find(|&&i| self.states[i] == state) {
+            self.cycle = Some(Cycle {
+                start: earlier as u64,
+                length: (step - earlier) as u64,
+            });
+        } else {
+            same_hash.push(step);
+            self.states.push(state);
+        }
+        self.cycle
+    }
+
+    /// The cycle, once a state repeated.
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Number of recorded states.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// The state after `n` steps: a recorded one, or the one it repeats if
+    /// the cycle is known.
+    pub fn state_after(&self, n: u64) -> Option<&T> {
+        let step = match self.cycle {
+            Some(cycle) => cycle.equivalent(n),
+            None => n,
+        };
+        usize::try_from(step).ok().and_then(|i| self.states.get(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hasher;
+
+    use super::*;
+
+    /// Every cycle found from all starts of the same steps.
+    fn all_cycles(step: impl Fn(&u64) -> u64 + Copy, starts: u64) {
+        for start in 0..starts {
+            let history = History::until_cycle(start, step);
+            let cycle = history.cycle().unwrap();
+            assert_eq!(history.len() as u64, cycle.start + cycle.length);
+            assert_eq!(brent(&start, step), cycle, "brent from {}", start);
+            assert_eq!(floyd(&start, step), cycle, "floyd from {}", start);
+
+            // the state after every step, up to twice around the cycle
+            let mut state = start;
+            for n in 0..2 * (cycle.start + cycle.length) {
+                assert_eq!(history.state_after(n), Some(&state));
+                assert_eq!(cycle.state_after(&start, step, n), state);
+                state = step(&state);
+            }
+        }
+    }
+
+    #[test]
+    fn agree_on_every_start() {
+        all_cycles(|x| (x * x + 1) % 1009, 200);
+        all_cycles(|x| x * 7 % 1000, 200);
+        all_cycles(|x| x / 2, 50);
+    }
+
+    #[test]
+    fn skip_far_ahead() {
+        // 0, 1, 2, ..., 9 and then 5, 6, 7, 8, 9 over and over
+        let step = |x: &u64| if *x == 9 { 5 } else { x + 1 };
+        let history = History::until_cycle(0, step);
+        let cycle = Cycle {
+            start: 5,
+            length: 5,
+        };
+        assert_eq!(history.cycle(), Some(cycle));
+        assert_eq!(brent(&0, step), cycle);
+        assert_eq!(floyd(&0, step), cycle);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(10), 5);
+        assert_eq!(cycle.equivalent(1_000_000_000_000_000_000), 5);
+        assert_eq!(history.state_after(1_000_000_000_000_000_002), Some(&7));
+        // u64::MAX is a multiple of 5
+        assert_eq!(cycle.state_after(&0, step, u64::MAX - 1), 9);
+    }
+
+    /// A state that hashes the same as every other one.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Colliding(u8);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            state.write_u8(0);
+        }
+    }
+
+    #[test]
+    fn tell_states_with_the_same_hash_apart() {
+        let mut history = History::new();
+        for state in [1, 2, 3, 4] {
+            assert_eq!(history.push(Colliding(state)), None);
+        }
+        let cycle = Cycle {
+            start: 1,
+            length: 3,
+        };
+        assert_eq!(history.push(Colliding(2)), Some(cycle));
+        assert_eq!(history.len(), 4);
+        // states after the cycle is found are ignored
+        assert_eq!(history.push(Colliding(9)), Some(cycle));
+        assert_eq!(history.state_after(7), Some(&Colliding(2)));
+    }
+
+    #[test]
+    fn state_after_without_cycle() {
+        let mut history = History::new();
+        history.push("a");
+        history.push("b");
+        assert_eq!(history.cycle(), None);
+        assert_eq!(history.state_after(1), Some(&"b"));
+        assert_eq!(history.state_after(2), None);
+    }
+}
@@ -0,0 +1,127 @@
+//! Facts a solver finds on the way to its answer, like the length of a
+//! cycle, which the runner reports next to the answer.
+//!
+//! Solvers report them wherever they are found, and they are dropped unless
+//! the caller collects them:
+//!
+//! ```
+//! use common::diagnostics::{self, Value};
+//!
+//! let (answer, reported) = diagnostics::collect(|| {
+//!     diagnostics::report("cycle_length", 7_u64);
+//!     42
+//! });
+//! assert_eq!(answer, 42);
+//! assert_eq!(reported, vec![("cycle_length", Value::Integer(7))]);
+//! ```
+
+use std::cell::RefCell;
+
+/// The value of a diagnostic.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    List(Vec<Value>),
+}
+
+pub type Diagnostic = (&'static str, Value);
+
+macro_rules! from_integer {
+    ($($integer:ty),*) => {
+        $(impl From<$integer> for Value {
+            fn from(value: $integer) -> Self {
+                Value::Integer(value as i128)
+            }
+        })*
+    };
+}
+
+from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+thread_local! {
+    static REPORTED: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+}
+
+/// Report a diagnostic of the running solver.
+pub fn report(name: &'static str, value: impl Into<Value>) {
+    REPORTED.with_borrow_mut(|reported| {
+        if let Some(reported) = reported {
+            reported.push((name, value.into()));
+        }
+    });
+}
+
+/// Puts back the diagnostics of an outer [`collect`], also when `f` panics.
+struct Restore(Option<Vec<Diagnostic>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        REPORTED.set(self.0.take());
+    }
+}
+
+/// Run `f` and collect the diagnostics reported meanwhile on this thread.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<Diagnostic>) {
+    let _restore = Restore(REPORTED.replace(Some(vec![])));
+    let result = f();
+    (result, REPORTED.take().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_uncollected_reports() {
+        report("lost", 1);
+        let ((), reported) = collect(|| report("kept", "yes"));
+        assert_eq!(reported, vec![("kept", Value::Text("yes".to_owned()))]);
+    }
+
+    #[test]
+    fn collect_nested() {
+        let (inner, outer) = collect(|| {
+            report("outer", 1);
+            let ((), inner) = collect(|| report("inner", vec![2, 3]));
+            report("outer", 4.5);
+            inner
+        });
+        assert_eq!(
+            inner,
+            vec![(
+                "inner",
+                Value::List(vec![Value::Integer(2), Value::Integer(3)])
+            )]
+        );
+        assert_eq!(
+            outer,
+            vec![("outer", Value::Integer(1)), ("outer", Value::Float(4.5))]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::{self, Debug};
+
+/// A case on which two algorithms for the same question give different
+/// answers, shrunk as far as they keep disagreeing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<C, A> {
+    /// The smallest disagreeing case found.
+    pub case: C,
+    pub slow: A,
+    pub fast: A,
+    /// The case the disagreement was first found on.
+    pub original: C,
+    /// How many times the case was shrunk.
+    pub shrinks: usize,
+}
+
+impl<C: Debug, A: Debug> fmt::Display for Disagreement<C, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slow algorithm gives {:?}, fast one {:?} on {:?} (shrunk {} times from {:?})",
+            self.slow, self.fast, self.case, self.shrinks, self.original
+        )
+    }
+}
+
+/// Run a slow, obviously right algorithm and a fast one on every case and
+/// return the number of cases they agree on.
+///
+/// On the first disagreement the case is shrunk: of the smaller variants
+/// `shrink` offers, the first one the algorithms still disagree on replaces
+/// the case, until none of them does.
+///
+/// ```
+/// use common::differential::compare;
+///
+/// // counting set bits, with a fast version that is wrong above 255
+/// let slow = |n: &u32| (0..32).filter(|i| n & (1 << i) != 0).count() as u32;
+/// let fast = |n: &u32| (*n as u8).count_ones();
+/// let shrink = |n: &u32| vec![n / 2, n - 1];
+///
+/// assert_eq!(compare(0..256, slow, fast, shrink), Ok(256));
+/// let disagreement = compare(200..300, slow, fast, shrink).unwrap_err();
+/// assert_eq!((disagreement.original, disagreement.case), (256, 256));
+/// let disagreement = compare([1000], slow, fast, shrink).unwrap_err();
+/// assert_eq!(disagreement.case, 256);
+/// ```
+pub fn compare<C, A, S, F, R, I, J>(
+    cases: I,
+    slow: S,
+    fast: F,
+    shrink: R,
+) -> Result<usize, Disagreement<C, A>>
+where
+    C: Clone,
+    A: PartialEq,
+    S: Fn(&C) -> A,
+    F: Fn(&C) -> A,
+    R: Fn(&C) -> J,
+    I: IntoIterator<Item = C>,
+    J: IntoIterator<Item = C>,
+{
+    let disagree = |case: &C| {
+        let (slow, fast) = (slow(case), fast(case));
+        (slow != fast).then_some((slow, fast))
+    };
+
+    let mut checked = 0;
+    for mut case in cases {
+        let Some(mut answers) = disagree(&case) else {
+            checked += 1;
+            continue;
+        };
+
+        let original = case.clone();
+        let mut shrinks = 0;
+        while let Some((smaller, smaller_answers)) = shrink(&case)
+            .into_iter()
+            .find_map(|smaller| disagree(&smaller).map(|answers| (smaller, answers)))
+        {
+            case = smaller;
+            answers = smaller_answers;
+            shrinks += 1;
+        }
+        let (slow, fast) = answers;
+        return Err(Disagreement {
+            case,
+            slow,
+            fast,
+            original,
+            shrinks,
+        });
+    }
+    Ok(checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_to_the_smallest_disagreement() {
+        // sums of a list, with a fast version that forgets negative numbers
+        let slow = |list: &Vec<i32>| list.iter().sum::<i32>();
+        let fast = |list: &Vec<i32>| list.iter().filter(|&&n| n > 0).sum::<i32>();
+        let shrink = |list: &Vec<i32>| {
+            (0..list.len())
+                .map(|i| {
+                    let mut smaller = list.clone();
+                    smaller.remove(i);
+                    smaller
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let cases = vec![vec![1, 2], vec![], vec![3, 5, -2, 7, -1]];
+        let disagreement = compare(cases, slow, fast, shrink).unwrap_err();
+        assert_eq!(disagreement.case, vec![-1]);
+        assert_eq!((disagreement.slow, disagreement.fast), (-1, 0));
+        assert_eq!(disagreement.original, vec![3, 5, -2, 7, -1]);
+        assert_eq!(disagreement.shrinks, 4);
+        assert_eq!(
+            disagreement.to_string(),
+            "slow algorithm gives -1, fast one 0 on [-1] (shrunk 4 times from [3, 5, -2, 7, -1])"
+        );
+    }
+}
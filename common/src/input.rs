@@ -0,0 +1,165 @@
+use std::{
+    borrow::Cow,
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable naming a directory with the inputs of all days, as
+/// `day1.txt`, `day2.txt`, ...
+pub const INPUT_DIR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// The source named by a command line argument, `-` for stdin and a path
+    /// otherwise.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Read and normalise the input.
+    pub fn read(&self) -> Result<String, InputError> {
+        let text = match self {
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            }
+            Source::File(path) => fs::read_to_string(path),
+        }
+        .map_err(|error| InputError {
+            source: self.clone(),
+            error,
+        })?;
+        Ok(normalize(&text).into_owned())
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub source: Source,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not read {}: {}", self.source, self.error)
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The puzzle text with a byte order mark removed, Windows line endings
+/// turned into `\n`, and exactly one newline at the end, like the inputs as
+/// downloaded. Text already in that form is borrowed.
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let trimmed = text.trim_end_matches(['\r', '\n']);
+    if trimmed.is_empty() {
+        return Cow::Borrowed("");
+    }
+    if !trimmed.contains('\r') && text.len() == trimmed.len() + 1 {
+        return Cow::Borrowed(text);
+    }
+    let mut normalized = trimmed.replace("\r\n", "\n");
+    normalized.push('\n');
+    Cow::Owned(normalized)
+}
+
+/// The input of a day when no source is named: its file in the directory of
+/// [`INPUT_DIR`] if that is set, the `src/input1.txt` of the day crate
+/// otherwise.
+pub fn default_path(day: u8, crate_dir: &Path) -> PathBuf {
+    default_path_in(day, crate_dir, env::var_os(INPUT_DIR).map(PathBuf::from))
+}
+
+fn default_path_in(day: u8, crate_dir: &Path, input_dir: Option<PathBuf>) -> PathBuf {
+    match input_dir {
+        Some(dir) => dir.join(format!("day{}.txt", day)),
+        None => crate_dir.join("src").join("input1.txt"),
+    }
+}
+
+/// Read the input of a day crate's binary: from the path given as the first
+/// argument, from stdin if that is `-`, or from [`default_path`].
+pub fn load(day: u8, crate_dir: &Path) -> Result<String, InputError> {
+    let source = match env::args().nth(1) {
+        Some(arg) => Source::from_arg(&arg),
+        None => Source::File(default_path(day, crate_dir)),
+    };
+    source.read()
+}
+
+/// Read the input of the day crate this is used in, see [`load`].
+#[macro_export]
+macro_rules! load_input {
+    () => {{
+        let day = env!("CARGO_PKG_NAME")
+            .trim_start_matches("day")
+            .parse()
+            .expect("day crate named dayN");
+        $crate::input::load(day, ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_text() {
+        assert_eq!(normalize("\u{feff}a\r\nb\r\n\r\n"), "a\nb\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("a\n\nb\n\n\n"), "a\n\nb\n");
+        assert_eq!(normalize("\n\n"), "");
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb\n")));
+    }
+
+    #[test]
+    fn resolve_sources() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("in.txt"),
+            Source::File(PathBuf::from("in.txt"))
+        );
+        let crate_dir = Path::new("day7");
+        assert_eq!(
+            default_path_in(7, crate_dir, None),
+            Path::new("day7/src/input1.txt")
+        );
+        assert_eq!(
+            default_path_in(7, crate_dir, Some(PathBuf::from("inputs"))),
+            Path::new("inputs/day7.txt")
+        );
+    }
+
+    #[test]
+    fn report_missing_files() {
+        let source = Source::File(PathBuf::from("no/such/input.txt"));
+        let error = source.read().unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("could not read no/such/input.txt: "));
+    }
+}
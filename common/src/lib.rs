@@ -0,0 +1,13 @@
+pub mod cycle;
+pub mod diagnostics;
+pub mod differential;
+pub mod input;
+mod logging;
+pub mod math;
+mod params;
+pub mod polygon;
+mod solution;
+
+pub use logging::{init_logging, Verbosity};
+pub use params::{ParamError, Params};
+pub use solution::Solution;
@@ -0,0 +1,144 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// How much diagnostic output the solvers write through the `log` macros.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only warnings about unexpected input.
+    #[default]
+    Quiet,
+    /// What the solver found out on the way, like the cycle on day 14.
+    Info,
+    /// Intermediate results, e.g. one line per input line.
+    Debug,
+    /// Every step of the inner loops, including grid dumps.
+    Trace,
+}
+
+impl Verbosity {
+    fn level_filter(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::Warn,
+            Verbosity::Info => LevelFilter::Info,
+            Verbosity::Debug => LevelFilter::Debug,
+            Verbosity::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+impl FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "quiet" => Ok(Verbosity::Quiet),
+            "info" => Ok(Verbosity::Info),
+            "debug" => Ok(Verbosity::Debug),
+            "trace" => Ok(Verbosity::Trace),
+            _ => Err(format!(
+                "unknown verbosity {:?}, expected quiet, info, debug or trace",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Info => "info",
+            Verbosity::Debug => "debug",
+            Verbosity::Trace => "trace",
+        };
+        f.write_str(name)
+    }
+}
+
+struct Logger {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+fn format_record(record: &Record) -> String {
+    format!(
+        "{:<5} {}: {}",
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let mut out = self.out.lock().expect("logger lock not poisoned");
+            // there is nowhere left to report a failing log destination
+            let _ = writeln!(out, "{}", format_record(record));
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.out.lock().expect("logger lock not poisoned").flush();
+    }
+}
+
+/// Send the log output of all crates to stderr, or to `file` if given.
+///
+/// There is only one logger per process, so only the first call succeeds.
+/// Buffered output to a file is written on `log::logger().flush()`.
+pub fn init_logging(verbosity: Verbosity, file: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let out: Box<dyn Write + Send> = match file {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stderr()),
+    };
+    log::set_boxed_logger(Box::new(Logger {
+        out: Mutex::new(out),
+    }))?;
+    log::set_max_level(verbosity.level_filter());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn parse_verbosity() {
+        for verbosity in [
+            Verbosity::Quiet,
+            Verbosity::Info,
+            Verbosity::Debug,
+            Verbosity::Trace,
+        ] {
+            assert_eq!(verbosity.to_string().parse(), Ok(verbosity));
+        }
+        assert!("loud".parse::<Verbosity>().is_err());
+        assert!(Verbosity::Quiet < Verbosity::Trace);
+    }
+
+    #[test]
+    fn format_lines() {
+        let line = format_record(
+            &Record::builder()
+                .level(Level::Debug)
+                .target("day14::part2")
+                .args(format_args!("cycle {} has load {}", 3, 87))
+                .build(),
+        );
+        assert_eq!(line, "DEBUG day14::part2: cycle 3 has load 87");
+    }
+}
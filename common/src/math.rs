@@ -0,0 +1,301 @@
+//! Number theory for puzzles whose answer lies too far out to simulate: when
+//! several periodic events next happen together, and square roots for
+//! counting the solutions of a quadratic without trying each.
+//!
+//! Products and least common multiples of puzzle periods quickly outgrow a
+//! `u64`, so they report an [`Overflow`] instead of wrapping around:
+//!
+//! ```
+//! use common::math::{self, Congruence, Periodic};
+//!
+//! assert_eq!(math::lcm_all([4, 6, 10]), Ok(60));
+//! assert!(math::lcm_all([u64::MAX, u64::MAX - 1]).is_err());
+//!
+//! // x = 2 (mod 6) and x = 8 (mod 10) share a factor of 2, and agree on it
+//! let both = math::crt([Congruence::new(2, 6), Congruence::new(8, 10)]);
+//! assert_eq!(both, Ok(Some(Congruence::new(8, 30))));
+//!
+//! // a bus leaving at 5, 12, 19, ... and one at 3, 8, 13, ... first meet at 33
+//! let buses = [Periodic::new(5, 7), Periodic::new(3, 5)];
+//! assert_eq!(math::first_common(&buses), Ok(Some(33)));
+//! ```
+
+use std::{error::Error, fmt};
+
+/// A result that does not fit in a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the result does not fit in 64 bits")
+    }
+}
+
+impl Error for Overflow {}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, 0 if either is 0.
+pub fn lcm(a: u64, b: u64) -> Result<u64, Overflow> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).ok_or(Overflow)
+}
+
+/// The least common multiple of all the numbers, 1 for none.
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> Result<u64, Overflow> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// The product of all the numbers, 1 for none.
+pub fn product(numbers: impl IntoIterator<Item = u64>) -> Result<u64, Overflow> {
+    numbers
+        .into_iter()
+        .try_fold(1_u64, |product, n| product.checked_mul(n).ok_or(Overflow))
+}
+
+/// The numbers `x` with `x = residue (mod modulus)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Congruence {
+    /// The smallest of the numbers, below the modulus.
+    pub residue: u64,
+    pub modulus: u64,
+}
+
+impl Congruence {
+    /// # Panics
+    ///
+    /// If the modulus is 0.
+    pub fn new(residue: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "a congruence modulo 0");
+        Congruence {
+            residue: residue % modulus,
+            modulus,
+        }
+    }
+
+    /// The numbers in both, `None` if there are none.
+    pub fn combine(self, other: Congruence) -> Result<Option<Congruence>, Overflow> {
+        let (a, m) = (self.residue as i128, self.modulus as i128);
+        let (b, n) = (other.residue as i128, other.modulus as i128);
+        let (g, inverse, _) = extended_gcd(m, n);
+        if (b - a) % g != 0 {
+            return Ok(None);
+        }
+        let modulus = lcm(self.modulus, other.modulus)?;
+        // a + m * k = b (mod n), for k = (b - a) / g / (m / g) (mod n / g),
+        // multiplied unsigned as both factors can take up 64 bits
+        let n_g = n / g;
+        let quotient = ((b - a) / g).rem_euclid(n_g) as u128;
+        let k = (quotient * inverse.rem_euclid(n_g) as u128 % n_g as u128) as i128;
+        let residue = (a + m * k).rem_euclid(modulus as i128);
+        Ok(Some(Congruence::new(residue as u64, modulus)))
+    }
+
+    /// The smallest of the numbers that is at least `start`.
+    pub fn first_from(self, start: u64) -> Result<u64, Overflow> {
+        if self.residue >= start {
+            return Ok(self.residue);
+        }
+        let moduli = (start - self.residue).div_ceil(self.modulus);
+        moduli
+            .checked_mul(self.modulus)
+            .and_then(|n| n.checked_add(self.residue))
+            .ok_or(Overflow)
+    }
+}
+
+/// The numbers satisfying all the congruences by the Chinese remainder
+/// theorem, for any moduli, coprime or not. `None` if the congruences
+/// contradict each other, and every number if there are none.
+pub fn crt(
+    congruences: impl IntoIterator<Item = Congruence>,
+) -> Result<Option<Congruence>, Overflow> {
+    let mut all = Congruence::new(0, 1);
+    for congruence in congruences {
+        match all.combine(congruence)? {
+            Some(combined) => all = combined,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(all))
+}
+
+/// An event happening first at `offset` and then every `period` after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Periodic {
+    pub offset: u64,
+    pub period: u64,
+}
+
+impl Periodic {
+    pub fn new(offset: u64, period: u64) -> Self {
+        Periodic { offset, period }
+    }
+}
+
+/// The first time all the events happen together, `None` if they never do.
+///
+/// # Panics
+///
+/// If a period is 0.
+pub fn first_common(events: &[Periodic]) -> Result<Option<u64>, Overflow> {
+    let Some(all) = crt(events.iter().map(|e| Congruence::new(e.offset, e.period)))? else {
+        return Ok(None);
+    };
+    // the residue may fall before an event has happened at all
+    let start = events.iter().map(|e| e.offset).max().unwrap_or(0);
+    all.first_from(start).map(Some)
+}
+
+/// `gcd(a, b)` with `x` and `y` such that `a * x + b * y = gcd(a, b)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    (old_r, old_x, old_y)
+}
+
+/// Exact integer square roots, on top of the standard library's rounded down
+/// `isqrt`.
+pub trait Sqrt: Sized {
+    /// The square root rounded down.
+    fn sqrt_floor(self) -> Self;
+    /// The square root rounded up.
+    fn sqrt_ceil(self) -> Self;
+    /// The square root if the number is a perfect square.
+    fn exact_sqrt(self) -> Option<Self>;
+}
+
+macro_rules! impl_sqrt {
+    ($($t:ty),*) => {$(
+        impl Sqrt for $t {
+            fn sqrt_floor(self) -> Self {
+                self.isqrt()
+            }
+
+            fn sqrt_ceil(self) -> Self {
+                let root = self.isqrt();
+                if root * root == self {
+                    root
+                } else {
+                    root + 1
+                }
+            }
+
+            fn exact_sqrt(self) -> Option<Self> {
+                let root = self.isqrt();
+                (root * root == self).then_some(root)
+            }
+        }
+    )*};
+}
+
+impl_sqrt!(u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_lcm() {
+        assert_eq!(lcm(4, 6), Ok(12));
+        assert_eq!(lcm(0, 6), Ok(0));
+        assert_eq!(lcm_all([]), Ok(1));
+        // the ghost periods of day 8 all share the number of directions
+        assert_eq!(
+            lcm_all([263 * 79, 263 * 61, 263 * 47]),
+            Ok(263 * 79 * 61 * 47)
+        );
+        assert_eq!(lcm_all([1 << 32, 1 << 31, 3 << 31]), Ok(3 << 32));
+        assert_eq!(lcm_all([1 << 32, (1 << 32) + 1]), Err(Overflow));
+    }
+
+    #[test]
+    fn checked_product() {
+        // the group cycle lengths of day 20 are primes
+        assert_eq!(product([3739, 3761, 3797, 3889]), Ok(207_652_583_562_007));
+        assert_eq!(product([]), Ok(1));
+        assert_eq!(product([1 << 32, 1 << 32]), Err(Overflow));
+        assert_eq!(product([1 << 32, 1 << 32, 0]), Err(Overflow));
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        let coprime = [
+            Congruence::new(2, 3),
+            Congruence::new(3, 5),
+            Congruence::new(2, 7),
+        ];
+        assert_eq!(crt(coprime), Ok(Some(Congruence::new(23, 105))));
+        let contradicting = [Congruence::new(1, 4), Congruence::new(2, 6)];
+        assert_eq!(crt(contradicting), Ok(None));
+        assert_eq!(crt([]), Ok(Some(Congruence::new(0, 1))));
+        assert_eq!(Congruence::new(23, 105).first_from(24), Ok(128));
+        assert_eq!(Congruence::new(23, 105).first_from(23), Ok(23));
+        assert_eq!(Congruence::new(1, 1 << 63).first_from(2), Ok((1 << 63) + 1));
+        assert_eq!(
+            Congruence::new(1, 1 << 63).first_from(u64::MAX),
+            Err(Overflow)
+        );
+
+        // moduli whose product only just fits
+        let large = [
+            Congruence::new(5, (1 << 32) - 1),
+            Congruence::new(6, (1 << 32) + 1),
+        ];
+        assert_eq!(
+            crt(large),
+            Ok(Some(Congruence::new(9_223_372_034_707_292_165, u64::MAX)))
+        );
+        let too_large = [Congruence::new(1, u64::MAX), Congruence::new(7, 1 << 63)];
+        assert_eq!(crt(too_large), Err(Overflow));
+        assert_eq!(
+            crt([Congruence::new(5, 1 << 62), Congruence::new(1, 4)]),
+            Ok(Some(Congruence::new(5, 1 << 62)))
+        );
+    }
+
+    #[test]
+    fn events_with_offsets() {
+        // when the first arrival is also the period, as on day 8, it is the lcm
+        let ghosts = [Periodic::new(12, 12), Periodic::new(18, 18)];
+        assert_eq!(first_common(&ghosts), Ok(Some(36)));
+        // ghosts first arriving after 2 and 1 steps, then every 2 and 3 steps
+        let late = [Periodic::new(2, 2), Periodic::new(1, 3)];
+        assert_eq!(first_common(&late), Ok(Some(4)));
+        // the residue 1 comes before the first event at 100
+        let once_started = [Periodic::new(100, 3), Periodic::new(1, 1)];
+        assert_eq!(first_common(&once_started), Ok(Some(100)));
+        let never = [Periodic::new(0, 2), Periodic::new(1, 2)];
+        assert_eq!(first_common(&never), Ok(None));
+    }
+
+    #[test]
+    fn square_roots() {
+        assert_eq!(24_u64.sqrt_floor(), 4);
+        assert_eq!(24_u64.sqrt_ceil(), 5);
+        assert_eq!(25_u64.sqrt_ceil(), 5);
+        assert_eq!(24_u64.exact_sqrt(), None);
+        assert_eq!(25_u64.exact_sqrt(), Some(5));
+        assert_eq!(u64::MAX.sqrt_floor(), u32::MAX as u64);
+        assert_eq!(u64::MAX.sqrt_ceil(), 1 << 32);
+        let square = (u64::MAX as u128).pow(2);
+        assert_eq!(square.exact_sqrt(), Some(u64::MAX as u128));
+        assert_eq!((square - 1).sqrt_floor(), u64::MAX as u128 - 1);
+        assert_eq!((square + 1).sqrt_ceil(), u64::MAX as u128 + 1);
+    }
+}
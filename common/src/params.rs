@@ -0,0 +1,149 @@
+use std::{error::Error, fmt};
+
+/// Named, optional parameters of a solver, settable from their text form.
+pub trait Params: Default + fmt::Debug {
+    /// Set the parameter `name` from `value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+
+    /// Names and current values of all parameters.
+    fn values(&self) -> Vec<(&'static str, String)>;
+
+    /// Build the parameters from their defaults and a list of overrides.
+    fn from_pairs<'a, I>(pairs: I) -> Result<Self, ParamError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut params = Self::default();
+        for (name, value) in pairs {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown(name.to_owned()))
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParamError {
+    Unknown(String),
+    Invalid { name: &'static str, value: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown(name) => write!(f, "unknown parameter {}", name),
+            ParamError::Invalid { name, value } => {
+                write!(f, "invalid value {:?} for parameter {}", value, name)
+            }
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+/// Declare a parameter struct with defaults and a [`Params`] implementation.
+///
+/// ```
+/// common::params! {
+///     pub struct Params {
+///         steps: u64 = 64,
+///     }
+/// }
+///
+/// use common::Params as _;
+/// let params = Params::from_pairs([("steps", "6")]).unwrap();
+/// assert_eq!(params.steps, 6);
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl $crate::Params for $name {
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::ParamError> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|_| $crate::ParamError::Invalid {
+                            name: stringify!($field),
+                            value: value.to_owned(),
+                        })?;
+                    })*
+                    _ => return Err($crate::ParamError::Unknown(name.to_owned())),
+                }
+                Ok(())
+            }
+
+            fn values(&self) -> Vec<(&'static str, String)> {
+                vec![$((stringify!($field), self.$field.to_string())),*]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        pub struct TestParams {
+            scale_factor: i64 = 1_000_000,
+            axis_min: f64 = 7.0,
+        }
+    }
+
+    #[test]
+    fn set_params() {
+        let params = TestParams::from_pairs([("scale_factor", "10")]).unwrap();
+        assert_eq!(params.scale_factor, 10);
+        assert_eq!(params.axis_min, 7.0);
+        assert_eq!(
+            params.values(),
+            vec![
+                ("scale_factor", "10".to_owned()),
+                ("axis_min", "7".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_params() {
+        assert_eq!(
+            TestParams::from_pairs([("steps", "10")]),
+            Err(ParamError::Unknown("steps".to_owned()))
+        );
+        assert_eq!(
+            TestParams::from_pairs([("axis_min", "ten")]),
+            Err(ParamError::Invalid {
+                name: "axis_min",
+                value: "ten".to_owned()
+            })
+        );
+        assert!(<()>::from_pairs([("steps", "10")]).is_err());
+    }
+}
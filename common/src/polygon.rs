@@ -0,0 +1,207 @@
+//! Polygons with their corners on the integer lattice, measured exactly.
+//!
+//! The area of a lattice polygon is a multiple of a half, so it is kept
+//! doubled as an integer. With the number of lattice points on the boundary,
+//! Pick's theorem gives the number inside, which is what a puzzle counting
+//! the tiles a loop encloses, or the cells a trench digs out, asks for:
+//!
+//! ```
+//! use common::polygon::Polygon;
+//!
+//! // a 4 by 3 rectangle, with 14 points on its edges and 6 inside
+//! let rectangle = Polygon::new(vec![(0, 0), (4, 0), (4, 3), (0, 3)]);
+//! assert_eq!(rectangle.doubled_area(), 24);
+//! assert_eq!(rectangle.boundary_points(), 14);
+//! assert_eq!(rectangle.interior_points(), 6);
+//! assert!(rectangle.contains((1, 2)));
+//! assert!(!rectangle.contains((4, 2)));
+//! ```
+
+use crate::math::gcd;
+
+/// A point of the lattice.
+pub type Point = (i64, i64);
+
+/// A closed polygon through its corners in order, the last one joined back to
+/// the first.
+///
+/// Corners may lie on a straight edge, like every tile of a loop, and the
+/// edges should not cross each other for the area and point counts to mean
+/// anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    corners: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(corners: Vec<Point>) -> Self {
+        Polygon { corners }
+    }
+
+    pub fn corners(&self) -> &[Point] {
+        &self.corners
+    }
+
+    /// The edges from each corner to the next.
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let next = self.corners.iter().cycle().skip(1);
+        self.corners.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the area by the shoelace formula, positive if the corners go
+    /// counterclockwise with y up, or clockwise with y down as on a grid.
+    pub fn doubled_signed_area(&self) -> i128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| x1 as i128 * y2 as i128 - x2 as i128 * y1 as i128)
+            .sum()
+    }
+
+    /// Twice the area, which is a whole number for a lattice polygon.
+    pub fn doubled_area(&self) -> i128 {
+        self.doubled_signed_area().abs()
+    }
+
+    /// Number of lattice points on the edges, corners included.
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| gcd((x2 - x1).unsigned_abs(), (y2 - y1).unsigned_abs()))
+            .map(i128::from)
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, by Pick's theorem:
+    /// `A = I + B / 2 - 1`.
+    pub fn interior_points(&self) -> i128 {
+        (self.doubled_area() - self.boundary_points() + 2) / 2
+    }
+
+    /// Number of lattice points inside or on the edges.
+    pub fn lattice_points(&self) -> i128 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether the point lies on one of the edges.
+    pub fn on_boundary(&self, point: Point) -> bool {
+        self.edges().any(|(from, to)| {
+            cross(from, to, point) == 0
+                && point.0 >= from.0.min(to.0)
+                && point.0 <= from.0.max(to.0)
+                && point.1 >= from.1.min(to.1)
+                && point.1 <= from.1.max(to.1)
+        })
+    }
+
+    /// How many times the edges wind around the point, positive in the
+    /// direction of a positive [`doubled_signed_area`](Self::doubled_signed_area).
+    /// It means nothing for a point on the boundary, which
+    /// [`on_boundary`](Self::on_boundary) tells apart.
+    pub fn winding_number(&self, point: Point) -> i32 {
+        let mut winding = 0;
+        for (from, to) in self.edges() {
+            if from.1 <= point.1 {
+                // an edge going up past the point, with the point on its left
+                if to.1 > point.1 && cross(from, to, point) > 0 {
+                    winding += 1;
+                }
+            } else if to.1 <= point.1 && cross(from, to, point) < 0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Whether the point lies strictly inside.
+    pub fn contains(&self, point: Point) -> bool {
+        !self.on_boundary(point) && self.winding_number(point) != 0
+    }
+}
+
+/// Twice the signed area of the triangle, positive if `point` lies to the
+/// left of the line from `from` to `to` with y up.
+fn cross(from: Point, to: Point, point: Point) -> i128 {
+    let (ax, ay) = ((to.0 - from.0) as i128, (to.1 - from.1) as i128);
+    let (bx, by) = ((point.0 - from.0) as i128, (point.1 - from.1) as i128);
+    ax * by - ay * bx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 6 by 4 rectangle with one corner cut off by a diagonal edge.
+    fn notched() -> Polygon {
+        Polygon::new(vec![(0, 0), (6, 0), (6, 2), (2, 4), (0, 4)])
+    }
+
+    #[test]
+    fn shoelace() {
+        let square = Polygon::new(vec![(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert_eq!(square.doubled_signed_area(), 8);
+        let reversed = Polygon::new(square.corners().iter().rev().copied().collect());
+        assert_eq!(reversed.doubled_signed_area(), -8);
+        assert_eq!(reversed.doubled_area(), 8);
+
+        // 6 * 4 minus the triangle of 4 * 2 cut off
+        assert_eq!(notched().doubled_area(), 2 * (24 - 4));
+
+        let triangle = Polygon::new(vec![(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(triangle.doubled_area(), 1);
+    }
+
+    #[test]
+    fn pick() {
+        let polygon = notched();
+        // the diagonal from (6, 2) to (2, 4) passes through (4, 3)
+        assert_eq!(polygon.boundary_points(), 6 + 2 + 2 + 2 + 4);
+        assert_eq!(polygon.interior_points(), 20 - 16 / 2 + 1);
+        let inside: Vec<Point> = (0..=6)
+            .flat_map(|x| (0..=4).map(move |y| (x, y)))
+            .filter(|&point| polygon.contains(point))
+            .collect();
+        assert_eq!(inside.len() as i128, polygon.interior_points());
+        assert_eq!(polygon.lattice_points(), 13 + 16);
+    }
+
+    #[test]
+    fn far_from_the_origin() {
+        let size = 1 << 40;
+        let offset = 1 << 50;
+        let square = Polygon::new(vec![
+            (offset, offset),
+            (offset + size, offset),
+            (offset + size, offset + size),
+            (offset, offset + size),
+        ]);
+        assert_eq!(square.doubled_area(), 2 * (size as i128).pow(2));
+        assert_eq!(square.interior_points(), (size as i128 - 1).pow(2));
+    }
+
+    #[test]
+    fn points_in_a_loop() {
+        // a U open to the top, its corners listed tile by tile
+        let mut tiles = vec![];
+        tiles.extend((0..=4).map(|y| (0, y)));
+        tiles.extend((1..=4).map(|x| (x, 4)));
+        tiles.extend((0..4).rev().map(|y| (4, y)));
+        tiles.extend((2..4).rev().map(|x| (x, 0)));
+        tiles.extend((1..=2).map(|y| (2, y)));
+        tiles.extend([(1, 2), (1, 1), (1, 0)]);
+        let u = Polygon::new(tiles);
+
+        assert!(u.contains((3, 2)));
+        assert!(!u.contains((2, 2)));
+        assert!(u.on_boundary((2, 2)));
+        assert!(!u.contains((5, 2)));
+        assert_eq!(u.winding_number((3, 3)), -1);
+        assert_eq!(u.winding_number((-1, 3)), 0);
+        assert_eq!(u.interior_points(), 5);
+    }
+
+    #[test]
+    fn wound_twice() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        let twice = Polygon::new(square.iter().chain(&square).copied().collect());
+        assert_eq!(twice.winding_number((2, 2)), 2);
+        assert!(!twice.contains((5, 2)));
+    }
+}
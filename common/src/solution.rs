@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+use crate::Params;
+
+/// A solver for one part of a puzzle, split into a parse and a solve step.
+///
+/// `Input` is the parsed puzzle model and may borrow from the puzzle text.
+/// Solvers that take extra parameters (like the number of steps on day 21)
+/// declare them through `Params`, everything else uses `()`.
+pub trait Solution {
+    type Input<'a>;
+    type Answer: Display;
+    type Params: Params;
+    type Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+    fn solve(input: &Self::Input<'_>, params: &Self::Params) -> Result<Self::Answer, Self::Error>;
+
+    /// Normalise, parse and solve the puzzle text in one go.
+    fn run(input: &str, params: &Self::Params) -> Result<Self::Answer, Self::Error> {
+        let input = crate::input::normalize(input);
+        let parsed = Self::parse(&input)?;
+        Self::solve(&parsed, params)
+    }
+}
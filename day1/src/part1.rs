@@ -1,31 +1,48 @@
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
-}
+use crate::Error;
+use common::Solution;
+use log::trace;
 
-fn find_digit<'a, I>(line: &mut I) -> Option<u32>
+fn find_digit<I>(line: &mut I) -> Option<u32>
 where
     I: Iterator<Item = char>,
 {
-    line.find(|c| c >= &&'0' && c <= &&'9')
-        .map(|c| c.to_digit(10))
-        .flatten()
+    line.find(|c| (&'0'..=&'9').contains(&c))
+        .and_then(|c| c.to_digit(10))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<&'a str>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<&str>, Error> {
+        Ok(input.lines().filter(|line| !line.is_empty()).collect())
+    }
+
+    fn solve(lines: &Vec<&str>, _params: &()) -> Result<u32, Error> {
+        lines
+            .iter()
+            .map(|line| {
+                let first_number = find_digit(&mut line.chars());
+                let second_number = find_digit(&mut line.chars().rev());
+                match (first_number, second_number) {
+                    (Some(first), Some(second)) => {
+                        let value = first * 10 + second;
+                        trace!("calibration value of {:?} is {}", line, value);
+                        Ok(value)
+                    }
+                    _ => Err(Error::NoDigit(line.to_string())),
+                }
+            })
+            .sum()
+    }
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .split("\n")
-        .map(|line| {
-            let first_number = find_digit(&mut line.chars().into_iter());
-            let second_number = find_digit(&mut line.chars().rev().into_iter());
-            if first_number.is_none() || second_number.is_none() {
-                eprintln!("Invalid input line {}", line);
-                return 0;
-            }
-            dbg!(first_number.unwrap() * 10 + second_number.unwrap())
-        })
-        .sum()
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -33,7 +50,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 142);
     }
 }
@@ -1,12 +1,10 @@
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part2(input);
-    dbg!(output);
-}
+use crate::Error;
+use common::Solution;
+use log::trace;
 
-static NUMBERS: &'static [&'static str] = &[
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    "1", "2", "3", "4", "5", "6", "7", "8", "9",
+static NUMBERS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
+    "5", "6", "7", "8", "9",
 ];
 
 fn find_digit(line: &str, reverse: bool) -> Option<u32> {
@@ -37,19 +35,39 @@ fn find_digit(line: &str, reverse: bool) -> Option<u32> {
     })
 }
 
-fn part2(input: &str) -> u32 {
-    input
-        .split("\n")
-        .map(|line| {
-            let first_number = find_digit(line, false);
-            let second_number = find_digit(line, true);
-            if first_number.is_none() || second_number.is_none() {
-                eprintln!("Invalid input line {}", line);
-                return 0;
-            }
-            dbg!(first_number.unwrap() * 10 + second_number.unwrap())
-        })
-        .sum()
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Vec<&'a str>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<&str>, Error> {
+        Ok(input.lines().filter(|line| !line.is_empty()).collect())
+    }
+
+    fn solve(lines: &Vec<&str>, _params: &()) -> Result<u32, Error> {
+        lines
+            .iter()
+            .map(|line| {
+                let first_number = find_digit(line, false);
+                let second_number = find_digit(line, true);
+                match (first_number, second_number) {
+                    (Some(first), Some(second)) => {
+                        let value = first * 10 + second;
+                        trace!("calibration value of {:?} is {}", line, value);
+                        Ok(value)
+                    }
+                    _ => Err(Error::NoDigit(line.to_string())),
+                }
+            })
+            .sum()
+    }
+}
+
+pub fn part2(input: &str) -> Result<u32, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -57,7 +75,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part2(include_str!("input2_test.txt"));
+        let result = part2(include_str!("input2_test.txt")).expect("run without errors");
         assert_eq!(result, 281);
     }
 }
@@ -0,0 +1,12 @@
+use grid::GridError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("no start tile `S` found")]
+    MissingStart,
+    #[error("the pipe loop breaks off at ({x}, {y})")]
+    BrokenLoop { x: usize, y: usize },
+}
@@ -1,50 +1,74 @@
+use crate::Error;
+use common::Solution;
+use grid::Grid;
 use itertools::Itertools;
+use log::trace;
 
-fn part1(input: &str, scale_factor: i64) -> i64 {
-    let grid: Vec<Vec<char>> = input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(|line| line.chars().collect())
-        .collect();
+common::params! {
+    pub struct Params {
+        /// how many rows or columns every empty row or column expands to
+        scale_factor: i64 = 1_000_000,
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Grid<char>;
+    type Answer = i64;
+    type Params = Params;
+    type Error = Error;
 
-    let empty_rows: Vec<usize> = grid
-        .iter()
-        .enumerate()
-        .filter_map(|(y, row)| row.iter().all(|c| *c == '.').then_some(y))
-        .collect();
-    let empty_cols: Vec<usize> = (0..grid[0].len())
-        .filter_map(|x| (0..grid.len()).all(|y| grid[y][x] == '.').then_some(x))
-        .collect();
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, params: &Params) -> Result<i64, Error> {
+        let empty_rows: Vec<usize> = grid
+            .rows()
+            .enumerate()
+            .filter_map(|(y, row)| row.iter().all(|c| *c == '.').then_some(y))
+            .collect();
+        let empty_cols: Vec<usize> = grid
+            .columns()
+            .enumerate()
+            .filter_map(|(x, mut column)| column.all(|c| *c == '.').then_some(x))
+            .collect();
 
-    let mut galaxies: Vec<(i64, i64)> = vec![];
-    for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
-            if grid[y][x] == '#' {
+        let mut galaxies: Vec<(i64, i64)> = vec![];
+        for ((x, y), &tile) in grid.iter() {
+            if tile == '#' {
                 let x_offset = empty_cols.iter().filter(|c| **c < x).count() as i64;
                 let y_offset = empty_rows.iter().filter(|r| **r < y).count() as i64;
 
-                // println!("At {}/{}, {} empty cols and {} empty rows", x, y, x_offset, y_offset);
+                trace!(
+                    "galaxy at ({}, {}) after {} empty columns and {} empty rows",
+                    x,
+                    y,
+                    x_offset,
+                    y_offset
+                );
 
                 galaxies.push((
-                    x as i64 + x_offset * (scale_factor - 1),
-                    y as i64 + y_offset * (scale_factor - 1),
+                    x as i64 + x_offset * (params.scale_factor - 1),
+                    y as i64 + y_offset * (params.scale_factor - 1),
                 ))
             }
         }
-    }
 
-    // compute manhattan distance of all pairs of galaxies
-    galaxies
-        .iter()
-        .tuple_combinations()
-        .map(|(a, b)| (a.0 - b.0).abs() + (a.1 - b.1).abs())
-        .sum()
+        Ok(
+            // compute manhattan distance of all pairs of galaxies
+            galaxies
+                .iter()
+                .tuple_combinations()
+                .map(|(a, b)| (a.0 - b.0).abs() + (a.1 - b.1).abs())
+                .sum(),
+        )
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input, 1_000_000);
-    dbg!(output);
+pub fn part2(input: &str, scale_factor: i64) -> Result<i64, Error> {
+    Part2::run(input, &Params { scale_factor })
 }
 
 #[cfg(test)]
@@ -53,13 +77,13 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"), 2);
+        let result = part2(include_str!("input1_test.txt"), 2).expect("run without errors");
         assert_eq!(result, 374);
 
-        let result = part1(include_str!("input1_test.txt"), 10);
+        let result = part2(include_str!("input1_test.txt"), 10).expect("run without errors");
         assert_eq!(result, 1030);
 
-        let result = part1(include_str!("input1_test.txt"), 100);
+        let result = part2(include_str!("input1_test.txt"), 100).expect("run without errors");
         assert_eq!(result, 8410);
     }
 }
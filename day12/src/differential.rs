@@ -0,0 +1,88 @@
+//! Count the arrangements of the same rows by trying every one of them, like
+//! the first part, and with the memoised counting of the second part.
+
+use common::differential::compare;
+
+use crate::{part1, part2};
+
+/// A row of springs and its groups of damaged ones.
+type Row = (String, Vec<usize>);
+
+fn rows(input: &str) -> Vec<Row> {
+    input
+        .lines()
+        .map(|line| {
+            let (springs, groups) = line.split_once(' ').expect("springs and groups");
+            let groups = groups.split(',').map(|n| n.parse().unwrap()).collect();
+            (springs.to_owned(), groups)
+        })
+        .collect()
+}
+
+fn slow((springs, groups): &Row) -> usize {
+    let groups: Vec<u32> = groups.iter().map(|&n| n as u32).collect();
+    part1::arrangements(&springs.chars().collect::<Vec<_>>(), &groups)
+}
+
+fn fast((springs, groups): &Row) -> usize {
+    part2::arrangements(springs, groups)
+}
+
+/// Rows with a spring less, an unknown spring known, a group less or a
+/// group smaller.
+fn shrink((springs, groups): &Row) -> Vec<Row> {
+    let mut smaller = vec![];
+    for (i, spring) in springs.char_indices() {
+        let mut fewer = springs.clone();
+        fewer.remove(i);
+        smaller.push((fewer, groups.clone()));
+        if spring == '?' {
+            for known in [".", "#"] {
+                let mut springs = springs.clone();
+                springs.replace_range(i..i + 1, known);
+                smaller.push((springs, groups.clone()));
+            }
+        }
+    }
+    for i in 0..groups.len() {
+        let mut fewer = groups.clone();
+        fewer.remove(i);
+        smaller.push((springs.clone(), fewer));
+        if groups[i] > 1 {
+            let mut groups = groups.clone();
+            groups[i] -= 1;
+            smaller.push((springs.clone(), groups));
+        }
+    }
+    smaller
+}
+
+#[test]
+fn agree_on_examples() {
+    let cases = rows(include_str!("input1_test.txt"));
+    if let Err(disagreement) = compare(cases, slow, fast, shrink) {
+        panic!("{}", disagreement);
+    }
+}
+
+#[test]
+fn agree_on_generated_rows() {
+    let generator = generate::find(12).expect("generator for day 12");
+    for seed in 0..10 {
+        let input = generator
+            .generate(seed, &[("rows", "200"), ("length", "14")])
+            .expect("valid parameters");
+        if let Err(disagreement) = compare(rows(&input), slow, fast, shrink) {
+            panic!("seed {}: {}", seed, disagreement);
+        }
+    }
+}
+
+#[test]
+fn shrink_rows() {
+    let smaller = shrink(&("?#".to_owned(), vec![2]));
+    assert!(smaller.contains(&("#".to_owned(), vec![2])));
+    assert!(smaller.contains(&(".#".to_owned(), vec![2])));
+    assert!(smaller.contains(&("?#".to_owned(), vec![1])));
+    assert!(smaller.contains(&("?#".to_owned(), vec![])));
+}
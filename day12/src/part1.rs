@@ -1,30 +1,21 @@
 extern crate nom;
 
-use std::str::FromStr;
-
+use crate::Error;
+use common::Solution;
+use log::{debug, trace, warn};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, one_of, space1},
-    combinator::map_res,
-    multi::{many1, separated_list1},
+    character::complete::{one_of, space1},
+    multi::many1,
     sequence::separated_pair,
-    IResult,
 };
+use parsing::{parse_lines, separated_number_list, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, (Vec<char>, Vec<u32>)> {
-    separated_pair(many1(one_of(".#?")), space1, number_list_parser)(i)
-}
-
-fn number_list_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(tag(","), int_parser)(i)
-}
-
-fn int_parser(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, FromStr::from_str)(i)
+fn line_parser(i: &str) -> IResult<'_, (Vec<char>, Vec<u32>)> {
+    separated_pair(many1(one_of(".#?")), space1, separated_number_list(','))(i)
 }
 
-fn is_valid_permutation(conditions: &Vec<char>, groups: &Vec<u32>, permutation: usize) -> bool {
-    // println!("Perm: {:?}, Groups: {:?}", permutation, groups);
+fn is_valid_permutation(conditions: &[char], groups: &[u32], permutation: usize) -> bool {
+    trace!("permutation {:b} for groups {:?}", permutation, groups);
     let mut contiguous_damaged_springs: u32 = 0;
     let mut question_mark_count: u32 = 0;
     let mut group_iter = groups.iter();
@@ -48,7 +39,7 @@ fn is_valid_permutation(conditions: &Vec<char>, groups: &Vec<u32>, permutation:
                     contiguous_damaged_springs = 0;
                 }
             }
-            _ => eprintln!("Invalid char in permutation: {}", spring),
+            _ => warn!("invalid char in permutation: {}", spring),
         }
     }
     // if permutation ends on #, check last group count
@@ -65,35 +56,38 @@ fn is_valid_permutation(conditions: &Vec<char>, groups: &Vec<u32>, permutation:
     group_iter.next().is_none()
 }
 
-fn part1(input: &str) -> usize {
-    let rows = input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect::<Vec<_>>();
+/// Number of ways to fill in the unknown springs of a row, counted by trying
+/// every one of them.
+pub fn arrangements(conditions: &[char], groups: &[u32]) -> usize {
+    let unknown = conditions.iter().filter(|&&spring| spring == '?').count();
+    (0..(2_usize.pow(unknown as u32)))
+        .filter(|&permutation| is_valid_permutation(conditions, groups, permutation))
+        .count()
+}
+
+pub struct Part1;
 
-    rows.into_iter()
-        .map(|(conditions, groups)| {
-            let unknown_indices = conditions
-                .iter()
-                .enumerate()
-                .filter_map(|(i, spring)| (*spring == '?').then_some(i))
-                .collect::<Vec<_>>();
+impl Solution for Part1 {
+    type Input<'a> = Vec<(Vec<char>, Vec<u32>)>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
 
-            (0..(2_usize.pow(unknown_indices.len() as u32)))
-                .filter_map(|permutation| {
-                    is_valid_permutation(&conditions, &groups, permutation).then_some(1)
-                })
-                .count()
-        })
-        .inspect(|count| println!("Valid permutations: {}", count))
-        .sum()
+    fn parse(input: &str) -> Result<Vec<(Vec<char>, Vec<u32>)>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(rows: &Vec<(Vec<char>, Vec<u32>)>, _params: &()) -> Result<usize, Error> {
+        Ok(rows
+            .iter()
+            .map(|(conditions, groups)| arrangements(conditions, groups))
+            .inspect(|count| debug!("{} valid permutations", count))
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<usize, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -111,7 +105,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 21);
     }
 }
@@ -1,42 +1,58 @@
 extern crate nom;
 
 use cached::proc_macro::cached;
-use std::str::FromStr;
 
+use crate::Error;
+use common::Solution;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, one_of, space1},
-    combinator::{map_res, recognize},
-    multi::{many1, separated_list1},
+    character::complete::{one_of, space1},
+    combinator::recognize,
+    multi::many1,
     sequence::separated_pair,
-    IResult,
 };
+use parsing::{parse_lines, separated_number_list, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, (&str, Vec<usize>)> {
-    separated_pair(recognize(many1(one_of(".#?"))), space1, number_list_parser)(i)
+fn line_parser(i: &str) -> IResult<'_, (&str, Vec<usize>)> {
+    separated_pair(
+        recognize(many1(one_of(".#?"))),
+        space1,
+        separated_number_list(','),
+    )(i)
 }
 
-fn number_list_parser(i: &str) -> IResult<&str, Vec<usize>> {
-    separated_list1(tag(","), int_parser)(i)
-}
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Vec<(&'a str, Vec<usize>)>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<(&str, Vec<usize>)>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
 
-fn int_parser(i: &str) -> IResult<&str, usize> {
-    map_res(digit1, FromStr::from_str)(i)
+    fn solve(rows: &Vec<(&str, Vec<usize>)>, _params: &()) -> Result<usize, Error> {
+        Ok(rows
+            .iter()
+            .map(|(conditions, groups)| {
+                // unfold the records to five copies of themselves
+                let conditions = [*conditions; 5].join("?");
+                let groups = groups.repeat(5);
+                arrangements(&conditions, &groups)
+            })
+            .sum())
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let unfolded_input = input.lines().fold(String::new(), |mut acc, line| {
-        let (c, g) = line.split_once(" ").expect("space-separated line");
-        acc.push_str(&format!("{c}?{c}?{c}?{c}?{c} {g},{g},{g},{g},{g}\n"));
-        acc
-    });
+pub fn part2(input: &str) -> Result<usize, Error> {
+    Part2::run(input, &())
+}
 
-    unfolded_input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(|line| line_parser(line).expect("valid input").1)
-        .map(|(conditions, groups)| permutations(conditions.as_bytes(), None, &groups))
-        .sum()
+/// Number of ways to fill in the unknown springs of a row, counted one spring
+/// at a time with the counts of the rest of the row memoised.
+pub fn arrangements(conditions: &str, groups: &[usize]) -> usize {
+    permutations(conditions.as_bytes(), None, groups)
 }
 
 #[cached(
@@ -47,7 +63,7 @@ fn permutations(input: &[u8], size: Option<usize>, groups: &[usize]) -> usize {
     if input.is_empty() {
         return match size {
             // final group has the right size
-            Some(n) if groups == &[n] => 1,
+            Some(n) if groups == [n] => 1,
             // all groups matched
             None if groups.is_empty() => 1,
             // unmatched groups remaining or wrong size, no possible permutations
@@ -73,12 +89,6 @@ fn permutations(input: &[u8], size: Option<usize>, groups: &[usize]) -> usize {
     }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,7 +101,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 525152);
     }
 }
@@ -1,5 +1,10 @@
-fn search_reflection(grid: &Vec<Vec<char>>) -> Option<usize> {
-    let width = grid[0].len();
+use crate::Error;
+use common::Solution;
+use grid::Grid;
+use log::debug;
+
+fn search_reflection(grid: &Grid<char>) -> Option<usize> {
+    let width = grid.width();
     for x in 0..width - 1 {
         let mut is_reflection = true;
         for d in 1..=width / 2 {
@@ -8,9 +13,7 @@ fn search_reflection(grid: &Vec<Vec<char>>) -> Option<usize> {
                 break;
             }
 
-            is_reflection &= (0..grid.len()).all(|y| {
-                grid[y][left_index as usize] == grid[y][x + d]
-            });
+            is_reflection &= grid.column(left_index as usize).eq(grid.column(x + d));
             if !is_reflection {
                 break;
             }
@@ -21,50 +24,42 @@ fn search_reflection(grid: &Vec<Vec<char>>) -> Option<usize> {
         }
     }
 
-    return None;
+    None
 }
 
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
-}
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<Grid<char>>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
 
-fn part1(input: &str) -> usize {
-    let patterns = input
-        .split("\n\n")
-        .map(|pattern| {
-            pattern
-                .lines()
-                .map(|line| line.chars().collect())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+    fn parse(input: &str) -> Result<Vec<Grid<char>>, Error> {
+        let patterns = input
+            .split("\n\n")
+            .map(Grid::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(patterns)
+    }
 
-    patterns
-        .iter()
-        .map(|pattern| {
-            let horizontal = search_reflection(pattern).unwrap_or(0);
-            let transposed = &transpose(pattern.clone());
-            let vertical = search_reflection(transposed).unwrap_or(0) * 100;
-            println!("H {} V {}", horizontal, vertical);
-            horizontal + vertical
-        })
-        .sum()
+    fn solve(patterns: &Vec<Grid<char>>, _params: &()) -> Result<usize, Error> {
+        Ok(patterns
+            .iter()
+            .map(|pattern| {
+                let horizontal = search_reflection(pattern).unwrap_or(0);
+                let transposed = &pattern.transpose();
+                let vertical = search_reflection(transposed).unwrap_or(0) * 100;
+                debug!("horizontal {} vertical {}", horizontal, vertical);
+                horizontal + vertical
+            })
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<usize, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -73,13 +68,20 @@ mod tests {
 
     #[test]
     fn test_reflection() {
-        let result = search_reflection(&vec!["#.##..##.".chars().collect()]);
+        let result = search_reflection(&Grid::parse("#.##..##.").unwrap());
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
+        assert_eq!(result, 405);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let input = include_str!("input1_test.txt").replace('\n', "\r\n");
+        let result = part1(&format!("\u{feff}{}\r\n", input)).expect("run without errors");
         assert_eq!(result, 405);
     }
 }
@@ -0,0 +1,10 @@
+use grid::GridError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("invalid tile {tile:?} at ({x}, {y})")]
+    InvalidTile { x: usize, y: usize, tile: char },
+}
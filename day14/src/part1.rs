@@ -1,37 +1,43 @@
-fn part1(input: &str) -> usize {
-    let grid = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
-
-    let mut rock_heights: Vec<usize> = vec!();
-    for x in 0..grid[0].len() {
-        let mut blocked_height = 0_usize;
-        for y in 0..grid.len() {
-            let tile = grid[y][x];
-            match tile {
-                '.' => continue,
-                '#' => blocked_height = y + 1,
-                'O' => {
-                    rock_heights.push(blocked_height);
-                    blocked_height += 1;
-                },
-                _ => eprintln!("Invalid character {}", tile),
+use crate::Error;
+use common::Solution;
+use grid::Grid;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Grid<char>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<usize, Error> {
+        let mut rock_heights: Vec<usize> = vec![];
+        for x in 0..grid.width() {
+            let mut blocked_height = 0_usize;
+            for (y, &tile) in grid.column(x).enumerate() {
+                match tile {
+                    '.' => continue,
+                    '#' => blocked_height = y + 1,
+                    'O' => {
+                        rock_heights.push(blocked_height);
+                        blocked_height += 1;
+                    }
+                    tile => return Err(Error::InvalidTile { x, y, tile }),
+                }
             }
         }
-    }
 
-    let height = grid.len();
-    rock_heights
-        .iter()
-        .map(|h| height - h)
-        .sum()
+        let height = grid.height();
+        Ok(rock_heights.iter().map(|h| height - h).sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<usize, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -40,7 +46,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 136);
     }
 }
@@ -1,152 +1,192 @@
-use std::{
-    collections::{hash_map::DefaultHasher, HashMap},
-    hash::Hasher,
-};
-
-#[derive(Debug)]
-struct HistoryEntry {
-    load: usize,
-    cycle: u32,
+use crate::Error;
+use animation::{Animate, Frame, Recorder};
+use common::{cycle::History, diagnostics, Solution};
+use grid::Grid;
+use log::{debug, info, trace};
+use raster::{palette, Image, Render};
+
+const SPIN_CYCLES: u64 = 1_000_000_000;
+
+fn load(grid: &Grid<char>) -> usize {
+    let height = grid.height();
+    grid.iter()
+        .map(|((_, y), &tile)| if tile == 'O' { height - y } else { 0 })
+        .sum()
 }
 
-fn part1(input: &str) -> usize {
-    let mut grid = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
-
-    let width = grid[0].len();
-    let height = grid.len();
-    let mut history: HashMap<u64, HistoryEntry> = HashMap::new();
-
-    let mut cycle_hash: u64 = 0;
-    let mut repeating_cycle: u32 = 0;
-
-    for cycle in 0..1_000_000_000 {
-        let load = (0..width)
-            .map(|x| {
-                (0..height)
-                    .map(|y| if grid[y][x] == 'O' { height - y } else { 0 })
-                    .sum::<usize>()
-            })
-            .sum::<usize>();
-
-        println!("Cycle {}, Load {}", cycle, load);
-        let board: String = grid
-            .iter()
-            .map(|row| row.iter().collect::<String>() + "\n")
-            .collect();
-        // println!("{}", board);
-
-        let mut hasher = DefaultHasher::new();
-        hasher.write(board.as_bytes());
-        let hash = hasher.finish();
-        if history.contains_key(&hash) {
-            repeating_cycle = cycle;
-            cycle_hash = hash;
-            break;
-        }
+/// Tilt the platform north, west, south and east once.
+fn spin(grid: &mut Grid<char>) -> Result<(), Error> {
+    let width = grid.width();
+    let height = grid.height();
 
-        history.entry(hash).or_insert(HistoryEntry { load, cycle });
-
-        // North
-        for x in 0..width {
-            let mut blocked_pos = 0_usize;
-            for y in 0..height {
-                let tile = grid[y][x];
-                match tile {
-                    '.' => continue,
-                    '#' => blocked_pos = y + 1,
-                    'O' => {
-                        grid[y][x] = '.';
-                        grid[blocked_pos][x] = 'O';
-                        blocked_pos += 1;
-                    }
-                    _ => eprintln!("Invalid character {}", tile),
+    // North
+    for x in 0..width {
+        let mut blocked_pos = 0_usize;
+        for y in 0..height {
+            let tile = grid[(x, y)];
+            match tile {
+                '.' => continue,
+                '#' => blocked_pos = y + 1,
+                'O' => {
+                    grid[(x, y)] = '.';
+                    grid[(x, blocked_pos)] = 'O';
+                    blocked_pos += 1;
                 }
+                tile => return Err(Error::InvalidTile { x, y, tile }),
             }
         }
+    }
 
-        // West
-        for y in 0..height {
-            let mut blocked_pos = 0_usize;
-            for x in 0..width {
-                let tile = grid[y][x];
-                match tile {
-                    '.' => continue,
-                    '#' => blocked_pos = x + 1,
-                    'O' => {
-                        grid[y][x] = '.';
-                        grid[y][blocked_pos] = 'O';
-                        blocked_pos += 1;
-                    }
-                    _ => eprintln!("Invalid character {}", tile),
+    // West
+    for y in 0..height {
+        let mut blocked_pos = 0_usize;
+        for x in 0..width {
+            let tile = grid[(x, y)];
+            match tile {
+                '.' => continue,
+                '#' => blocked_pos = x + 1,
+                'O' => {
+                    grid[(x, y)] = '.';
+                    grid[(blocked_pos, y)] = 'O';
+                    blocked_pos += 1;
                 }
+                tile => return Err(Error::InvalidTile { x, y, tile }),
             }
         }
+    }
 
-        // South
-        for x in 0..width {
-            let mut blocked_pos = height - 1;
-            for y in (0..height).rev() {
-                let tile = grid[y][x];
-                match tile {
-                    '.' => continue,
-                    '#' => blocked_pos = if y > 0 { y - 1 } else { 0 },
-                    'O' => {
-                        grid[y][x] = '.';
-                        grid[blocked_pos][x] = 'O';
-                        if blocked_pos > 0 {
-                            blocked_pos -= 1;
-                        }
-                    }
-                    _ => eprintln!("Invalid character {}", tile),
+    // South
+    for x in 0..width {
+        let mut blocked_pos = height - 1;
+        for y in (0..height).rev() {
+            let tile = grid[(x, y)];
+            match tile {
+                '.' => continue,
+                '#' => blocked_pos = if y > 0 { y - 1 } else { 0 },
+                'O' => {
+                    grid[(x, y)] = '.';
+                    grid[(x, blocked_pos)] = 'O';
+                    blocked_pos = blocked_pos.saturating_sub(1);
                 }
+                tile => return Err(Error::InvalidTile { x, y, tile }),
             }
         }
+    }
 
-        // East
-        for y in 0..height {
-            let mut blocked_pos = width - 1;
-            for x in (0..width).rev() {
-                let tile = grid[y][x];
-                match tile {
-                    '.' => continue,
-                    '#' => blocked_pos = if x > 0 { x - 1 } else { 0 },
-                    'O' => {
-                        grid[y][x] = '.';
-                        grid[y][blocked_pos] = 'O';
-                        if blocked_pos > 0 {
-                            blocked_pos -= 1;
-                        }
-                    }
-                    _ => eprintln!("Invalid character {}", tile),
+    // East
+    for y in 0..height {
+        let mut blocked_pos = width - 1;
+        for x in (0..width).rev() {
+            let tile = grid[(x, y)];
+            match tile {
+                '.' => continue,
+                '#' => blocked_pos = if x > 0 { x - 1 } else { 0 },
+                'O' => {
+                    grid[(x, y)] = '.';
+                    grid[(blocked_pos, y)] = 'O';
+                    blocked_pos = blocked_pos.saturating_sub(1);
                 }
+                tile => return Err(Error::InvalidTile { x, y, tile }),
             }
         }
     }
 
-    let prev_entry = history.get(&cycle_hash).expect("found previous entry");
-    println!(
-        "Found cycle! Current cycle {}, previous cycle {}, load {}",
-        repeating_cycle, prev_entry.cycle, prev_entry.load
+    Ok(())
+}
+
+/// The platform after a billion spin cycles, found by skipping ahead once
+/// the platform repeats an earlier state. `on_cycle` sees the platform
+/// before every cycle that is actually spun.
+fn final_platform<F>(grid: &Grid<char>, mut on_cycle: F) -> Result<Grid<char>, Error>
+where
+    F: FnMut(u32, &Grid<char>),
+{
+    let mut grid = grid.clone();
+    let mut history = History::new();
+
+    let repetition = loop {
+        let cycle = history.len() as u32;
+        debug!("cycle {} has load {}", cycle, load(&grid));
+        trace!("grid:\n{}", grid);
+        on_cycle(cycle, &grid);
+
+        if let Some(repetition) = history.push(grid.clone()) {
+            break repetition;
+        }
+        if u64::from(cycle) == SPIN_CYCLES {
+            return Ok(grid);
+        }
+        spin(&mut grid)?;
+    };
+
+    info!(
+        "cycle {} repeats cycle {} with load {}",
+        repetition.start + repetition.length,
+        repetition.start,
+        load(&grid)
     );
-    let cycle_len = repeating_cycle - prev_entry.cycle;
-    let final_offset = (1_000_000_000 - repeating_cycle) % cycle_len;
-    let final_cycle = prev_entry.cycle + final_offset;
-    let final_entry = history
-        .values()
-        .find(|entry| entry.cycle == final_cycle)
-        .expect("found final cycle entry");
-    println!("Final entry {:?}", final_entry);
-
-    final_entry.load
+    diagnostics::report("cycle_start", repetition.start);
+    diagnostics::report("cycle_length", repetition.length);
+    info!(
+        "cycle {} has the final load",
+        repetition.equivalent(SPIN_CYCLES)
+    );
+
+    Ok(history
+        .state_after(SPIN_CYCLES)
+        .expect("final cycle is recorded")
+        .clone())
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Grid<char>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<usize, Error> {
+        Ok(load(&final_platform(grid, |_, _| {})?))
+    }
+}
+
+/// Draws the rounded rocks where they end up after the last spin cycle.
+impl Render for Part2 {
+    fn render(grid: &Grid<char>, _params: &()) -> Result<Image, Error> {
+        Ok(final_platform(grid, |_, _| {})?.map(|&tile| match tile {
+            'O' => palette::PATH,
+            '#' => palette::WALL,
+            _ => palette::BACKGROUND,
+        }))
+    }
+}
+
+/// Plays the spin cycles until the platform repeats, then jumps to the end.
+impl Animate for Part2 {
+    fn animate(grid: &Grid<char>, _params: &(), recorder: &mut Recorder) -> Result<(), Error> {
+        let platform = final_platform(grid, |cycle, platform| {
+            recorder.record(|| {
+                Frame::new(
+                    format!("cycle {}, load {}", cycle, load(platform)),
+                    platform,
+                )
+            })
+        })?;
+        recorder.keep(Frame::new(
+            format!("cycle {}, load {}", SPIN_CYCLES, load(&platform)),
+            platform,
+        ));
+        Ok(())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<usize, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -155,7 +195,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 64);
     }
 }
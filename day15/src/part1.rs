@@ -1,3 +1,6 @@
+use crate::Error;
+use common::Solution;
+
 fn hash(step: &str) -> u32 {
     let mut result: u32 = 0;
     for c in step.as_bytes() {
@@ -8,18 +11,29 @@ fn hash(step: &str) -> u32 {
     result
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .replace("\n", "")
-        .split(",")
-        .map(hash)
-        .sum()
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<String>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(input
+            .replace("\n", "")
+            .split(",")
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn solve(steps: &Vec<String>, _params: &()) -> Result<u32, Error> {
+        Ok(steps.iter().map(|step| hash(step)).sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -27,7 +41,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 1320);
     }
 }
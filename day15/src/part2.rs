@@ -1,3 +1,6 @@
+use crate::Error;
+use common::Solution;
+
 #[derive(Debug, Clone)]
 struct Lens {
     label: String,
@@ -14,57 +17,75 @@ fn hash(step: &str) -> u32 {
     result
 }
 
-fn part1(input: &str) -> usize {
-    let mut boxes: Vec<Vec<Lens>> = vec![vec!(); 256];
-    let input = input.replace("\n", "");
-    let steps = input.split(",").collect::<Vec<&str>>();
+pub struct Part2;
 
-    for step in steps {
-        if step.ends_with('-') {
-            let label = step.strip_suffix('-').expect("- terminated string");
-            let box_id = hash(label);
-            let lenses = boxes.get_mut(box_id as usize).expect("box found");
-            let index = lenses.iter().position(|l| l.label == label);
-            if index.is_some() {
-                lenses.remove(index.unwrap());
-            }
-        } else if step.contains('=') {
-            let (label, num) = step.split_once('=').expect("= separated string");
-            let box_id = hash(label);
-            let lenses = boxes.get_mut(box_id as usize).expect("box found");
-            let focal_length = num.parse().expect("valid focal length number");
-            let index = lenses.iter().position(|l| l.label == label);
-            let lens = Lens {
-                label: label.to_owned(),
-                focal_length,
-            };
-            if index.is_some() {
-                lenses[index.unwrap()] = lens;
+impl Solution for Part2 {
+    type Input<'a> = Vec<String>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        let steps: Vec<String> = input
+            .replace("\n", "")
+            .split(",")
+            .map(str::to_owned)
+            .collect();
+
+        Ok(steps)
+    }
+
+    fn solve(steps: &Vec<String>, _params: &()) -> Result<usize, Error> {
+        let mut boxes: Vec<Vec<Lens>> = vec![vec!(); 256];
+
+        for step in steps {
+            let step = step.as_str();
+            if step.ends_with('-') {
+                let label = step.strip_suffix('-').expect("- terminated string");
+                let box_id = hash(label);
+                let lenses = boxes.get_mut(box_id as usize).expect("box found");
+                let index = lenses.iter().position(|l| l.label == label);
+                if let Some(index) = index {
+                    lenses.remove(index);
+                }
+            } else if step.contains('=') {
+                let (label, num) = step.split_once('=').expect("= separated string");
+                let box_id = hash(label);
+                let lenses = boxes.get_mut(box_id as usize).expect("box found");
+                let focal_length = num
+                    .parse()
+                    .map_err(|_| Error::InvalidStep(step.to_owned()))?;
+                let index = lenses.iter().position(|l| l.label == label);
+                let lens = Lens {
+                    label: label.to_owned(),
+                    focal_length,
+                };
+                if let Some(index) = index {
+                    lenses[index] = lens;
+                } else {
+                    lenses.push(lens);
+                }
             } else {
-                lenses.push(lens);
+                return Err(Error::InvalidStep(step.to_owned()));
             }
-        } else {
-            eprintln!("Invalid step: {}", step);
         }
-    }
 
-    boxes
-        .iter()
-        .enumerate()
-        .map(|(box_id, lenses)| {
-            lenses
-                .iter()
-                .enumerate()
-                .map(|(slot, lens)| (box_id + 1) * (slot + 1) * lens.focal_length as usize)
-                .sum::<usize>()
-        })
-        .sum()
+        Ok(boxes
+            .iter()
+            .enumerate()
+            .map(|(box_id, lenses)| {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(|(slot, lens)| (box_id + 1) * (slot + 1) * lens.focal_length as usize)
+                    .sum::<usize>()
+            })
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<usize, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -72,7 +93,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 145);
     }
 }
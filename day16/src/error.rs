@@ -0,0 +1,12 @@
+use grid::GridError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("invalid tile {tile:?} at ({x}, {y})")]
+    InvalidTile { x: usize, y: usize, tile: char },
+    #[error("no tiles on the edge to shoot the beam from")]
+    NoEdgeTiles,
+}
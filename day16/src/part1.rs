@@ -1,126 +1,187 @@
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
-}
+use crate::Error;
+use animation::{Animate, Frame, Recorder};
+use common::Solution;
+use grid::{Direction, Grid};
+use log::trace;
+use raster::{palette, Image, Render};
 use Direction::*;
 
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            NORTH => (0, -1),
-            EAST => (1, 0),
-            SOUTH => (0, 1),
-            WEST => (-1, 0),
-        }
-    }
-
-    fn bit(&self) -> u32 {
-        match self {
-            NORTH => 1 << 0,
-            EAST => 1 << 1,
-            SOUTH => 1 << 2,
-            WEST => 1 << 3,
-        }
-    }
-}
-
-fn is_in_bounds(position: (i32, i32), width: usize, height: usize) -> bool {
-    position.0 >= 0 && position.1 >= 0 && position.0 < width as i32 && position.1 < height as i32
-}
-
-fn shoot_laser(
-    grid: &Vec<Vec<char>>,
-    status_grid: &mut Vec<Vec<u32>>,
+fn shoot_laser<F>(
+    grid: &Grid<char>,
+    status_grid: &mut Grid<u8>,
     initial_direction: Direction,
-    initial_position: (i32, i32),
-) {
-    let mut position = (initial_position.0, initial_position.1);
+    initial_position: (usize, usize),
+    on_step: &mut F,
+) -> Result<(), Error>
+where
+    F: FnMut(&Grid<u8>, (usize, usize)),
+{
+    let mut position = Some(initial_position);
     let mut direction = initial_direction;
-    let width = grid[0].len();
-    let height = grid.len();
 
-    while is_in_bounds(position, width, height) {
-        if (status_grid[position.1 as usize][position.0 as usize] & direction.bit()) > 0 {
-            println!("Been there before, stopping!");
+    while let Some(pos) = position {
+        if (status_grid[pos] & direction.bit()) > 0 {
+            trace!("been at {:?} going {:?} before, stopping", pos, direction);
             break;
         }
-        status_grid[position.1 as usize][position.0 as usize] |= direction.bit();
-        let tile = grid[position.1 as usize][position.0 as usize];
-        println!("At {:?} going {:?} found {}", position, direction, tile);
+        status_grid[pos] |= direction.bit();
+        on_step(status_grid, pos);
+        let tile = grid[pos];
+        trace!("at {:?} going {:?} found {}", pos, direction, tile);
 
         match tile {
             '.' => {}
             '/' => {
                 direction = match direction {
-                    NORTH => EAST,
-                    EAST => NORTH,
-                    SOUTH => WEST,
-                    WEST => SOUTH,
+                    North => East,
+                    East => North,
+                    South => West,
+                    West => South,
                 };
             }
             '\\' => {
                 direction = match direction {
-                    NORTH => WEST,
-                    EAST => SOUTH,
-                    SOUTH => EAST,
-                    WEST => NORTH,
+                    North => West,
+                    East => South,
+                    South => East,
+                    West => North,
                 };
             }
             '|' => {
-                if direction == EAST || direction == WEST {
-                    println!("Splitting vertically!");
-                    direction = NORTH;
-                    shoot_laser(grid, status_grid, SOUTH, (position.0, position.1 + 1));
+                if direction.is_horizontal() {
+                    trace!("splitting vertically");
+                    direction = North;
+                    if let Some(below) = grid.step(pos, South) {
+                        shoot_laser(grid, status_grid, South, below, on_step)?;
+                    }
                 }
             }
             '-' => {
-                if direction == NORTH || direction == SOUTH {
-                    println!("Splitting horizontally!");
-                    direction = WEST;
-                    shoot_laser(grid, status_grid, EAST, (position.0 + 1, position.1));
+                if direction.is_vertical() {
+                    trace!("splitting horizontally");
+                    direction = West;
+                    if let Some(right) = grid.step(pos, East) {
+                        shoot_laser(grid, status_grid, East, right, on_step)?;
+                    }
                 }
             }
-            _ => eprintln!("Invalid tile {}", tile),
+            _ => {
+                let (x, y) = pos;
+                return Err(Error::InvalidTile { x, y, tile });
+            }
+        }
+        position = grid.step(pos, direction);
+    }
+    Ok(())
+}
+
+/// Follow the beam entering the top left corner heading east, returning the
+/// directions it passed every tile in as bits. `on_step` sees every tile the
+/// beam enters.
+fn energize<F>(grid: &Grid<char>, mut on_step: F) -> Result<Grid<u8>, Error>
+where
+    F: FnMut(&Grid<u8>, (usize, usize)),
+{
+    if grid.width() == 0 || grid.height() == 0 {
+        return Err(Error::NoEdgeTiles);
+    }
+    let mut status_grid = Grid::new(grid.width(), grid.height(), 0_u8);
+
+    shoot_laser(grid, &mut status_grid, East, (0, 0), &mut on_step)?;
+
+    trace!(
+        "energized tiles:\n{}",
+        status_grid.map(|&s| if s > 0 { '#' } else { '.' })
+    );
+
+    Ok(status_grid)
+}
+
+fn count_energized(status_grid: &Grid<u8>) -> usize {
+    status_grid.iter().filter(|(_, &s)| s > 0).count()
+}
+
+/// The contraption with the energized empty tiles as `#`.
+fn beam_map(grid: &Grid<char>, status_grid: &Grid<u8>, head: Option<(usize, usize)>) -> Grid<char> {
+    let mut map = grid.clone();
+    for (position, &status) in status_grid.iter() {
+        if status > 0 && map[position] == '.' {
+            map[position] = '#';
+        }
+    }
+    if let Some(head) = head {
+        map[head] = '@';
+    }
+    map
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Grid<char>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<u32, Error> {
+        let status_grid = energize(grid, |_, _| {})?;
+
+        Ok(count_energized(&status_grid) as u32)
+    }
+}
+
+/// Draws the energized tiles, with the mirrors and splitters on top, tinted
+/// where the beam hits them.
+impl Render for Part1 {
+    fn render(grid: &Grid<char>, _params: &()) -> Result<Image, Error> {
+        let status_grid = energize(grid, |_, _| {})?;
+
+        let mut image = status_grid.map(|&s| {
+            if s > 0 {
+                palette::ENERGIZED
+            } else {
+                palette::BACKGROUND
+            }
+        });
+        for (position, &tile) in grid.iter() {
+            if tile != '.' {
+                image[position] = palette::WALL.mix(image[position], 0.4);
+            }
         }
-        let delta = direction.delta();
-        position.0 += delta.0;
-        position.1 += delta.1;
+
+        Ok(image)
     }
 }
 
-fn part1(input: &str) -> u32 {
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect();
-
-    let width = grid[0].len();
-    let height = grid.len();
-    let mut status_grid: Vec<Vec<u32>> = vec![vec![0; width]; height];
-
-    shoot_laser(&grid, &mut status_grid, EAST, (0, 0));
-
-    status_grid
-        .iter()
-        .inspect(|row| {
-            println!(
-                "{}",
-                row.iter()
-                    .map(|s| if *s > 0 { '#' } else { '.' })
-                    .collect::<String>()
-            )
-        })
-        .map(|row| row.iter().map(|s| (*s > 0) as u32).sum::<u32>())
-        .sum()
+/// Follows the beam tile by tile, marking the head with `@`.
+impl Animate for Part1 {
+    fn animate(grid: &Grid<char>, _params: &(), recorder: &mut Recorder) -> Result<(), Error> {
+        let status_grid = energize(grid, |status_grid, head| {
+            recorder.record(|| {
+                Frame::new(
+                    format!(
+                        "beam at {:?}, {} tiles energized",
+                        head,
+                        count_energized(status_grid)
+                    ),
+                    beam_map(grid, status_grid, Some(head)),
+                )
+            })
+        })?;
+        recorder.keep(Frame::new(
+            format!("{} tiles energized", count_energized(&status_grid)),
+            beam_map(grid, &status_grid, None),
+        ));
+        Ok(())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -128,7 +189,13 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 46);
     }
+
+    #[test]
+    fn empty_grid() {
+        let grid = Grid::new(0, 0, '.');
+        assert!(matches!(Part1::solve(&grid, &()), Err(Error::NoEdgeTiles)));
+    }
 }
@@ -0,0 +1,10 @@
+use grid::GridError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("no path from the lava pool to the factory")]
+    NoPath,
+}
@@ -0,0 +1,6 @@
+mod error;
+pub mod part1;
+pub mod part2;
+mod render;
+
+pub use error::Error;
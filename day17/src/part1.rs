@@ -1,67 +1,57 @@
-use array2d::{Array2D, Error};
-use pathfinding::directed::astar::astar;
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+use crate::{render::draw_path, Error};
+use common::{diagnostics, Solution};
+use grid::{Direction, Grid};
+use log::{log_enabled, trace, Level};
+use raster::{Image, Render};
+use search::{Path, Search, State};
 use Direction::*;
 
-#[derive(Debug, PartialEq, EnumIter, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
+/// A crucible at a city block, having moved `count + 1` blocks in a row in
+/// its direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Pos {
+    x: usize,
+    y: usize,
+    direction: Direction,
+    count: u32,
 }
 
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            NORTH => (0, -1),
-            EAST => (1, 0),
-            SOUTH => (0, 1),
-            WEST => (-1, 0),
-        }
+impl Pos {
+    fn at(&self, (x, y): (usize, usize)) -> bool {
+        (self.x, self.y) == (x, y)
     }
+}
 
-    fn opposite(&self) -> Direction {
-        match self {
-            NORTH => SOUTH,
-            EAST => WEST,
-            SOUTH => NORTH,
-            WEST => EAST,
-        }
-    }
+/// The bottom right corner, where the crucible goes.
+fn target(grid: &Grid<u32>) -> (usize, usize) {
+    (grid.width() - 1, grid.height() - 1)
+}
 
-    fn char(&self) -> char {
-        match self {
-            NORTH => '^',
-            EAST => '>',
-            SOUTH => 'v',
-            WEST => '<',
-        }
+impl State for Pos {
+    type Context = Grid<u32>;
+    type Cost = u32;
+
+    fn successors(&self, grid: &Grid<u32>) -> Vec<(Pos, u32)> {
+        get_successors(grid, *self)
     }
-}
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
-struct Pos {
-    x: i32,
-    y: i32,
-    direction: Direction,
-    count: u32,
-}
-impl PartialEq for Pos {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+    fn is_goal(&self, grid: &Grid<u32>) -> bool {
+        self.at(target(grid))
     }
-}
 
-fn is_in_bounds(position: (i32, i32), width: usize, height: usize) -> bool {
-    position.0 >= 0 && position.1 >= 0 && position.0 < width as i32 && position.1 < height as i32
+    // manhattan distance heuristic
+    fn heuristic(&self, grid: &Grid<u32>) -> u32 {
+        let (x, y) = target(grid);
+        (self.x.abs_diff(x) + self.y.abs_diff(y)) as u32
+    }
 }
 
-fn get_successors(grid: &Array2D<u32>, pos: Pos) -> Vec<(Pos, u32)> {
+fn get_successors(grid: &Grid<u32>, pos: Pos) -> Vec<(Pos, u32)> {
     Direction::iter()
         .filter_map(|direction| {
-            if direction == pos.direction.opposite() {
+            // no turning back, except at the start where the crucible can go
+            // either way
+            if direction == pos.direction.opposite() && !pos.at((0, 0)) {
                 return None;
             }
             let direction_count = if direction == pos.direction {
@@ -74,81 +64,90 @@ fn get_successors(grid: &Array2D<u32>, pos: Pos) -> Vec<(Pos, u32)> {
             if direction_count > 2 {
                 return None;
             }
-            let delta = direction.delta();
-            let new_pos = (pos.x + delta.0, pos.y + delta.1);
-            is_in_bounds(new_pos, grid.num_columns(), grid.num_rows()).then(|| {
+            grid.step((pos.x, pos.y), direction).map(|(x, y)| {
                 (
                     Pos {
-                        x: new_pos.0,
-                        y: new_pos.1,
+                        x,
+                        y,
                         direction,
                         count: direction_count,
                     },
-                    *grid
-                        .get(new_pos.0 as usize, new_pos.1 as usize)
-                        .expect("found heat loss"),
+                    grid[(x, y)],
                 )
             })
         })
         .collect()
 }
 
-fn part1(input: &str) -> Result<u32, Error> {
-    let lines: Vec<Vec<u32>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).expect("valid digit"))
-                .collect::<Vec<u32>>()
-        })
-        .collect();
-
-    let grid = Array2D::from_rows(&lines)?;
-
-    let width = grid.num_columns();
-    let height = grid.num_rows();
+/// The path with the least heat loss from the top left to the bottom right
+/// corner, and its heat loss.
+fn find_path(grid: &Grid<u32>) -> Result<(Vec<Pos>, u32), Error> {
+    let width = grid.width();
+    let height = grid.height();
 
     let position = Pos {
         x: 0,
         y: 0,
-        direction: NORTH,
-        count: 0,
-    };
-    let target = Pos {
-        x: width as i32 - 1,
-        y: height as i32 - 1,
-        direction: EAST,
+        direction: North,
         count: 0,
     };
 
-    let (path, heat_loss) = astar(
-        &position,
-        |p| get_successors(&grid, *p),
-        // manhattan distance heuristic
-        |p| ((p.x - target.x).abs() + (p.y - target.y).abs()) as u32,
-        |p| *p == target,
-    )
-    .expect("found path");
-
-    for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let pos = path.iter().find(|p| p.x == x && p.y == y);
-            if pos.is_some() {
-                print!("{}", pos.unwrap().direction.char());
-            } else {
-                print!("{}", grid.get(x as usize, y as usize).unwrap());
+    let mut search = Search::new(grid);
+    let Path {
+        states: path,
+        cost: heat_loss,
+    } = search.astar(position).ok_or(Error::NoPath)?;
+    diagnostics::report("expanded_states", search.stats().expanded);
+
+    if log_enabled!(Level::Trace) {
+        let mut map = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pos = path.iter().find(|p| p.x == x && p.y == y);
+                if let Some(pos) = pos {
+                    map.push(pos.direction.arrow());
+                } else {
+                    map.push_str(&grid[(x, y)].to_string());
+                }
             }
+            map.push('\n');
         }
-        println!();
+        trace!("path:\n{}", map);
     }
 
-    Ok(heat_loss)
+    Ok((path, heat_loss))
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input).expect("found result");
-    dbg!(output);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Grid<u32>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<u32>, Error> {
+        Ok(Grid::parse_with(input, |c| c.to_digit(10))?)
+    }
+
+    fn solve(grid: &Grid<u32>, _params: &()) -> Result<u32, Error> {
+        let (_, heat_loss) = find_path(grid)?;
+
+        Ok(heat_loss)
+    }
+}
+
+/// Draws the path over the city blocks, shaded by their heat loss.
+impl Render for Part1 {
+    fn render(grid: &Grid<u32>, _params: &()) -> Result<Image, Error> {
+        let (path, _) = find_path(grid)?;
+
+        Ok(draw_path(grid, path.iter().map(|p| (p.x, p.y))))
+    }
+}
+
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -159,4 +158,11 @@ mod tests {
         let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 102);
     }
+
+    #[test]
+    fn wider_than_high() {
+        // at most three blocks east in a row, so down a block before the end
+        let result = part1("11119\n99911\n").expect("run without errors");
+        assert_eq!(result, 5);
+    }
 }
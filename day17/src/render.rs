@@ -0,0 +1,17 @@
+use grid::Grid;
+use raster::{palette, Image};
+
+/// Shade every block by its heat loss, darker for less, and draw the path
+/// over it.
+pub(crate) fn draw_path<I>(grid: &Grid<u32>, path: I) -> Image
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut image = grid
+        .map(|&loss| palette::BACKGROUND.mix(palette::WALL, loss.saturating_sub(1) as f64 / 8.0));
+    for position in path {
+        image[position] = palette::PATH;
+    }
+    image[(0, 0)] = palette::START;
+    image
+}
@@ -0,0 +1,158 @@
+//! Measure the same lagoons by flood filling a grid, like the first part, and
+//! with the area and boundary of the polygon, like the second part.
+
+use std::collections::HashSet;
+
+use common::differential::compare;
+
+use crate::{part1, part2};
+
+type Plan = Vec<(char, u32)>;
+
+fn plan(input: &str) -> Plan {
+    input
+        .lines()
+        .map(|line| {
+            let mut words = line.split(' ');
+            let direction = words.next().and_then(|d| d.chars().next()).unwrap();
+            (direction, words.next().unwrap().parse().unwrap())
+        })
+        .collect()
+}
+
+fn slow(plan: &Plan) -> Result<i64, String> {
+    part1::lagoon_volume(plan)
+        .map(i64::from)
+        .map_err(|e| e.to_string())
+}
+
+fn fast(plan: &Plan) -> Result<i64, String> {
+    part2::lagoon_volume(plan).map_err(|e| e.to_string())
+}
+
+fn step(direction: char) -> (i32, i32) {
+    match direction {
+        'R' => (1, 0),
+        'D' => (0, 1),
+        'L' => (-1, 0),
+        _ => (0, -1),
+    }
+}
+
+/// Whether both can measure the plan: a loop that stays clear of the edges of
+/// the flood fill's grid and never touches itself, not even with trenches
+/// side by side, which would shut in cells outside the loop.
+fn fillable(plan: &Plan) -> bool {
+    let (mut x, mut y) = (0, 0);
+    let mut dug = HashSet::new();
+    for &(direction, steps) in plan {
+        let (dx, dy) = step(direction);
+        for _ in 0..steps {
+            (x, y) = (x + dx, y + dy);
+            if !dug.insert((x, y)) || x.abs() >= 399 || y.abs() >= 399 {
+                return false;
+            }
+        }
+    }
+    (x, y) == (0, 0)
+        && dug.len() >= 4
+        && dug.iter().all(|&(x, y)| {
+            let neighbours = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+            neighbours.iter().filter(|n| dug.contains(n)).count() == 2
+        })
+}
+
+/// Plans with two trenches of opposite directions shortened by the same
+/// length, which keeps the loop closed.
+fn shrink(plan: &Plan) -> Vec<Plan> {
+    let opposite = |a: char, b: char| step(a).0 == -step(b).0 && step(a).1 == -step(b).1;
+    let mut smaller = vec![];
+    for i in 0..plan.len() {
+        for j in i + 1..plan.len() {
+            if !opposite(plan[i].0, plan[j].0) {
+                continue;
+            }
+            let shortest = plan[i].1.min(plan[j].1);
+            for by in [shortest, shortest / 2, 1] {
+                if by == 0 {
+                    continue;
+                }
+                let mut shorter = plan.clone();
+                shorter[i].1 -= by;
+                shorter[j].1 -= by;
+                // join the trenches left on either side of a removed one
+                let mut joined: Plan = vec![];
+                for (direction, steps) in shorter.into_iter().filter(|&(_, steps)| steps > 0) {
+                    match joined.last_mut() {
+                        Some(last) if last.0 == direction => last.1 += steps,
+                        _ => joined.push((direction, steps)),
+                    }
+                }
+                if fillable(&joined) {
+                    smaller.push(joined);
+                }
+            }
+        }
+    }
+    smaller
+}
+
+/// The plan turned a quarter clockwise `turns` times, and dug backwards if
+/// `reversed`, which gives the same lagoon in every orientation.
+fn turned(plan: &Plan, turns: usize, reversed: bool) -> Plan {
+    const CLOCKWISE: [char; 4] = ['R', 'D', 'L', 'U'];
+    let turn = |direction: char, by: usize| {
+        let at = CLOCKWISE.iter().position(|&d| d == direction).unwrap();
+        CLOCKWISE[(at + by) % 4]
+    };
+    let mut plan: Plan = plan
+        .iter()
+        .map(|&(d, steps)| (turn(d, turns), steps))
+        .collect();
+    if reversed {
+        plan.reverse();
+        plan.iter_mut().for_each(|(d, _)| *d = turn(*d, 2));
+    }
+    plan
+}
+
+#[test]
+fn agree_on_example() {
+    let example = plan(include_str!("input1_test.txt"));
+    let cases: Vec<Plan> = (0..8).map(|i| turned(&example, i % 4, i >= 4)).collect();
+    assert!(cases.iter().all(fillable));
+    if let Err(disagreement) = compare(cases, slow, fast, shrink) {
+        panic!("{}", disagreement);
+    }
+}
+
+#[test]
+fn agree_on_generated_plans() {
+    let generator = generate::find(18).expect("generator for day 18");
+    for seed in 0..10 {
+        let input = generator
+            .generate(seed, &[("width", "4"), ("height", "4"), ("step", "4")])
+            .expect("valid parameters");
+        let plan = plan(&input);
+        assert!(fillable(&plan), "seed {} gives an unfillable plan", seed);
+        if let Err(disagreement) = compare([plan], slow, fast, shrink) {
+            panic!("seed {}: {}", seed, disagreement);
+        }
+    }
+}
+
+#[test]
+fn shrink_into_smaller_loops() {
+    let square = vec![('R', 3), ('D', 3), ('L', 3), ('U', 3)];
+    assert!(fillable(&square));
+    let smaller = shrink(&square);
+    assert!(smaller.contains(&vec![('R', 2), ('D', 3), ('L', 2), ('U', 3)]));
+    assert!(smaller.contains(&vec![('R', 3), ('D', 2), ('L', 3), ('U', 2)]));
+    assert!(smaller.iter().all(fillable));
+
+    // shortening two trenches of different directions breaks the loop
+    assert!(!fillable(&vec![('R', 1), ('D', 2), ('L', 2), ('U', 1)]));
+    assert!(!fillable(&vec![('R', 1), ('L', 1)]));
+    // trenches side by side
+    assert!(!fillable(&vec![('R', 2), ('D', 1), ('L', 2), ('U', 1)]));
+}
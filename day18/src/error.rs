@@ -0,0 +1,14 @@
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("invalid direction {0:?}")]
+    InvalidDirection(char),
+    #[error("invalid color {0:?}")]
+    InvalidColor(String),
+    #[error("the trench leaves the dig site: {0}")]
+    OutOfBounds(#[from] array2d::Error),
+}
@@ -0,0 +1,7 @@
+#[cfg(test)]
+mod differential;
+mod error;
+pub mod part1;
+pub mod part2;
+
+pub use error::Error;
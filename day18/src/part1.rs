@@ -1,27 +1,32 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::collections::VecDeque;
 
-use array2d::{Array2D, Error};
+use crate::Error;
+use array2d::Array2D;
+use common::Solution;
+use log::{log_enabled, trace, Level};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, digit1, one_of, space1},
-    combinator::map_res,
+    bytes::complete::take_while_m_n,
+    character::complete::{one_of, space1},
+    error::context,
     sequence::{delimited, tuple},
-    IResult,
 };
+use parsing::{int_parser, parse_lines, token, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, (char, u32, &str)> {
-    let (i, (direction, _, steps, _, color)) = tuple((
-        one_of("RDLU"),
-        space1,
-        int_parser,
-        space1,
-        delimited(tag("(#"), alphanumeric1, tag(")")),
-    ))(i)?;
+fn line_parser(i: &str) -> IResult<'_, (char, u32, &str)> {
+    let (i, (direction, _, steps, _, color)) =
+        tuple((one_of("RDLU"), space1, int_parser, space1, color_parser))(i)?;
     Ok((i, (direction, steps, color)))
 }
 
-fn int_parser(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, FromStr::from_str)(i)
+fn color_parser(i: &str) -> IResult<'_, &str> {
+    delimited(
+        token("(#"),
+        context(
+            "color",
+            take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+        ),
+        token(")"),
+    )(i)
 }
 
 fn flood_fill(
@@ -59,57 +64,73 @@ fn flood_fill(
     Ok(())
 }
 
-fn part1(input: &str) -> Result<u32, Error> {
-    let lines: Vec<(char, u32, &str)> = input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect();
-
+/// Cubic metres the lagoon holds after digging the plan of directions and
+/// distances, counted by drawing the trench on a grid and filling the outside.
+pub fn lagoon_volume(plan: &[(char, u32)]) -> Result<u32, Error> {
     let mut grid = Array2D::filled_with('.', 800, 800);
     let mut position = (400, 400);
 
     grid.set(position.1 as usize, position.0 as usize, '#')?;
 
-    for (dir, steps, _color) in lines {
+    for &(dir, steps) in plan {
         let direction = match dir {
             'R' => (1, 0),
             'L' => (-1, 0),
             'U' => (0, -1),
             'D' => (0, 1),
-            _ => {
-                eprintln!("Invalid input! {}", dir);
-                (0, 0)
-            }
+            _ => return Err(Error::InvalidDirection(dir)),
         };
 
-        for _step in 0..steps {
+        for step in 0..steps {
             position = (position.0 + direction.0, position.1 + direction.1);
-            // println!("Step {} at {:?}", _step, position);
+            trace!("step {} at {:?}", step, position);
             grid.set(position.1 as usize, position.0 as usize, '#')?;
         }
     }
 
-    flood_fill(position.1 as usize + 1, position.0 as usize + 1, '.', '#', &mut grid)?;
+    // the outside is the one region sure to have a cell we know, the corner,
+    // whichever way the trench goes
+    flood_fill(0, 0, '.', ' ', &mut grid)?;
 
-    // for y in 0..height as i32 {
-    //     for x in 0..width as i32 {
-    //         print!("{}", grid.get(y as usize, x as usize).unwrap());
-    //     }
-    //     println!();
-    // }
+    if log_enabled!(Level::Trace) {
+        let map: String = grid
+            .rows_iter()
+            .flat_map(|row| row.chain(&['\n']))
+            .collect();
+        trace!("filled outside:\n{}", map);
+    }
 
     let volume = grid
         .rows_iter()
-        .map(|row| row.filter(|c| **c == '#').count() as u32)
+        .map(|row| row.filter(|c| **c != ' ').count() as u32)
         .sum();
 
     Ok(volume)
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input).expect("found result");
-    dbg!(output);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<(char, u32, &'a str)>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<(char, u32, &str)>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(lines: &Vec<(char, u32, &str)>, _params: &()) -> Result<u32, Error> {
+        let plan: Vec<(char, u32)> = lines
+            .iter()
+            .map(|&(dir, steps, _color)| (dir, steps))
+            .collect();
+        lagoon_volume(&plan)
+    }
+}
+
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -1,81 +1,102 @@
-use polygonical::{point::Point, polygon::Polygon};
-
+use crate::Error;
+use common::{polygon::Polygon, Solution};
+use log::{debug, trace};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, digit1, one_of, space1},
+    bytes::complete::take_while_m_n,
+    character::complete::{digit1, one_of, space1},
+    error::context,
     sequence::{delimited, tuple},
-    IResult,
 };
+use parsing::{parse_lines, token, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, &str> {
-    let (i, (_, _, _, _, color)) = tuple((
-        one_of("RDLU"),
-        space1,
-        digit1,
-        space1,
-        delimited(tag("(#"), alphanumeric1, tag(")")),
-    ))(i)?;
+fn line_parser(i: &str) -> IResult<'_, &str> {
+    let (i, (_, _, _, _, color)) =
+        tuple((one_of("RDLU"), space1, digit1, space1, color_parser))(i)?;
     Ok((i, color))
 }
 
-fn polygon_perimeter(points: &[Point]) -> f64 {
-    let mut perimeter = 0.0;
-    for i in 0..points.len() {
-        let j = (i + 1) % points.len();
-        perimeter += (points[i].x - points[j].x).abs();
-        perimeter += (points[i].y - points[j].y).abs();
-    }
-    perimeter
+fn color_parser(i: &str) -> IResult<'_, &str> {
+    delimited(
+        token("(#"),
+        context(
+            "color",
+            take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+        ),
+        token(")"),
+    )(i)
 }
 
-fn part1(input: &str) -> i64 {
-    let lines: Vec<&str> = input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect();
-
-    let mut points: Vec<Point> = vec![];
-    let mut position = (0, 0);
+/// Cubic metres the lagoon holds after digging the plan of directions and
+/// distances: the cells of the trench, which are the lattice points on the
+/// polygon it runs along, and the cells inside.
+pub fn lagoon_volume(plan: &[(char, u32)]) -> Result<i64, Error> {
+    let mut points = vec![];
+    let mut position: (i64, i64) = (0, 0);
 
-    points.push(Point::new(position.0, position.1));
-
-    for color in lines {
-        let mut steps_hex = color.to_owned();
-        let direction_char = steps_hex.pop().expect("found direction char");
-        let direction = match direction_char {
-            '0' => (1, 0),
-            '2' => (-1, 0),
-            '3' => (0, -1),
-            '1' => (0, 1),
-            _ => {
-                eprintln!("Invalid input! {}", direction_char);
-                (0, 0)
-            }
+    for &(dir, steps) in plan {
+        let direction = match dir {
+            'R' => (1, 0),
+            'L' => (-1, 0),
+            'U' => (0, -1),
+            'D' => (0, 1),
+            _ => return Err(Error::InvalidDirection(dir)),
         };
-        let steps = i32::from_str_radix(&steps_hex, 16).expect("valid hex number");
+        let steps = i64::from(steps);
 
         position = (
             position.0 + direction.0 * steps,
             position.1 + direction.1 * steps,
         );
-        points.push(Point::new(position.0, position.1));
-        println!("At position {:?}", position);
+        points.push(position);
+        trace!("at {:?}", position);
     }
 
-    let perimeter = polygon_perimeter(&points);
-    let half_perimeter = (perimeter / 2.0).floor() + 1.0;
-    println!("Perimeter: {}, half: {}", perimeter, half_perimeter);
+    let polygon = Polygon::new(points);
+    debug!(
+        "doubled area {}, trench {}",
+        polygon.doubled_area(),
+        polygon.boundary_points()
+    );
+
+    Ok(polygon.lattice_points() as i64)
+}
+
+pub struct Part2;
 
-    let area = Polygon::new(points).area();
-    println!("Area: {}", area);
+impl Solution for Part2 {
+    type Input<'a> = Vec<&'a str>;
+    type Answer = i64;
+    type Params = ();
+    type Error = Error;
 
-    area.round().abs() as i64 + half_perimeter.abs() as i64
+    fn parse(input: &str) -> Result<Vec<&str>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(lines: &Vec<&str>, _params: &()) -> Result<i64, Error> {
+        let mut plan = vec![];
+        for &color in lines {
+            let mut steps_hex = color.to_owned();
+            let direction_char = steps_hex
+                .pop()
+                .ok_or_else(|| Error::InvalidColor(color.to_owned()))?;
+            let direction = match direction_char {
+                '0' => 'R',
+                '2' => 'L',
+                '3' => 'U',
+                '1' => 'D',
+                _ => return Err(Error::InvalidDirection(direction_char)),
+            };
+            let steps = u32::from_str_radix(&steps_hex, 16)
+                .map_err(|_| Error::InvalidColor(color.to_owned()))?;
+            plan.push((direction, steps));
+        }
+        lagoon_volume(&plan)
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<i64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -83,7 +104,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 952408144115);
     }
 }
@@ -0,0 +1,12 @@
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no workflow named {0}")]
+    UnknownWorkflow(String),
+    #[error("invalid stat {0:?}")]
+    InvalidStat(char),
+}
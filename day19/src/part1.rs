@@ -1,48 +1,147 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt, hash::Hash};
 
+use crate::Error;
+use common::Solution;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, char, digit1, one_of},
-    combinator::{map_res, opt},
+    character::complete::{alpha1, char, newline, one_of},
+    combinator::opt,
     multi::separated_list1,
-    sequence::{delimited, pair, separated_pair, tuple},
-    IResult,
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
 };
+use parsing::{int_parser, parse_all, token, IResult};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part {
+    pub x: u32,
+    pub m: u32,
+    pub a: u32,
+    pub s: u32,
+}
+
+/// The workflows and parts, with the names of the workflows as `S`: `&str`
+/// when borrowed from the puzzle text, `String` when owned.
 #[derive(Debug, PartialEq, Eq)]
-struct Part {
-    x: u32,
-    m: u32,
-    a: u32,
-    s: u32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct System<S: Eq + Hash> {
+    pub workflows: HashMap<S, Vec<Rule<S>>>,
+    pub parts: Vec<Part>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Rule<'a> {
-    stat: char,
-    is_gt: bool,
-    value: u32,
-    target: &'a str,
-    is_fallback: bool,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule<S> {
+    pub stat: char,
+    pub is_gt: bool,
+    pub value: u32,
+    pub target: S,
+    pub is_fallback: bool,
+}
+
+impl<S: AsRef<str> + Eq + Hash> System<S> {
+    /// The system borrowing the names of its workflows, to solve an owned one.
+    pub fn as_deref(&self) -> System<&str> {
+        System {
+            workflows: self
+                .workflows
+                .iter()
+                .map(|(name, rules)| (name.as_ref(), rules.iter().map(Rule::as_deref).collect()))
+                .collect(),
+            parts: self.parts.clone(),
+        }
+    }
+}
+
+impl<S: AsRef<str>> Rule<S> {
+    pub fn as_deref(&self) -> Rule<&str> {
+        Rule {
+            stat: self.stat,
+            is_gt: self.is_gt,
+            value: self.value,
+            target: self.target.as_ref(),
+            is_fallback: self.is_fallback,
+        }
+    }
+}
+
+/// The workflows ordered by name, then the parts.
+impl<S: fmt::Display + Ord + Hash> fmt::Display for System<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&S> = self.workflows.keys().collect();
+        names.sort();
+        for name in names {
+            write!(f, "{}{{", name)?;
+            for (i, rule) in self.workflows[name].iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{}", rule)?;
+            }
+            writeln!(f, "}}")?;
+        }
+        for part in &self.parts {
+            write!(f, "\n{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Rule<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_fallback {
+            return write!(f, "{}", self.target);
+        }
+        let operator = if self.is_gt { '>' } else { '<' };
+        write!(f, "{}{}{}:{}", self.stat, operator, self.value, self.target)
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
+    }
 }
 
-fn workflow_parser(i: &str) -> IResult<&str, (&str, Vec<Rule>)> {
-    let (i, (name, rules)) = tuple((
+fn system_parser(i: &str) -> IResult<'_, System<&str>> {
+    let (i, (workflows, parts)) = separated_pair(
+        separated_list1(newline, workflow_parser),
+        token("\n\n"),
+        separated_list1(newline, part_parser),
+    )(i)?;
+    Ok((
+        i,
+        System {
+            workflows: workflows.into_iter().collect(),
+            parts,
+        },
+    ))
+}
+
+fn workflow_parser(i: &str) -> IResult<'_, (&str, Vec<Rule<&str>>)> {
+    pair(
         alpha1,
-        delimited(tag("{"), separated_list1(char(','), rule_parser), tag("}")),
-    ))(i)?;
-    Ok((i, (name, rules)))
+        delimited(
+            token("{"),
+            separated_list1(char(','), rule_parser),
+            token("}"),
+        ),
+    )(i)
 }
 
-fn rule_parser(i: &str) -> IResult<&str, Rule> {
+fn rule_parser(i: &str) -> IResult<'_, Rule<&str>> {
     let (i, (parts, target)) = pair(
-        opt(tuple((alpha1, one_of("<>"), int_parser, tag(":")))),
+        opt(tuple((
+            one_of("xmas"),
+            one_of("<>"),
+            int_parser,
+            token(":"),
+        ))),
         alpha1,
     )(i)?;
 
     let rule = if let Some((stat, operator, value, _)) = parts {
         Rule {
-            stat: stat.chars().nth(0).expect("found stat"),
+            stat,
             is_gt: operator == '>',
             value,
             target,
@@ -61,79 +160,77 @@ fn rule_parser(i: &str) -> IResult<&str, Rule> {
     Ok((i, rule))
 }
 
-fn part_parser(i: &str) -> IResult<&str, Part> {
-    let (i, stats) = delimited(
-        tag("{"),
-        separated_list1(char(','), separated_pair(alpha1, char('='), int_parser)),
-        tag("}"),
+fn part_parser(i: &str) -> IResult<'_, Part> {
+    let (i, (x, m, a, s)) = delimited(
+        token("{"),
+        tuple((
+            preceded(token("x="), int_parser),
+            preceded(token(",m="), int_parser),
+            preceded(token(",a="), int_parser),
+            preceded(token(",s="), int_parser),
+        )),
+        token("}"),
     )(i)?;
-
-    Ok((
-        i,
-        Part {
-            x: stats[0].1,
-            m: stats[1].1,
-            a: stats[2].1,
-            s: stats[3].1,
-        },
-    ))
+    Ok((i, Part { x, m, a, s }))
 }
 
-fn int_parser(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, FromStr::from_str)(i)
-}
+pub struct Part1;
 
-fn part1(input: &str) -> u32 {
-    let (workflows_input, parts_input) = input.split_once("\n\n").expect("valid input");
-    let workflows: HashMap<&str, Vec<Rule>> = workflows_input
-        .lines()
-        .map(|line| workflow_parser(line).expect("valid input").1)
-        .collect();
-
-    parts_input
-        .lines()
-        .map(|line| part_parser(line).expect("valid input").1)
-        .filter_map(|part| {
-            let mut current_workflow = "in";
-            loop {
-                if current_workflow == "A" {
-                    return Some(part.x + part.m + part.a + part.s);
-                } else if current_workflow == "R" {
-                    return None;
-                }
+impl Solution for Part1 {
+    type Input<'a> = System<&'a str>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<System<&str>, Error> {
+        Ok(parse_all(input, system_parser)?)
+    }
+
+    fn solve(system: &System<&str>, _params: &()) -> Result<u32, Error> {
+        let System { workflows, parts } = system;
 
-                let rules = workflows.get(current_workflow).expect("workflow found");
-                for rule in rules {
-                    if rule.is_fallback {
-                        current_workflow = rule.target;
-                        break;
+        parts
+            .iter()
+            .map(|part| {
+                let mut current_workflow = "in";
+                loop {
+                    if current_workflow == "A" {
+                        return Ok(part.x + part.m + part.a + part.s);
+                    } else if current_workflow == "R" {
+                        return Ok(0);
                     }
 
-                    let value = match rule.stat {
-                        'x' => part.x,
-                        'm' => part.m,
-                        'a' => part.a,
-                        's' => part.s,
-                        _ => {
-                            eprintln!("Invalid stat {}", rule.stat);
-                            0
+                    let rules = workflows
+                        .get(current_workflow)
+                        .ok_or_else(|| Error::UnknownWorkflow(current_workflow.to_string()))?;
+                    for rule in rules {
+                        if rule.is_fallback {
+                            current_workflow = rule.target;
+                            break;
                         }
-                    };
 
-                    if (value > rule.value && rule.is_gt) || (value < rule.value && !rule.is_gt) {
-                        current_workflow = rule.target;
-                        break;
+                        let value = match rule.stat {
+                            'x' => part.x,
+                            'm' => part.m,
+                            'a' => part.a,
+                            's' => part.s,
+                            stat => return Err(Error::InvalidStat(stat)),
+                        };
+
+                        if (value > rule.value && rule.is_gt) || (value < rule.value && !rule.is_gt)
+                        {
+                            current_workflow = rule.target;
+                            break;
+                        }
                     }
                 }
-            }
-        })
-        .sum()
+            })
+            .sum()
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -142,7 +239,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 19114);
     }
 
@@ -212,6 +309,36 @@ mod tests {
     fn parse_part() {
         let (i, part) = part_parser("{x=1679,m=44,a=2067,s=496}").unwrap();
         assert_eq!(i, "");
-        assert_eq!(part, Part { x: 1679, m: 44, a: 2067, s: 496 });
+        assert_eq!(
+            part,
+            Part {
+                x: 1679,
+                m: 44,
+                a: 2067,
+                s: 496
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let system = Part1::parse(include_str!("input1_test.txt")).unwrap();
+        let json = serde_json::to_string(&system).unwrap();
+        let owned: System<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned.as_deref(), system);
+        assert_eq!(Part1::solve(&owned.as_deref(), &()).unwrap(), 19114);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let system = Part1::parse(include_str!("input1_test.txt")).unwrap();
+        let text = system.to_string();
+        assert!(text.starts_with("crn{x>2662:A,R}\ngd{a>3333:R,R}\n"));
+        assert!(text.contains("}\n\n{x=787,m=2655,a=1222,s=2876}\n"));
+        assert!(text.ends_with("\n{x=2127,m=1623,a=2188,s=1013}"));
+        assert_eq!(Part1::parse(&text).unwrap(), system);
+        let system = Part1::parse(include_str!("input1.txt")).unwrap();
+        assert_eq!(Part1::parse(&system.to_string()).unwrap(), system);
     }
 }
@@ -1,62 +1,88 @@
 use std::{
-    cmp::{max, min},
     collections::{HashMap, VecDeque},
-    str::FromStr,
+    fmt,
 };
 
+use crate::Error;
+use common::Solution;
+use intervals::{HyperRect, Interval};
+use log::trace;
 use nom::{
-    character::complete::{alpha1, char, digit1, one_of},
-    combinator::{map_res, opt},
+    character::complete::{alpha1, char, one_of},
+    combinator::opt,
     multi::separated_list1,
     sequence::{delimited, pair, tuple},
-    IResult,
 };
+use parsing::{int_parser, parse_lines, token, IResult};
 
-#[derive(Debug, Clone)]
-struct PartRange {
-    min: [u64; 4],
-    max: [u64; 4],
+/// The parts with their x, m, a and s ratings in an interval each.
+type PartRange = HyperRect<u64, 4>;
+
+/// Every part, with ratings from 1 to 4000.
+fn all_parts() -> PartRange {
+    HyperRect::new([Interval::new(1, 4001); 4])
+}
+
+/// A rule of a workflow, with the name of its target as `S`: `&str` when
+/// borrowed from the puzzle text, `String` when owned.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule<S> {
+    pub stat: char,
+    pub is_gt: bool,
+    pub value: u64,
+    pub target: S,
+    pub is_fallback: bool,
 }
 
-impl Default for PartRange {
-    fn default() -> Self {
-        Self {
-            min: [1, 1, 1, 1],
-            max: [4000, 4000, 4000, 4000],
+impl<S: AsRef<str>> Rule<S> {
+    /// The rule borrowing the name of its target, to solve owned workflows.
+    pub fn as_deref(&self) -> Rule<&str> {
+        Rule {
+            stat: self.stat,
+            is_gt: self.is_gt,
+            value: self.value,
+            target: self.target.as_ref(),
+            is_fallback: self.is_fallback,
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Rule<'a> {
-    stat: char,
-    is_gt: bool,
-    value: u64,
-    target: &'a str,
-    is_fallback: bool,
+impl<S: fmt::Display> fmt::Display for Rule<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_fallback {
+            return write!(f, "{}", self.target);
+        }
+        let operator = if self.is_gt { '>' } else { '<' };
+        write!(f, "{}{}{}:{}", self.stat, operator, self.value, self.target)
+    }
 }
 
-fn workflow_parser(i: &str) -> IResult<&str, (&str, Vec<Rule>)> {
-    let (i, (name, rules)) = tuple((
+fn workflow_parser(i: &str) -> IResult<'_, (&str, Vec<Rule<&str>>)> {
+    pair(
         alpha1,
         delimited(
-            char('{'),
+            token("{"),
             separated_list1(char(','), rule_parser),
-            char('}'),
+            token("}"),
         ),
-    ))(i)?;
-    Ok((i, (name, rules)))
+    )(i)
 }
 
-fn rule_parser(i: &str) -> IResult<&str, Rule> {
+fn rule_parser(i: &str) -> IResult<'_, Rule<&str>> {
     let (i, (parts, target)) = pair(
-        opt(tuple((alpha1, one_of("<>"), int_parser, char(':')))),
+        opt(tuple((
+            one_of("xmas"),
+            one_of("<>"),
+            int_parser,
+            token(":"),
+        ))),
         alpha1,
     )(i)?;
 
     let rule = if let Some((stat, operator, value, _)) = parts {
         Rule {
-            stat: stat.chars().nth(0).expect("found stat"),
+            stat,
             is_gt: operator == '>',
             value,
             target,
@@ -75,72 +101,74 @@ fn rule_parser(i: &str) -> IResult<&str, Rule> {
     Ok((i, rule))
 }
 
-fn int_parser(i: &str) -> IResult<&str, u64> {
-    map_res(digit1, FromStr::from_str)(i)
-}
+pub struct Part2;
 
-fn part1(input: &str) -> u64 {
-    let (workflows_input, _) = input.split_once("\n\n").expect("valid input");
-    let workflows: HashMap<&str, Vec<Rule>> = workflows_input
-        .lines()
-        .map(|line| workflow_parser(line).expect("valid input").1)
-        .collect();
-
-    let mut queue = VecDeque::new();
-    queue.push_back(("in", PartRange::default()));
-    let mut accepted_parts = vec![];
-
-    while !queue.is_empty() {
-        let (current_workflow, part) = queue.pop_front().unwrap();
-        println!("Workflow: {}, {:?}", current_workflow, part);
-
-        if current_workflow == "A" {
-            accepted_parts.push(part);
-            continue;
-        } else if current_workflow == "R" {
-            continue;
-        }
+impl Solution for Part2 {
+    type Input<'a> = HashMap<&'a str, Vec<Rule<&'a str>>>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
 
-        let rules = workflows.get(current_workflow).expect("workflow found");
-        let mut current_part = part.clone();
-        let mut next_part = part;
-        for rule in rules {
-            if rule.is_fallback {
-                queue.push_back((rule.target, current_part));
-                break;
-            }
+    fn parse(input: &str) -> Result<HashMap<&str, Vec<Rule<&str>>>, Error> {
+        // the parts below the workflows don't matter for the accepted ranges
+        let workflows_input = input
+            .split_once("\n\n")
+            .map_or(input, |(workflows, _)| workflows);
+        Ok(parse_lines(workflows_input, workflow_parser)?
+            .into_iter()
+            .collect())
+    }
 
-            let stat = "xmas".find(rule.stat).expect("valid stat");
+    fn solve(workflows: &HashMap<&str, Vec<Rule<&str>>>, _params: &()) -> Result<u64, Error> {
+        let mut queue = VecDeque::new();
+        queue.push_back(("in", all_parts()));
+        let mut accepted_parts = vec![];
 
-            // make criteria for min and max values more precise based on current rule
-            // the next rule is dependant on the current rule not applying, so make its min/ max more precise as well
-            if rule.is_gt {
-                current_part.min[stat] = max(current_part.min[stat], rule.value + 1);
-                next_part.max[stat] = min(next_part.max[stat], rule.value);
-            } else {
-                current_part.max[stat] = min(current_part.max[stat], rule.value - 1);
-                next_part.min[stat] = max(next_part.min[stat], rule.value);
+        while !queue.is_empty() {
+            let (current_workflow, part) = queue.pop_front().unwrap();
+            trace!("workflow {} with {:?}", current_workflow, part);
+
+            if current_workflow == "A" {
+                accepted_parts.push(part);
+                continue;
+            } else if current_workflow == "R" {
+                continue;
             }
 
-            queue.push_back((rule.target, current_part));
-            current_part = next_part.clone();
+            let rules = workflows
+                .get(current_workflow)
+                .ok_or_else(|| Error::UnknownWorkflow(current_workflow.to_string()))?;
+            // the parts a rule doesn't apply to go on to the next rule
+            let mut rest = part;
+            for rule in rules {
+                if rule.is_fallback {
+                    queue.push_back((rule.target, rest));
+                    break;
+                }
+
+                let stat = "xmas"
+                    .find(rule.stat)
+                    .ok_or(Error::InvalidStat(rule.stat))?;
+
+                let (matching, not_matching) = if rule.is_gt {
+                    let (low, high) = rest.split(stat, rule.value + 1);
+                    (high, low)
+                } else {
+                    rest.split(stat, rule.value)
+                };
+                if !matching.is_empty() {
+                    queue.push_back((rule.target, matching));
+                }
+                rest = not_matching;
+            }
         }
-    }
 
-    accepted_parts
-        .iter()
-        .map(|part| {
-            (0..4)
-                .map(|i| part.max[i] - part.min[i] + 1) // inclusive range
-                .product::<u64>()
-        })
-        .sum()
+        Ok(accepted_parts.iter().map(PartRange::volume).sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -149,7 +177,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 167409079868000);
     }
 
@@ -214,4 +242,22 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn display_round_trip() {
+        let (i, rules) = workflow_parser("px{a<2006:qkq,m>2090:A,rfg}").unwrap();
+        assert_eq!(i, "");
+        let rules: Vec<String> = rules.1.iter().map(ToString::to_string).collect();
+        assert_eq!(rules, ["a<2006:qkq", "m>2090:A", "rfg"]);
+
+        let workflows = Part2::parse(include_str!("input1.txt")).unwrap();
+        let text: String = workflows
+            .iter()
+            .map(|(name, rules)| {
+                let rules: Vec<String> = rules.iter().map(ToString::to_string).collect();
+                format!("{}{{{}}}\n", name, rules.join(","))
+            })
+            .collect();
+        assert_eq!(Part2::parse(&text).unwrap(), workflows);
+    }
 }
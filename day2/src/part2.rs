@@ -1,96 +1,140 @@
 extern crate nom;
+use std::{cmp, fmt};
+
+use crate::Error;
+use common::Solution;
+use log::trace;
 use nom::{
+    branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, char, digit1},
+    character::complete::char,
+    error::context,
     multi::{separated_list0, separated_list1},
-    sequence::separated_pair,
-    IResult,
+    sequence::{preceded, separated_pair},
 };
-use std::cmp;
+use parsing::{int_parser, parse_lines, token, IResult};
 
 #[derive(Default, Debug, PartialEq, Eq)]
-struct Game {
-    id: u32,
-    bags: Vec<Bag>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
+    pub id: u32,
+    pub bags: Vec<Bag>,
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
-struct Bag {
-    red: u32,
-    green: u32,
-    blue: u32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bag {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, bag) in self.bags.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", bag)?;
+        }
+        Ok(())
+    }
+}
+
+/// The colors drawn, in the order red, green, blue and leaving out the ones
+/// not drawn.
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let drawn = [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ];
+        for (i, (num, color)) in drawn.iter().filter(|(num, _)| *num > 0).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", num, color)?;
+        }
+        Ok(())
+    }
 }
 
-fn line_parser(i: &str) -> IResult<&str, Game> {
-    dbg!(i);
-    let (i, parsed) = separated_pair(
-        separated_pair(tag("Game"), char(' '), digit1),
-        tag(": "),
+fn line_parser(i: &str) -> IResult<'_, Game> {
+    trace!("parsing {:?}", i);
+    let (i, (id, bags)) = separated_pair(
+        preceded(token("Game "), int_parser),
+        token(": "),
         game_parser,
     )(i)?;
-    Ok((
-        i,
-        Game {
-            id: parsed.0 .1.parse().expect("valid game ID"),
-            bags: parsed.1,
-        },
-    ))
+    Ok((i, Game { id, bags }))
 }
 
-fn game_parser(i: &str) -> IResult<&str, Vec<Bag>> {
-    separated_list1(tag("; "), bag_parser)(i)
+fn game_parser(i: &str) -> IResult<'_, Vec<Bag>> {
+    separated_list1(token("; "), bag_parser)(i)
 }
 
-fn bag_parser(i: &str) -> IResult<&str, Bag> {
-    let (i, color_list) = separated_list0(tag(", "), separated_pair(digit1, char(' '), alpha1))(i)?;
+fn bag_parser(i: &str) -> IResult<'_, Bag> {
+    let (i, color_list) = separated_list0(
+        token(", "),
+        separated_pair(int_parser, char(' '), color_parser),
+    )(i)?;
     let mut bag = Bag {
         red: 0,
         green: 0,
         blue: 0,
     };
-    for color in color_list {
-        let num: u32 = color.0.parse().expect("valid cube amount");
-        match color.1 {
+    for (num, color) in color_list {
+        match color {
             "red" => bag.red = num,
             "green" => bag.green = num,
             "blue" => bag.blue = num,
-            _ => eprintln!("Invalid line!"),
+            _ => unreachable!("color_parser only accepts red, green and blue"),
         }
     }
     Ok((i, bag))
 }
 
-fn part2(input: &str) -> u32 {
-    input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(line_parser)
-        .map(|result| {
-            result
-                .as_ref()
-                .map(|(_, game)| {
-                    game.bags.iter().fold(
-                        Bag {
-                            red: 0,
-                            green: 0,
-                            blue: 0,
-                        },
-                        |max_bag, bag| Bag {
-                            red: cmp::max(max_bag.red, bag.red),
-                            green: cmp::max(max_bag.green, bag.green),
-                            blue: cmp::max(max_bag.blue, bag.blue),
-                        },
-                    )
-                })
-                .expect("valid line")
-        })
-        .fold(0, |sum, bag| sum + bag.red * bag.green * bag.blue)
+fn color_parser(i: &str) -> IResult<'_, &str> {
+    context("color", alt((tag("red"), tag("green"), tag("blue"))))(i)
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part2(input);
-    dbg!(output);
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Vec<Game>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Game>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(games: &Vec<Game>, _params: &()) -> Result<u32, Error> {
+        Ok(games
+            .iter()
+            .map(|game| {
+                game.bags.iter().fold(
+                    Bag {
+                        red: 0,
+                        green: 0,
+                        blue: 0,
+                    },
+                    |max_bag, bag| Bag {
+                        red: cmp::max(max_bag.red, bag.red),
+                        green: cmp::max(max_bag.green, bag.green),
+                        blue: cmp::max(max_bag.blue, bag.blue),
+                    },
+                )
+            })
+            .fold(0, |sum, bag| sum + bag.red * bag.green * bag.blue))
+    }
+}
+
+pub fn part2(input: &str) -> Result<u32, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -157,7 +201,21 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part2(include_str!("input2_test.txt"));
+        let result = part2(include_str!("input2_test.txt")).expect("run without errors");
         assert_eq!(result, 2286);
     }
+
+    #[test]
+    fn display_round_trip() {
+        let games = Part2::parse(include_str!("input1_test.txt")).unwrap();
+        assert_eq!(
+            games[0].to_string(),
+            "Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green"
+        );
+        for input in [include_str!("input1_test.txt"), include_str!("input1.txt")] {
+            let games = Part2::parse(input).unwrap();
+            let text: String = games.iter().map(|game| format!("{}\n", game)).collect();
+            assert_eq!(Part2::parse(&text).unwrap(), games);
+        }
+    }
 }
@@ -0,0 +1,10 @@
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("the groups only line up after more presses than fit in 64 bits")]
+    Overflow(#[from] common::math::Overflow),
+}
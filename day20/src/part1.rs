@@ -1,33 +1,91 @@
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt,
+};
 
+use crate::Error;
+use animation::{Animate, Frame, Recorder};
+use common::Solution;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, one_of},
+    branch::alt,
+    character::complete::{alpha1, char},
+    combinator::value,
+    error::context,
     multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
+use parsing::{parse_lines, token, IResult};
 
-#[derive(Debug, PartialEq, Eq)]
-enum ModuleType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModuleType {
     Broadcaster,
     FlipFlop,
     Nand,
 }
 
+/// A module, with `S` as `&str` for names borrowed from the input or `String`
+/// for owned ones.
 #[derive(Debug, PartialEq, Eq)]
-struct Module<'a> {
-    module_type: ModuleType,
-    name: &'a str,
-    connections: Vec<&'a str>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module<S> {
+    pub module_type: ModuleType,
+    pub name: S,
+    pub connections: Vec<S>,
+}
+
+impl<S: AsRef<str>> Module<S> {
+    /// The module borrowing its names, to solve owned modules.
+    pub fn as_deref(&self) -> Module<&str> {
+        Module {
+            module_type: self.module_type,
+            name: self.name.as_ref(),
+            connections: self.connections.iter().map(AsRef::as_ref).collect(),
+        }
+    }
+}
+
+/// The prefix of the module type, where the broadcaster's is the `b` the
+/// parser keeps of its name.
+impl fmt::Display for ModuleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self {
+            ModuleType::Broadcaster => 'b',
+            ModuleType::FlipFlop => '%',
+            ModuleType::Nand => '&',
+        };
+        write!(f, "{}", prefix)
+    }
 }
 
-fn line_parser(i: &str) -> IResult<&str, (&str, Module)> {
-    let (i, (type_char, name, _, connections)) = tuple((
-        one_of("b%&"),
+impl<S: fmt::Display> fmt::Display for Module<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{} ->", self.module_type, self.name)?;
+        for (i, connection) in self.connections.iter().enumerate() {
+            let separator = if i > 0 { "," } else { "" };
+            write!(f, "{} {}", separator, connection)?;
+        }
+        Ok(())
+    }
+}
+
+fn module_type_parser(i: &str) -> IResult<'_, ModuleType> {
+    context(
+        "module type",
+        alt((
+            value(ModuleType::Broadcaster, char('b')),
+            value(ModuleType::FlipFlop, char('%')),
+            value(ModuleType::Nand, char('&')),
+        )),
+    )(i)
+}
+
+fn line_parser(i: &str) -> IResult<'_, (&str, Module<&str>)> {
+    let (i, (module_type, name, _, connections)) = tuple((
+        module_type_parser,
         alpha1,
-        tag(" -> "),
-        separated_list1(tag(", "), alpha1),
+        token(" -> "),
+        separated_list1(token(", "), alpha1),
     ))(i)?;
     Ok((
         i,
@@ -36,97 +94,199 @@ fn line_parser(i: &str) -> IResult<&str, (&str, Module)> {
             Module {
                 name,
                 connections,
-                module_type: match type_char {
-                    'b' => ModuleType::Broadcaster,
-                    '%' => ModuleType::FlipFlop,
-                    '&' => ModuleType::Nand,
-                    _ => panic!("Invalid module type {}", type_char),
-                },
+                module_type,
             },
         ),
     ))
 }
 
-fn part1(input: &str) -> u64 {
-    let modules: HashMap<&str, Module> = input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect();
-
-    let mut flip_flop_state: HashMap<&str, bool> = HashMap::new();
-    let mut nand_state: HashMap<&str, HashMap<&str, bool>> = HashMap::new();
-
-    // init nand states (find all input connections)
-    let nand_names = modules
-        .iter()
-        .filter_map(|(name, m)| (m.module_type == ModuleType::Nand).then_some(name))
-        .collect::<Vec<_>>();
-
-    for module in modules.values() {
-        for nand_name in nand_names.iter() {
-            if module.connections.contains(nand_name) {
-                nand_state
-                    .entry(nand_name)
-                    .or_insert(HashMap::new())
-                    .insert(module.name, false);
+/// A pulse on its way: whether it is high, where it goes and where it comes from.
+type Pulse<'a> = (bool, &'a str, &'a str);
+
+/// Flip-flop and conjunction memory of the whole network.
+#[derive(Debug, Default)]
+struct State<'a> {
+    flip_flops: HashMap<&'a str, bool>,
+    nands: HashMap<&'a str, HashMap<&'a str, bool>>,
+}
+
+impl<'a> State<'a> {
+    fn new(modules: &HashMap<&'a str, Module<&'a str>>) -> Self {
+        let mut state = State::default();
+
+        // init nand states (find all input connections)
+        let nand_names = modules
+            .iter()
+            .filter_map(|(name, m)| (m.module_type == ModuleType::Nand).then_some(name))
+            .collect::<Vec<_>>();
+
+        for module in modules.values() {
+            for nand_name in nand_names.iter() {
+                if module.connections.contains(nand_name) {
+                    state
+                        .nands
+                        .entry(nand_name)
+                        .or_default()
+                        .insert(module.name, false);
+                }
             }
         }
+        state
     }
+}
 
+/// Push the button once and count the low and high pulses sent until the
+/// network settles. `on_pulse` sees every pulse with the ones still queued
+/// behind it.
+fn push_button<'a, F>(
+    modules: &HashMap<&'a str, Module<&'a str>>,
+    state: &mut State<'a>,
+    on_pulse: &mut F,
+) -> (u64, u64)
+where
+    F: FnMut(&Pulse<'a>, &VecDeque<Pulse<'a>>, &State<'a>),
+{
     let mut low_pulses = 0;
     let mut high_pulses = 0;
 
-    for _i in 0..1000 {
-        let mut queue = VecDeque::new();
-        queue.push_back((false, "roadcaster", "")); // button press
+    let mut queue = VecDeque::new();
+    queue.push_back((false, "roadcaster", "")); // button press
 
-        while !queue.is_empty() {
-            let (is_high, name, prev_name) = queue.pop_front().unwrap();
-            if is_high {
-                high_pulses += 1;
-            } else {
-                low_pulses += 1;
-            }
+    while let Some(pulse) = queue.pop_front() {
+        on_pulse(&pulse, &queue, state);
+        let (is_high, name, prev_name) = pulse;
+        if is_high {
+            high_pulses += 1;
+        } else {
+            low_pulses += 1;
+        }
 
-            let module = modules.get(name);
-            if module.is_none() {
-                continue;
-            }
-            let module = module.unwrap();
-            let mut pulse_type = is_high;
-
-            match module.module_type {
-                ModuleType::Broadcaster => {}
-                ModuleType::FlipFlop => {
-                    if !is_high {
-                        let prev_state = *flip_flop_state.entry(name).or_insert(false);
-                        flip_flop_state.insert(name, !prev_state);
-                        pulse_type = !prev_state;
-                    } else {
-                        continue; // don't send output pulse for high input
-                    }
-                }
-                ModuleType::Nand => {
-                    let state = nand_state.get_mut(name).unwrap();
-                    state.insert(prev_name, is_high);
-                    let result = state.values().fold(true, |acc, s| acc & *s); // AND all inputs
-                    pulse_type = !result; // NOT
+        let Some(module) = modules.get(name) else {
+            continue;
+        };
+        let mut pulse_type = is_high;
+
+        match module.module_type {
+            ModuleType::Broadcaster => {}
+            ModuleType::FlipFlop => {
+                if !is_high {
+                    let prev_state = *state.flip_flops.entry(module.name).or_insert(false);
+                    state.flip_flops.insert(module.name, !prev_state);
+                    pulse_type = !prev_state;
+                } else {
+                    continue; // don't send output pulse for high input
                 }
             }
-
-            for connection_name in module.connections.iter() {
-                queue.push_back((pulse_type, connection_name, name));
+            ModuleType::Nand => {
+                let inputs = state.nands.get_mut(name).unwrap();
+                inputs.insert(prev_name, is_high);
+                let result = inputs.values().fold(true, |acc, s| acc & *s); // AND all inputs
+                pulse_type = !result; // NOT
             }
         }
+
+        for connection_name in module.connections.iter() {
+            queue.push_back((pulse_type, connection_name, module.name));
+        }
+    }
+
+    (low_pulses, high_pulses)
+}
+
+/// The pulse being handled, the queue behind it and the memory of every module.
+fn describe(pulse: &Pulse, queue: &VecDeque<Pulse>, state: &State) -> String {
+    // the parser keeps the `b` of the broadcaster as its module type
+    let name = |name: &'_ str| match name {
+        "" => "button".to_owned(),
+        "roadcaster" => "broadcaster".to_owned(),
+        name => name.to_owned(),
+    };
+    let arrow = |&(is_high, to, from): &Pulse| {
+        format!(
+            "{} -{}-> {}",
+            name(from),
+            if is_high { "high" } else { "low" },
+            name(to)
+        )
+    };
+
+    let mut text = format!("{}\n\nqueue:\n", arrow(pulse));
+    for queued in queue {
+        text += &format!("  {}\n", arrow(queued));
+    }
+
+    let flip_flops: BTreeMap<_, _> = state.flip_flops.iter().collect();
+    text += "\nflip-flops:\n ";
+    for (name, &on) in flip_flops {
+        text += &format!(" {}={}", name, if on { "on" } else { "off" });
+    }
+
+    let nands: BTreeMap<_, _> = state.nands.iter().collect();
+    text += "\n\nconjunctions:\n";
+    for (name, inputs) in nands {
+        let inputs: BTreeMap<_, _> = inputs.iter().collect();
+        let inputs: Vec<String> = inputs
+            .into_iter()
+            .map(|(input, &high)| format!("{}={}", input, if high { "high" } else { "low" }))
+            .collect();
+        text += &format!("  {}: {}\n", name, inputs.join(" "));
+    }
+    text
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = HashMap<&'a str, Module<&'a str>>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<HashMap<&str, Module<&str>>, Error> {
+        Ok(parse_lines(input, line_parser)?.into_iter().collect())
+    }
+
+    fn solve(modules: &HashMap<&str, Module<&str>>, _params: &()) -> Result<u64, Error> {
+        let mut state = State::new(modules);
+
+        let mut low_pulses = 0;
+        let mut high_pulses = 0;
+
+        for _i in 0..1000 {
+            let (low, high) = push_button(modules, &mut state, &mut |_, _, _| {});
+            low_pulses += low;
+            high_pulses += high;
+        }
+
+        Ok(low_pulses * high_pulses)
     }
+}
 
-    low_pulses * high_pulses
+/// Steps through the pulses of all button presses one at a time.
+impl Animate for Part1 {
+    fn animate(
+        modules: &HashMap<&str, Module<&str>>,
+        _params: &(),
+        recorder: &mut Recorder,
+    ) -> Result<(), Error> {
+        let mut state = State::new(modules);
+
+        for press in 1..=1000 {
+            push_button(modules, &mut state, &mut |pulse, queue, state| {
+                recorder.record(|| {
+                    Frame::new(
+                        format!("button press {}", press),
+                        describe(pulse, queue, state),
+                    )
+                })
+            });
+        }
+        Ok(())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u64, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -135,12 +295,27 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 32000000);
-        let result = part1(include_str!("input2_test.txt"));
+        let result = part1(include_str!("input2_test.txt")).expect("run without errors");
         assert_eq!(result, 11687500);
     }
 
+    #[test]
+    fn record_pulses() {
+        let modules = Part1::parse(include_str!("input1_test.txt")).expect("valid modules");
+        let mut recorder = Recorder::new(20_000);
+        Part1::animate(&modules, &(), &mut recorder).expect("run without errors");
+        let frames = recorder.into_frames();
+
+        // every press sends the same 8 low and 4 high pulses
+        assert_eq!(frames.len(), 12 * 1000);
+        assert_eq!(frames[0].caption, "button press 1");
+        assert!(frames[0].text.starts_with("button -low-> broadcaster\n"));
+        assert!(frames[1].text.contains("queue:\n  broadcaster -low-> b\n"));
+        assert!(frames[11].text.contains("a=off b=off c=off"));
+    }
+
     #[test]
     fn parse_module() {
         let (i, (name, module)) = line_parser("%sf -> pz, gj").unwrap();
@@ -155,4 +330,32 @@ mod tests {
             }
         )
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let modules = Part1::parse(include_str!("input1_test.txt")).unwrap();
+        let json = serde_json::to_string(&modules).unwrap();
+        let owned: HashMap<String, Module<String>> = serde_json::from_str(&json).unwrap();
+        let borrowed: HashMap<&str, Module<&str>> = owned
+            .iter()
+            .map(|(name, module)| (name.as_str(), module.as_deref()))
+            .collect();
+        assert_eq!(borrowed, modules);
+        assert_eq!(Part1::solve(&borrowed, &()).unwrap(), 32000000);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let (_, (_, module)) = line_parser("broadcaster -> a, b, c").unwrap();
+        assert_eq!(module.to_string(), "broadcaster -> a, b, c");
+        for input in [include_str!("input2_test.txt"), include_str!("input1.txt")] {
+            let modules = Part1::parse(input).unwrap();
+            let text: String = modules
+                .values()
+                .map(|module| format!("{}\n", module))
+                .collect();
+            assert_eq!(Part1::parse(&text).unwrap(), modules);
+        }
+    }
 }
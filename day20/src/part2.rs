@@ -1,33 +1,91 @@
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt,
+};
 
+use crate::Error;
+use common::{cycle::History, diagnostics, math, Solution};
+use log::{debug, info, trace};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, one_of},
+    branch::alt,
+    character::complete::{alpha1, char},
+    combinator::value,
+    error::context,
     multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
+use parsing::{parse_lines, token, IResult};
 
-#[derive(Debug, PartialEq, Eq)]
-enum ModuleType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModuleType {
     Broadcaster,
     FlipFlop,
     Nand,
 }
 
+/// A module, with `S` as `&str` for names borrowed from the input or `String`
+/// for owned ones.
 #[derive(Debug, PartialEq, Eq)]
-struct Module<'a> {
-    module_type: ModuleType,
-    name: &'a str,
-    connections: Vec<&'a str>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module<S> {
+    pub module_type: ModuleType,
+    pub name: S,
+    pub connections: Vec<S>,
+}
+
+impl<S: AsRef<str>> Module<S> {
+    /// The module borrowing its names, to solve owned modules.
+    pub fn as_deref(&self) -> Module<&str> {
+        Module {
+            module_type: self.module_type,
+            name: self.name.as_ref(),
+            connections: self.connections.iter().map(AsRef::as_ref).collect(),
+        }
+    }
+}
+
+/// The prefix of the module type, where the broadcaster's is the `b` the
+/// parser keeps of its name.
+impl fmt::Display for ModuleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self {
+            ModuleType::Broadcaster => 'b',
+            ModuleType::FlipFlop => '%',
+            ModuleType::Nand => '&',
+        };
+        write!(f, "{}", prefix)
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Module<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{} ->", self.module_type, self.name)?;
+        for (i, connection) in self.connections.iter().enumerate() {
+            let separator = if i > 0 { "," } else { "" };
+            write!(f, "{} {}", separator, connection)?;
+        }
+        Ok(())
+    }
+}
+
+fn module_type_parser(i: &str) -> IResult<'_, ModuleType> {
+    context(
+        "module type",
+        alt((
+            value(ModuleType::Broadcaster, char('b')),
+            value(ModuleType::FlipFlop, char('%')),
+            value(ModuleType::Nand, char('&')),
+        )),
+    )(i)
 }
 
-fn line_parser(i: &str) -> IResult<&str, (&str, Module)> {
-    let (i, (type_char, name, _, connections)) = tuple((
-        one_of("b%&"),
+fn line_parser(i: &str) -> IResult<'_, (&str, Module<&str>)> {
+    let (i, (module_type, name, _, connections)) = tuple((
+        module_type_parser,
         alpha1,
-        tag(" -> "),
-        separated_list1(tag(", "), alpha1),
+        token(" -> "),
+        separated_list1(token(", "), alpha1),
     ))(i)?;
     Ok((
         i,
@@ -36,173 +94,178 @@ fn line_parser(i: &str) -> IResult<&str, (&str, Module)> {
             Module {
                 name,
                 connections,
-                module_type: match type_char {
-                    'b' => ModuleType::Broadcaster,
-                    '%' => ModuleType::FlipFlop,
-                    '&' => ModuleType::Nand,
-                    _ => panic!("Invalid module type {}", type_char),
-                },
+                module_type,
             },
         ),
     ))
 }
 
-fn part1(input: &str) -> u64 {
-    let modules: HashMap<&str, Module> = input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect();
+pub struct Part2;
 
-    let mut flip_flop_state: BTreeMap<&str, bool> = BTreeMap::new();
-    let mut nand_state: HashMap<&str, HashMap<&str, bool>> = HashMap::new();
+impl Solution for Part2 {
+    type Input<'a> = HashMap<&'a str, Module<&'a str>>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
 
-    // init flip flop states
-    for module in modules.values() {
-        if module.module_type == ModuleType::FlipFlop {
-            flip_flop_state.insert(module.name, false);
-        }
+    fn parse(input: &str) -> Result<HashMap<&str, Module<&str>>, Error> {
+        Ok(parse_lines(input, line_parser)?.into_iter().collect())
     }
 
-    // init nand states (find all input connections)
-    let nand_names = modules
-        .iter()
-        .filter_map(|(name, m)| (m.module_type == ModuleType::Nand).then_some(name))
-        .collect::<Vec<_>>();
-
-    for module in modules.values() {
-        for nand_name in nand_names.iter() {
-            if module.connections.contains(nand_name) {
-                nand_state
-                    .entry(nand_name)
-                    .or_insert(HashMap::new())
-                    .insert(module.name, false);
-            }
-        }
-    }
+    fn solve(modules: &HashMap<&str, Module<&str>>, _params: &()) -> Result<u64, Error> {
+        let mut flip_flop_state: BTreeMap<&str, bool> = BTreeMap::new();
+        let mut nand_state: HashMap<&str, HashMap<&str, bool>> = HashMap::new();
 
-    // all flip flops reachable from the network root module
-    let mut groups: BTreeMap<&str, HashSet<&str>> = BTreeMap::new();
-    let mut queue = VecDeque::new();
-    let mut visited: Vec<&str> = vec![];
-    queue.push_back(("roadcaster", None));
-
-    while !queue.is_empty() {
-        let (name, group) = queue.pop_front().unwrap();
-        visited.push(name);
-        if !modules.contains_key(name) {
-            continue;
-        }
-        let module = modules.get(name).unwrap();
-        if module.module_type == ModuleType::FlipFlop {
-            groups
-                .entry(group.unwrap()) // safe because there are no flip flops at top level
-                .or_insert(HashSet::new())
-                .insert(name);
+        // init flip flop states
+        for module in modules.values() {
+            if module.module_type == ModuleType::FlipFlop {
+                flip_flop_state.insert(module.name, false);
+            }
         }
-        for connection_name in module.connections.iter() {
-            let group = group.unwrap_or(connection_name);
 
-            if !visited.contains(connection_name) {
-                queue.push_back((connection_name, Some(group)));
+        // init nand states (find all input connections)
+        let nand_names = modules
+            .iter()
+            .filter_map(|(name, m)| (m.module_type == ModuleType::Nand).then_some(name))
+            .collect::<Vec<_>>();
+
+        for module in modules.values() {
+            for nand_name in nand_names.iter() {
+                if module.connections.contains(nand_name) {
+                    nand_state
+                        .entry(nand_name)
+                        .or_default()
+                        .insert(module.name, false);
+                }
             }
         }
-    }
-
-    println!("Groups {:?}", groups);
 
-    // network root module name to history of hashes
-    let mut group_histories: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
-    // network root module name to length of cycle
-    let mut group_cycles: BTreeMap<&str, u64> = BTreeMap::new();
-    let mut i = 0;
-
-    for &group_name in groups.keys() {
-        group_histories.insert(group_name, vec![]);
-    }
-
-    while group_cycles.len() < groups.len() {
+        // all flip flops reachable from the network root module
+        let mut groups: BTreeMap<&str, HashSet<&str>> = BTreeMap::new();
         let mut queue = VecDeque::new();
-        queue.push_back((false, "roadcaster", "")); // button press
-
-        /*println!(
-            "Iteration {}, FlipFlops {}, {}",
-            i,
-            flip_flop_state
-                .values()
-                .map(|s| if *s { '1' } else { '0' })
-                .collect::<String>(),
-            flip_flop_state.len()
-        );*/
+        let mut visited: Vec<&str> = vec![];
+        queue.push_back(("roadcaster", None));
 
-        for (&group_name, group_modules) in groups.iter() {
-            if group_cycles.contains_key(group_name) {
+        while !queue.is_empty() {
+            let (name, group) = queue.pop_front().unwrap();
+            visited.push(name);
+            if !modules.contains_key(name) {
                 continue;
             }
+            let module = modules.get(name).unwrap();
+            if module.module_type == ModuleType::FlipFlop {
+                groups
+                    .entry(group.unwrap()) // safe because there are no flip flops at top level
+                    .or_default()
+                    .insert(name);
+            }
+            for connection_name in module.connections.iter() {
+                let group = group.unwrap_or(connection_name);
 
-            // concat binary number from flip flop states
-            let history_entry = group_modules
-                .iter()
-                .map(|&module_name| *flip_flop_state.get(module_name).unwrap_or(&false))
-                .fold(0, |acc, s| (acc << 1) | s as u64);
-
-            let history = group_histories.get_mut(group_name).unwrap();
-            if history.contains(&history_entry) {
-                group_cycles.insert(group_name, i);
+                if !visited.contains(connection_name) {
+                    queue.push_back((connection_name, Some(group)));
+                }
             }
+        }
+
+        debug!("groups {:?}", groups);
 
-            history.push(history_entry);
+        // network root module name to history of flip flop states
+        let mut group_histories: BTreeMap<&str, History<u64>> = BTreeMap::new();
+        // network root module name to length of cycle
+        let mut group_cycles: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut i = 0;
+
+        for &group_name in groups.keys() {
+            group_histories.insert(group_name, History::new());
         }
 
-        while !queue.is_empty() {
-            let (is_high, name, prev_name) = queue.pop_front().unwrap();
+        while group_cycles.len() < groups.len() {
+            let mut queue = VecDeque::new();
+            queue.push_back((false, "roadcaster", "")); // button press
+
+            trace!(
+                "iteration {}, flip-flops {}, {}",
+                i,
+                flip_flop_state
+                    .values()
+                    .map(|s| if *s { '1' } else { '0' })
+                    .collect::<String>(),
+                flip_flop_state.len()
+            );
+
+            for (&group_name, group_modules) in groups.iter() {
+                if group_cycles.contains_key(group_name) {
+                    continue;
+                }
 
-            if name == "rx" && !is_high {
-                return i;
-            }
+                // concat binary number from flip flop states
+                let history_entry = group_modules
+                    .iter()
+                    .map(|&module_name| *flip_flop_state.get(module_name).unwrap_or(&false))
+                    .fold(0, |acc, s| (acc << 1) | s as u64);
 
-            let module = modules.get(name);
-            if module.is_none() {
-                continue;
+                let history = group_histories.get_mut(group_name).unwrap();
+                if let Some(cycle) = history.push(history_entry) {
+                    debug!("group {} repeats from press {}", group_name, cycle.start);
+                    group_cycles.insert(group_name, cycle.length);
+                }
             }
-            let module = module.unwrap();
-            let mut pulse_type = is_high;
-
-            match module.module_type {
-                ModuleType::Broadcaster => {}
-                ModuleType::FlipFlop => {
-                    if !is_high {
-                        let prev_state = *flip_flop_state.entry(name).or_insert(false);
-                        flip_flop_state.insert(name, !prev_state);
-                        pulse_type = !prev_state;
-                    } else {
-                        continue; // don't send output pulse for high input
+
+            while !queue.is_empty() {
+                let (is_high, name, prev_name) = queue.pop_front().unwrap();
+
+                if name == "rx" && !is_high {
+                    return Ok(i);
+                }
+
+                let module = modules.get(name);
+                if module.is_none() {
+                    continue;
+                }
+                let module = module.unwrap();
+                let mut pulse_type = is_high;
+
+                match module.module_type {
+                    ModuleType::Broadcaster => {}
+                    ModuleType::FlipFlop => {
+                        if !is_high {
+                            let prev_state = *flip_flop_state.entry(name).or_insert(false);
+                            flip_flop_state.insert(name, !prev_state);
+                            pulse_type = !prev_state;
+                        } else {
+                            continue; // don't send output pulse for high input
+                        }
+                    }
+                    ModuleType::Nand => {
+                        let state = nand_state.get_mut(name).unwrap();
+                        state.insert(prev_name, is_high);
+                        let result = state.values().all(|s| *s); // AND all inputs
+                        pulse_type = !result; // NOT
                     }
                 }
-                ModuleType::Nand => {
-                    let state = nand_state.get_mut(name).unwrap();
-                    state.insert(prev_name, is_high);
-                    let result = state.values().fold(true, |acc, s| acc && *s); // AND all inputs
-                    pulse_type = !result; // NOT
+
+                for connection_name in module.connections.iter() {
+                    queue.push_back((pulse_type, connection_name, name));
                 }
             }
 
-            for connection_name in module.connections.iter() {
-                queue.push_back((pulse_type, connection_name, name));
-            }
+            i += 1;
         }
 
-        i += 1;
+        // rx is activated when all groups repeat at the same time (so they all have sent out a high pulse last),
+        // the first time for the least common multiple of the cycle lengths
+        info!("group cycles {:?}", group_cycles);
+        diagnostics::report(
+            "group_cycles",
+            group_cycles.values().copied().collect::<Vec<_>>(),
+        );
+        Ok(math::lcm_all(group_cycles.values().copied())?)
     }
-
-    // rx is activated when all groups repeat at the same time (so they all have sent out a high pulse last)
-    println!("Group cycles {:?}", group_cycles);
-    group_cycles.values().product()
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -211,9 +274,9 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 1);
-        let result = part1(include_str!("input2_test.txt"));
+        let result = part2(include_str!("input2_test.txt")).expect("run without errors");
         assert_eq!(result, 4);
     }
 
@@ -231,4 +294,18 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn display_round_trip() {
+        let (_, (_, module)) = line_parser("broadcaster -> a, b, c").unwrap();
+        assert_eq!(module.to_string(), "broadcaster -> a, b, c");
+        for input in [include_str!("input2_test.txt"), include_str!("input1.txt")] {
+            let modules = Part2::parse(input).unwrap();
+            let text: String = modules
+                .values()
+                .map(|module| format!("{}\n", module))
+                .collect();
+            assert_eq!(Part2::parse(&text).unwrap(), modules);
+        }
+    }
 }
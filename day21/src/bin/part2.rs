@@ -0,0 +1,5 @@
+fn main() {
+    let input = common::load_input!().expect("readable input");
+    let output = day21::part2::part2(&input, 64).expect("found result");
+    dbg!(output);
+}
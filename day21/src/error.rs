@@ -0,0 +1,10 @@
+use grid::GridError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("no start tile `S` found")]
+    MissingStart,
+}
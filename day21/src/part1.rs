@@ -1,142 +1,147 @@
-use std::collections::VecDeque;
-
-use array2d::{Array2D, Error};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-use Direction::*;
-
-#[derive(Debug, PartialEq, EnumIter, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
-}
-
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            NORTH => (0, -1),
-            EAST => (1, 0),
-            SOUTH => (0, 1),
-            WEST => (-1, 0),
-        }
+use crate::Error;
+use animation::{Animate, Frame, Recorder};
+use common::Solution;
+use grid::Grid;
+use log::{debug, log_enabled, trace, Level};
+use raster::{palette, Image, Render};
+use search::{Reached, Search, State};
+
+common::params! {
+    pub struct Params {
+        /// number of steps the elf takes from the start
+        steps: u32 = 64,
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
+/// A garden plot the elf can stand on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Pos {
-    x: i32,
-    y: i32,
-    distance: u32,
+    x: usize,
+    y: usize,
 }
-impl PartialEq for Pos {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+
+impl State for Pos {
+    type Context = Grid<char>;
+    type Cost = u32;
+
+    fn successors(&self, grid: &Grid<char>) -> Vec<(Pos, u32)> {
+        grid.neighbours((self.x, self.y))
+            .filter(|&(_, next)| grid[next] != '#')
+            .map(|(_, (x, y))| (Pos { x, y }, 1))
+            .collect()
     }
 }
 
-fn is_in_bounds(position: (i32, i32), width: usize, height: usize) -> bool {
-    position.0 >= 0 && position.1 >= 0 && position.0 < width as i32 && position.1 < height as i32
+/// The plots the elf can reach in at most `steps` steps, with the fewest
+/// steps to each.
+fn reachable(grid: &Grid<char>, steps: u32) -> Result<Reached<Pos>, Error> {
+    let (x, y) = grid
+        .position(|&tile| tile == 'S')
+        .ok_or(Error::MissingStart)?;
+    let mut search = Search::new(grid);
+    let reached = search.reachable_within(Pos { x, y }, steps as usize);
+    debug!(
+        "reached {} plots, expanding {}",
+        reached.len(),
+        search.stats().expanded
+    );
+    Ok(reached)
 }
 
-fn get_successors(grid: &Array2D<u8>, pos: Pos) -> Vec<Pos> {
-    Direction::iter()
-        .filter_map(|direction| {
-            let delta = direction.delta();
-            let new_pos = (pos.x + delta.0, pos.y + delta.1);
-            let in_bounds = is_in_bounds(new_pos, grid.num_columns(), grid.num_rows());
-            if !in_bounds {
-                return None;
-            }
-
-            let grid_value = *grid
-                .get(new_pos.1 as usize, new_pos.0 as usize)
-                .expect("found grid tile");
-            if grid_value == b'#' {
-                return None;
-            }
-
-            Some(Pos {
-                x: new_pos.0,
-                y: new_pos.1,
-                distance: pos.distance + 1,
-            })
-        })
-        .collect()
+/// The distinct garden plots the elf can be on after exactly `step` steps:
+/// the ones it can reach in as many steps or an even number fewer, stepping
+/// back and forth for the rest.
+fn plots_after(reached: &Reached<Pos>, step: u32) -> Vec<Pos> {
+    let step = step as usize;
+    let mut plots: Vec<Pos> = reached
+        .iter()
+        .filter(|&(_, steps)| steps <= step && steps % 2 == step % 2)
+        .map(|(&pos, _)| pos)
+        .collect();
+    plots.sort();
+    plots
 }
 
-fn part1(input: &str, steps: u32) -> Result<u32, Error> {
-    let lines: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
-
-    let grid = Array2D::from_rows(&lines)?;
-
-    let width = grid.num_columns();
-    let height = grid.num_rows();
-
-    let mut position = Pos {
-        x: 0,
-        y: 0,
-        distance: 0,
-    };
-
-    'outer: for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let tile = *grid.get(x as usize, y as usize).unwrap();
-            if tile == b'S' {
-                position.x = x;
-                position.y = y;
-                break 'outer;
+fn trace_plots(grid: &Grid<char>, plots: &[Pos]) {
+    if log_enabled!(Level::Trace) {
+        let mut map = String::new();
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if plots.binary_search(&Pos { x, y }).is_ok() {
+                    map.push('O');
+                } else {
+                    map.push(grid[(x, y)]);
+                }
             }
+            map.push('\n');
         }
+        trace!("final positions:\n{}", map);
     }
+}
 
-    let mut queue = VecDeque::new();
-    queue.push_back(position);
+/// The plots the elf can be on after exactly `steps` steps.
+fn final_plots(grid: &Grid<char>, steps: u32) -> Result<Vec<Pos>, Error> {
+    let plots = plots_after(&reachable(grid, steps)?, steps);
+    debug!("found {} unique final positions", plots.len());
+    trace_plots(grid, &plots);
+    Ok(plots)
+}
 
-    for step in 0..steps {
-        println!("Step {}", step);
+pub struct Part1;
 
-        let mut next_steps = VecDeque::new();
+impl Solution for Part1 {
+    type Input<'a> = Grid<char>;
+    type Answer = u32;
+    type Params = Params;
+    type Error = Error;
 
-        // only handle positions added before this step
-        while !queue.is_empty() {
-            let pos = queue.pop_front().unwrap();
-            for next in get_successors(&grid, pos) {
-                if !next_steps.contains(&next) {
-                    next_steps.push_back(next);
-                }
-            }
-        }
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
 
-        queue = next_steps;
+    fn solve(grid: &Grid<char>, params: &Params) -> Result<u32, Error> {
+        Ok(final_plots(grid, params.steps)?.len() as u32)
     }
+}
 
-    let mut final_steps: Vec<Pos> = Vec::from(queue);
-    println!("Found {} possible paths", final_steps.len());
-    final_steps.sort();
-    final_steps.dedup();
-    println!("Found {} unique final positions", final_steps.len());
-
-    for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let pos = final_steps.iter().find(|p| p.x == x && p.y == y);
-            if pos.is_some() {
-                print!("O");
-            } else {
-                print!("{}", *grid.get(y as usize, x as usize).unwrap() as char);
+/// Draws the plots reachable in exactly the given number of steps.
+impl Render for Part1 {
+    fn render(grid: &Grid<char>, params: &Params) -> Result<Image, Error> {
+        let mut image = grid.map(|&tile| match tile {
+            '#' => palette::WALL,
+            'S' => palette::START,
+            _ => palette::BACKGROUND,
+        });
+        for pos in final_plots(grid, params.steps)? {
+            if grid[(pos.x, pos.y)] != 'S' {
+                image[(pos.x, pos.y)] = palette::VISITED;
             }
         }
-        println!();
+        Ok(image)
     }
+}
 
-    Ok(final_steps.len() as u32)
+/// Shows the plots reachable after every step, spreading out from the start.
+impl Animate for Part1 {
+    fn animate(grid: &Grid<char>, params: &Params, recorder: &mut Recorder) -> Result<(), Error> {
+        recorder.keep(Frame::new("step 0, 1 plot", grid));
+        let reached = reachable(grid, params.steps)?;
+        for step in 1..=params.steps {
+            recorder.record(|| {
+                let plots = plots_after(&reached, step);
+                let mut map = grid.clone();
+                for pos in &plots {
+                    map[(pos.x, pos.y)] = 'O';
+                }
+                Frame::new(format!("step {}, {} plots", step, plots.len()), map)
+            });
+        }
+        Ok(())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input, 64).expect("found result");
-    dbg!(output);
+pub fn part1(input: &str, steps: u32) -> Result<u32, Error> {
+    Part1::run(input, &Params { steps })
 }
 
 #[cfg(test)]
@@ -147,4 +152,17 @@ mod tests {
         let result = part1(include_str!("input1_test.txt"), 6).expect("run without errors");
         assert_eq!(result, 16);
     }
+
+    #[test]
+    fn render_reachable_plots() {
+        let grid = Part1::parse(include_str!("input1_test.txt")).expect("valid grid");
+        let image = Part1::render(&grid, &Params { steps: 6 }).expect("rendered");
+        let count = |color| image.iter().filter(|(_, &c)| c == color).count();
+        // the start is reachable again after an even number of steps
+        assert_eq!(count(palette::VISITED) + count(palette::START), 16);
+        assert_eq!(
+            count(palette::WALL),
+            grid.iter().filter(|(_, &t)| t == '#').count()
+        );
+    }
 }
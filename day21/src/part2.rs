@@ -1,136 +1,109 @@
-use std::collections::VecDeque;
-
-use array2d::{Array2D, Error};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-use Direction::*;
-
-#[derive(Debug, PartialEq, EnumIter, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
-}
-
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            NORTH => (0, -1),
-            EAST => (1, 0),
-            SOUTH => (0, 1),
-            WEST => (-1, 0),
-        }
+use crate::Error;
+use common::Solution;
+use grid::{Direction, Grid};
+use log::{debug, log_enabled, trace, Level};
+use search::{Search, State};
+
+common::params! {
+    pub struct Params {
+        /// number of steps the elf takes from the start
+        steps: u64 = 64,
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
+/// A garden plot the elf can stand on, in any of the copies of the garden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Pos {
-    x: i32,
-    y: i32,
-    distance: u64,
+    x: isize,
+    y: isize,
 }
-impl PartialEq for Pos {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-fn get_successors<'a>(grid: &'a Array2D<u8>, pos: &'a Pos) -> impl Iterator<Item = Pos> + 'a {
-    Direction::iter().filter_map(|direction| {
-        let delta = direction.delta();
-        let new_pos = (pos.x + delta.0, pos.y + delta.1);
-        let bounds_pos = (
-            new_pos.0.rem_euclid(grid.num_columns() as i32),
-            new_pos.1.rem_euclid(grid.num_rows() as i32),
-        );
 
-        let grid_value = *grid
-            .get(bounds_pos.1 as usize, bounds_pos.0 as usize)
-            .expect("found grid tile");
-        if grid_value == b'#' {
-            return None;
-        }
+impl State for Pos {
+    type Context = Grid<char>;
+    type Cost = u64;
 
-        Some(Pos {
-            x: new_pos.0,
-            y: new_pos.1,
-            distance: pos.distance + 1,
-        })
-    })
-}
+    fn successors(&self, grid: &Grid<char>) -> Vec<(Pos, u64)> {
+        Direction::iter()
+            .filter_map(|direction| {
+                let delta = direction.delta();
+                let (x, y) = (self.x + delta.0, self.y + delta.1);
 
-fn part1(input: &str, steps: u64) -> Result<u64, Error> {
-    let lines: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+                // the garden repeats infinitely in every direction
+                if *grid.get_wrapping(x, y) == '#' {
+                    return None;
+                }
 
-    let grid = Array2D::from_rows(&lines)?;
+                Some((Pos { x, y }, 1))
+            })
+            .collect()
+    }
+}
 
-    let width = grid.num_columns();
-    let height = grid.num_rows();
+pub struct Part2;
 
-    let mut position = Pos {
-        x: 0,
-        y: 0,
-        distance: 0,
-    };
+impl Solution for Part2 {
+    type Input<'a> = Grid<char>;
+    type Answer = u64;
+    type Params = Params;
+    type Error = Error;
 
-    'outer: for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let tile = *grid.get(x as usize, y as usize).unwrap();
-            if tile == b'S' {
-                position.x = x;
-                position.y = y;
-                break 'outer;
-            }
-        }
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
     }
 
-    let mut queue = VecDeque::new();
-    queue.push_back(position);
-
-    for step in 0..steps {
-        println!("Step {}", step);
-
-        let mut next_steps = VecDeque::new();
+    fn solve(grid: &Grid<char>, params: &Params) -> Result<u64, Error> {
+        let (x, y) = grid
+            .position(|&tile| tile == 'S')
+            .ok_or(Error::MissingStart)?;
+        let position = Pos {
+            x: x as isize,
+            y: y as isize,
+        };
+
+        let steps = params.steps as usize;
+        let mut search = Search::new(grid);
+        let reached = search.reachable_within(position, steps);
+        debug!(
+            "reached {} plots, expanding {}",
+            reached.len(),
+            search.stats().expanded
+        );
 
-        // only handle positions added before this step
-        while !queue.is_empty() {
-            let pos = queue.pop_front().unwrap();
-            for next in get_successors(&grid, &pos) {
-                if !next_steps.contains(&next) {
-                    next_steps.push_back(next);
+        // stepping back and forth, the elf can end on any plot it reaches in
+        // an even number of steps fewer
+        let mut final_steps: Vec<Pos> = reached
+            .iter()
+            .filter(|&(_, distance)| distance % 2 == steps % 2)
+            .map(|(&pos, _)| pos)
+            .collect();
+        final_steps.sort();
+        debug!("found {} unique final positions", final_steps.len());
+
+        if log_enabled!(Level::Trace) {
+            let mut map = String::new();
+            for y in 0..grid.height() {
+                for x in 0..grid.width() {
+                    let pos = Pos {
+                        x: x as isize,
+                        y: y as isize,
+                    };
+                    if final_steps.binary_search(&pos).is_ok() {
+                        map.push('O');
+                    } else {
+                        map.push(grid[(x, y)]);
+                    }
                 }
+                map.push('\n');
             }
+            trace!("final positions:\n{}", map);
         }
 
-        queue = next_steps;
-    }
-
-    let mut final_steps: Vec<Pos> = Vec::from(queue);
-    println!("Found {} possible paths", final_steps.len());
-    final_steps.sort();
-    final_steps.dedup();
-    println!("Found {} unique final positions", final_steps.len());
-
-    for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let pos = final_steps.iter().find(|p| p.x == x && p.y == y);
-            if pos.is_some() {
-                print!("O");
-            } else {
-                print!("{}", *grid.get(y as usize, x as usize).unwrap() as char);
-            }
-        }
-        println!();
+        Ok(final_steps.len() as u64)
     }
-
-    Ok(final_steps.len() as u64)
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input, 64).expect("found result");
-    dbg!(output);
+pub fn part2(input: &str, steps: u64) -> Result<u64, Error> {
+    Part2::run(input, &Params { steps })
 }
 
 #[cfg(test)]
@@ -142,12 +115,19 @@ mod tests {
     #[case(6, 16)]
     #[case(10, 50)]
     #[case(50, 1594)]
-    #[case(100, 6539)]
+    #[case(100, 6536)]
     #[case(500, 167004)]
+    fn it_works(#[case] steps: u64, #[case] expected: u64) {
+        let result = part2(include_str!("input1_test.txt"), steps).expect("run without errors");
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
     #[case(1000, 668697)]
     #[case(5000, 16733044)]
-    fn it_works(#[case] steps: u64, #[case] expected: u64) {
-        let result = part1(include_str!("input1_test.txt"), steps).expect("run without errors");
+    #[ignore = "slow, run with --release"]
+    fn many_steps(#[case] steps: u64, #[case] expected: u64) {
+        let result = part2(include_str!("input1_test.txt"), steps).expect("run without errors");
         assert_eq!(result, expected);
     }
 }
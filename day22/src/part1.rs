@@ -1,31 +1,60 @@
+use std::fmt;
+
 use itertools::Itertools;
-use std::str::FromStr;
 
+use crate::Error;
+use animation::{Animate, Frame, Recorder};
+use common::Solution;
 use nom::{
-    character::complete::{char, digit1},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    character::complete::char,
+    sequence::{preceded, separated_pair, tuple},
 };
+use parsing::{int_parser, parse_lines, IResult};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Brick {
-    start: Vec3,
-    end: Vec3,
-    supports: Vec<usize>,
-    supported_by: Vec<usize>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brick {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub supports: Vec<usize>,
+    pub supported_by: Vec<usize>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Vec3 {
-    x: u32,
-    y: u32,
-    z: u32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+/// The two ends of the brick, without what the solver finds it supports.
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}~{}", self.start, self.end)
+    }
 }
 
-fn line_parser(i: &str) -> IResult<&str, Brick> {
-    let (i, (start, end)) = separated_pair(vec_parser, char('~'), vec_parser)(i)?;
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+fn line_parser(i: &str) -> IResult<'_, Brick> {
+    let (i, (a, b)) = separated_pair(vec_parser, char('~'), vec_parser)(i)?;
+    // a brick may be written end first, but goes from its lowest to its
+    // highest corner on every axis here
+    let start = Vec3 {
+        x: a.x.min(b.x),
+        y: a.y.min(b.y),
+        z: a.z.min(b.z),
+    };
+    let end = Vec3 {
+        x: a.x.max(b.x),
+        y: a.y.max(b.y),
+        z: a.z.max(b.z),
+    };
     Ok((
         i,
         Brick {
@@ -37,21 +66,13 @@ fn line_parser(i: &str) -> IResult<&str, Brick> {
     ))
 }
 
-fn vec_parser(i: &str) -> IResult<&str, Vec3> {
-    let (i, vec) = separated_list1(char(','), int_parser)(i)?;
-    assert_eq!(vec.len(), 3);
-    Ok((
-        i,
-        Vec3 {
-            x: vec[0],
-            y: vec[1],
-            z: vec[2],
-        },
-    ))
-}
-
-fn int_parser(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, FromStr::from_str)(i)
+fn vec_parser(i: &str) -> IResult<'_, Vec3> {
+    let (i, (x, y, z)) = tuple((
+        int_parser,
+        preceded(char(','), int_parser),
+        preceded(char(','), int_parser),
+    ))(i)?;
+    Ok((i, Vec3 { x, y, z }))
 }
 
 fn check_range(val: u32, start: u32, end: u32) -> bool {
@@ -73,10 +94,16 @@ fn check_collision(a: &Brick, b: &Brick) -> bool {
             || check_range(b.end.y, a.start.y, a.end.y))
 }
 
-fn get_settled_bricks(input: &str) -> Vec<Brick> {
-    input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
+/// Let the bricks fall in order of their height until they rest on the
+/// ground or each other. `on_settle` sees the settled bricks every time one
+/// more came to rest.
+fn get_settled_bricks<F>(bricks: &[Brick], mut on_settle: F) -> Vec<Brick>
+where
+    F: FnMut(&[Brick]),
+{
+    bricks
+        .iter()
+        .cloned()
         .sorted_by_key(|brick| brick.start.z)
         .fold(
             (Vec::<Brick>::new(), 1),
@@ -117,30 +144,106 @@ fn get_settled_bricks(input: &str) -> Vec<Brick> {
 
                 let new_max_z = max_z.max(brick.end.z);
                 result.push(brick);
+                on_settle(&result);
                 (result, new_max_z)
             },
         )
         .0
 }
 
-fn part1(input: &str) -> u64 {
-    let bricks: Vec<Brick> = get_settled_bricks(input);
-
-    bricks
+/// The bricks seen from the front and from the side, with `@` for the one
+/// that settled last.
+fn side_views(bricks: &[Brick]) -> String {
+    let top = bricks.iter().map(|b| b.end.z).max().unwrap_or(0) as usize;
+    let width_x = bricks
+        .iter()
+        .map(|b| b.end.x.max(b.start.x))
+        .max()
+        .unwrap_or(0) as usize
+        + 1;
+    let width_y = bricks
         .iter()
-        .filter(|brick| {
-            brick
-                .supports
-                .iter()
-                .all(|&i| bricks[i].supported_by.len() > 1)
-        })
-        .count() as u64
+        .map(|b| b.end.y.max(b.start.y))
+        .max()
+        .unwrap_or(0) as usize
+        + 1;
+
+    // the latest brick covering every cell of the two views
+    let mut front = vec![vec![None; width_x]; top + 1];
+    let mut side = vec![vec![None; width_y]; top + 1];
+    for (i, brick) in bricks.iter().enumerate() {
+        for z in brick.start.z..=brick.end.z {
+            for x in brick.start.x.min(brick.end.x)..=brick.start.x.max(brick.end.x) {
+                front[z as usize][x as usize] = Some(i);
+            }
+            for y in brick.start.y.min(brick.end.y)..=brick.start.y.max(brick.end.y) {
+                side[z as usize][y as usize] = Some(i);
+            }
+        }
+    }
+    let row = |cells: &[Option<usize>]| -> String {
+        cells
+            .iter()
+            .map(|cell| match cell {
+                Some(i) if i + 1 == bricks.len() => '@',
+                Some(_) => '#',
+                None => '.',
+            })
+            .collect()
+    };
+
+    let mut text = format!("{:<w$}   y\n", "x", w = width_x);
+    for z in (1..=top).rev() {
+        text += &format!("{}   {} {}\n", row(&front[z]), row(&side[z]), z);
+    }
+    text += &format!("{}   {} 0\n", "-".repeat(width_x), "-".repeat(width_y));
+    text
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<Brick>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Brick>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(snapshot: &Vec<Brick>, _params: &()) -> Result<u64, Error> {
+        let bricks: Vec<Brick> = get_settled_bricks(snapshot, |_| {});
+
+        Ok(bricks
+            .iter()
+            .filter(|brick| {
+                brick
+                    .supports
+                    .iter()
+                    .all(|&i| bricks[i].supported_by.len() > 1)
+            })
+            .count() as u64)
+    }
+}
+
+/// Drops the bricks one by one, seen from the front and from the side.
+impl Animate for Part1 {
+    fn animate(snapshot: &Vec<Brick>, _params: &(), recorder: &mut Recorder) -> Result<(), Error> {
+        get_settled_bricks(snapshot, |settled| {
+            recorder.record(|| {
+                Frame::new(
+                    format!("{} of {} bricks settled", settled.len(), snapshot.len()),
+                    side_views(settled),
+                )
+            })
+        });
+        Ok(())
+    }
+}
+
+pub fn part1(input: &str) -> Result<u64, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -149,7 +252,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 5);
     }
 
@@ -167,4 +270,31 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn reversed_bricks() {
+        let (_, brick) = line_parser("1,8,23~1,8,21").unwrap();
+        assert_eq!(brick.start, Vec3 { x: 1, y: 8, z: 21 });
+        assert_eq!(brick.end, Vec3 { x: 1, y: 8, z: 23 });
+
+        let reversed: String = include_str!("input1_test.txt")
+            .lines()
+            .map(|line| {
+                let (start, end) = line.split_once('~').unwrap();
+                format!("{}~{}\n", end, start)
+            })
+            .collect();
+        assert_eq!(part1(&reversed).expect("run without errors"), 5);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let example = include_str!("input1_test.txt");
+        let bricks = Part1::parse(example).unwrap();
+        let text: String = bricks.iter().map(|brick| format!("{}\n", brick)).collect();
+        assert_eq!(text, example);
+        let bricks = Part1::parse(include_str!("input1.txt")).unwrap();
+        let text: String = bricks.iter().map(|brick| format!("{}\n", brick)).collect();
+        assert_eq!(Part1::parse(&text).unwrap(), bricks);
+    }
 }
@@ -1,32 +1,60 @@
+use std::fmt;
+
 use itertools::Itertools;
 use priority_queue::PriorityQueue;
-use std::str::FromStr;
 
+use crate::Error;
+use common::Solution;
 use nom::{
-    character::complete::{char, digit1},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    character::complete::char,
+    sequence::{preceded, separated_pair, tuple},
 };
+use parsing::{int_parser, parse_lines, IResult};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Brick {
-    start: Vec3,
-    end: Vec3,
-    supports: Vec<usize>,
-    supported_by: Vec<usize>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brick {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub supports: Vec<usize>,
+    pub supported_by: Vec<usize>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Vec3 {
-    x: u32,
-    y: u32,
-    z: u32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+/// The two ends of the brick, without what the solver finds it supports.
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}~{}", self.start, self.end)
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
 }
 
-fn line_parser(i: &str) -> IResult<&str, Brick> {
-    let (i, (start, end)) = separated_pair(vec_parser, char('~'), vec_parser)(i)?;
+fn line_parser(i: &str) -> IResult<'_, Brick> {
+    let (i, (a, b)) = separated_pair(vec_parser, char('~'), vec_parser)(i)?;
+    // a brick may be written end first, but goes from its lowest to its
+    // highest corner on every axis here
+    let start = Vec3 {
+        x: a.x.min(b.x),
+        y: a.y.min(b.y),
+        z: a.z.min(b.z),
+    };
+    let end = Vec3 {
+        x: a.x.max(b.x),
+        y: a.y.max(b.y),
+        z: a.z.max(b.z),
+    };
     Ok((
         i,
         Brick {
@@ -38,21 +66,13 @@ fn line_parser(i: &str) -> IResult<&str, Brick> {
     ))
 }
 
-fn vec_parser(i: &str) -> IResult<&str, Vec3> {
-    let (i, vec) = separated_list1(char(','), int_parser)(i)?;
-    assert_eq!(vec.len(), 3);
-    Ok((
-        i,
-        Vec3 {
-            x: vec[0],
-            y: vec[1],
-            z: vec[2],
-        },
-    ))
-}
-
-fn int_parser(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, FromStr::from_str)(i)
+fn vec_parser(i: &str) -> IResult<'_, Vec3> {
+    let (i, (x, y, z)) = tuple((
+        int_parser,
+        preceded(char(','), int_parser),
+        preceded(char(','), int_parser),
+    ))(i)?;
+    Ok((i, Vec3 { x, y, z }))
 }
 
 fn check_range(val: u32, start: u32, end: u32) -> bool {
@@ -74,10 +94,10 @@ fn check_collision(a: &Brick, b: &Brick) -> bool {
             || check_range(b.end.y, a.start.y, a.end.y))
 }
 
-fn get_settled_bricks(input: &str) -> Vec<Brick> {
-    input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
+fn get_settled_bricks(bricks: &[Brick]) -> Vec<Brick> {
+    bricks
+        .iter()
+        .cloned()
         .sorted_by_key(|brick| brick.start.z)
         .fold(
             (Vec::<Brick>::new(), 1),
@@ -124,46 +144,57 @@ fn get_settled_bricks(input: &str) -> Vec<Brick> {
         .0
 }
 
-fn part1(input: &str) -> usize {
-    let bricks: Vec<Brick> = get_settled_bricks(input);
-    (0..bricks.len())
-        .rev()
-        .map(|i| {
-            if bricks[i].supports.is_empty() {
-                return 0;
-            }
+pub struct Part2;
 
-            let mut fallen_bricks: Vec<usize> = vec![];
-            let mut queue = PriorityQueue::<usize, u32>::new();
-            queue.push(i, bricks[i].end.z);
+impl Solution for Part2 {
+    type Input<'a> = Vec<Brick>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
 
-            while !queue.is_empty() {
-                let (id, _) = queue.pop().unwrap();
-                fallen_bricks.push(id);
-                let brick = &bricks[id];
+    fn parse(input: &str) -> Result<Vec<Brick>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(snapshot: &Vec<Brick>, _params: &()) -> Result<usize, Error> {
+        let bricks: Vec<Brick> = get_settled_bricks(snapshot);
+        Ok((0..bricks.len())
+            .rev()
+            .map(|i| {
+                if bricks[i].supports.is_empty() {
+                    return 0;
+                }
+
+                let mut fallen_bricks: Vec<usize> = vec![];
+                let mut queue = PriorityQueue::<usize, u32>::new();
+                queue.push(i, bricks[i].end.z);
 
-                for supported_id in &brick.supports {
-                    let supported_brick = &bricks[*supported_id];
+                while !queue.is_empty() {
+                    let (id, _) = queue.pop().unwrap();
+                    fallen_bricks.push(id);
+                    let brick = &bricks[id];
 
-                    if supported_brick
-                        .supported_by
-                        .iter()
-                        .all(|id| fallen_bricks.iter().rev().contains(id))
-                    {
-                        queue.push(*supported_id, supported_brick.end.z);
+                    for supported_id in &brick.supports {
+                        let supported_brick = &bricks[*supported_id];
+
+                        if supported_brick
+                            .supported_by
+                            .iter()
+                            .all(|id| fallen_bricks.iter().rev().contains(id))
+                        {
+                            queue.push(*supported_id, supported_brick.end.z);
+                        }
                     }
                 }
-            }
 
-            fallen_bricks.len() - 1
-        })
-        .sum()
+                fallen_bricks.len() - 1
+            })
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<usize, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -172,7 +203,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 7);
     }
 
@@ -190,4 +221,31 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn reversed_bricks() {
+        let (_, brick) = line_parser("1,8,23~1,8,21").unwrap();
+        assert_eq!(brick.start, Vec3 { x: 1, y: 8, z: 21 });
+        assert_eq!(brick.end, Vec3 { x: 1, y: 8, z: 23 });
+
+        let reversed: String = include_str!("input1_test.txt")
+            .lines()
+            .map(|line| {
+                let (start, end) = line.split_once('~').unwrap();
+                format!("{}~{}\n", end, start)
+            })
+            .collect();
+        assert_eq!(part2(&reversed).expect("run without errors"), 7);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let example = include_str!("input1_test.txt");
+        let bricks = Part2::parse(example).unwrap();
+        let text: String = bricks.iter().map(|brick| format!("{}\n", brick)).collect();
+        assert_eq!(text, example);
+        let bricks = Part2::parse(include_str!("input1.txt")).unwrap();
+        let text: String = bricks.iter().map(|brick| format!("{}\n", brick)).collect();
+        assert_eq!(Part2::parse(&text).unwrap(), bricks);
+    }
 }
@@ -0,0 +1,5 @@
+mod error;
+pub mod part1;
+pub mod part2;
+
+pub use error::Error;
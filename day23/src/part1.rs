@@ -1,162 +1,143 @@
-use std::collections::VecDeque;
-
-use array2d::{Array2D, Error};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+use crate::Error;
+use common::Solution;
+use grid::{Direction, Grid};
+use raster::{palette, Image, Render};
+use search::{Search, State};
 use Direction::*;
 
-#[derive(Debug, PartialEq, EnumIter, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
-}
-
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            NORTH => (0, -1),
-            EAST => (1, 0),
-            SOUTH => (0, 1),
-            WEST => (-1, 0),
-        }
-    }
-
-    fn opposite(&self) -> Direction {
-        match self {
-            NORTH => SOUTH,
-            EAST => WEST,
-            SOUTH => NORTH,
-            WEST => EAST,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Pos {
-    x: i32,
-    y: i32,
-}
-impl PartialEq for Pos {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-fn is_in_bounds(position: (i32, i32), width: usize, height: usize) -> bool {
-    position.0 >= 0 && position.1 >= 0 && position.0 < width as i32 && position.1 < height as i32
+    x: usize,
+    y: usize,
 }
 
 fn get_successors(
-    grid: &Array2D<u8>,
+    grid: &Grid<char>,
     pos: &Pos,
     prev_direction: &Direction,
 ) -> Vec<(Pos, Direction)> {
-    Direction::iter()
-        .filter_map(|direction| {
+    grid.neighbours((pos.x, pos.y))
+        .filter_map(|(direction, (x, y))| {
             if direction == prev_direction.opposite() {
                 return None;
             }
 
-            let delta = direction.delta();
-            let new_pos = (pos.x + delta.0, pos.y + delta.1);
-            let in_bounds = is_in_bounds(new_pos, grid.num_columns(), grid.num_rows());
-            if !in_bounds {
-                return None;
-            }
-
-            let grid_value = *grid
-                .get(new_pos.1 as usize, new_pos.0 as usize)
-                .expect("found grid tile");
-            match grid_value {
-                b'#' => return None,
-                b'^' if direction != NORTH => return None,
-                b'>' if direction != EAST => return None,
-                b'v' if direction != SOUTH => return None,
-                b'<' if direction != WEST => return None,
-                _ => (),
+            // slopes can only be walked down
+            match grid[(x, y)] {
+                '#' => return None,
+                slope => {
+                    if Direction::from_arrow(slope).is_some_and(|down| down != direction) {
+                        return None;
+                    }
+                }
             }
 
-            Some((
-                Pos {
-                    x: new_pos.0,
-                    y: new_pos.1,
-                },
-                direction,
-            ))
+            Some((Pos { x, y }, direction))
         })
         .collect()
 }
 
-fn part1(input: &str) -> Result<u32, Error> {
-    let lines: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+/// A hiker on a tile, having come from `direction` after `length` steps.
+/// The length is part of the state as every hike that gets there differently
+/// is followed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Hike {
+    pos: Pos,
+    direction: Direction,
+    length: u32,
+}
 
-    let grid = Array2D::from_rows(&lines)?;
+impl State for Hike {
+    type Context = Grid<char>;
+    type Cost = u32;
+
+    fn successors(&self, grid: &Grid<char>) -> Vec<(Hike, u32)> {
+        get_successors(grid, &self.pos, &self.direction)
+            .into_iter()
+            .map(|(pos, direction)| {
+                let length = self.length + 1;
+                (
+                    Hike {
+                        pos,
+                        direction,
+                        length,
+                    },
+                    1,
+                )
+            })
+            .collect()
+    }
 
-    let initial_position = Pos { x: 1, y: 0 };
+    fn is_goal(&self, grid: &Grid<char>) -> bool {
+        self.pos.y == grid.height() - 1
+    }
+}
 
-    let mut queue = VecDeque::new();
-    queue.push_back((initial_position, 0, SOUTH, vec![initial_position]));
+/// Length and tiles of the longest hike from the top left to the bottom right
+/// that never steps onto a tile twice.
+fn longest_path(grid: &Grid<char>) -> Result<(u32, Vec<Pos>), Error> {
+    let start = Hike {
+        pos: Pos { x: 1, y: 0 },
+        direction: South,
+        length: 0,
+    };
+
+    // the slopes never lead back, so every hike ends
+    let hikes = Search::new(grid).reachable(start);
+    let longest = hikes
+        .iter()
+        .map(|(hike, _)| hike)
+        .filter(|hike| hike.is_goal(grid))
+        .max_by_key(|hike| hike.length)
+        .ok_or(Error::NoPath)?;
+    let path = hikes
+        .path_to(longest)
+        .expect("path to a reached hike")
+        .into_iter()
+        .map(|hike| hike.pos)
+        .collect();
+
+    Ok((longest.length, path))
+}
 
-    let mut path_lengths = vec![];
+pub struct Part1;
 
-    while !queue.is_empty() {
-        let (mut pos, mut current_length, mut prev_direction, mut path) =
-            queue.pop_front().unwrap();
-        loop {
-            let successors = get_successors(&grid, &pos, &prev_direction);
-            if successors.len() == 0 {
-                break;
-            }
-            if successors.len() == 2 {
-                let mut split_path = path.clone();
-                split_path.push(successors[1].0);
-                queue.push_back((
-                    successors[1].0,
-                    current_length + 1,
-                    successors[1].1,
-                    split_path,
-                ));
-            }
-            if successors.len() > 2 {
-                panic!("Dang, not binary!!!");
-            }
-            pos = successors[0].0;
-            prev_direction = successors[0].1;
-            current_length += 1;
-            path.push(pos);
-        }
+impl Solution for Part1 {
+    type Input<'a> = Grid<char>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
 
-        path_lengths.push((current_length, path));
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
     }
 
-    let (longest_length, _longest_path) = path_lengths
-        .iter()
-        .max_by_key(|(len, _)| len)
-        .expect("found max");
-
-    /* enable for debug output of grid and chosen path
-    for y in 0..grid.num_rows() as i32 {
-        for x in 0..grid.num_columns() as i32 {
-            let pos = longest_path.iter().find(|p| p.x == x && p.y == y);
-            if pos.is_some() {
-                print!("O");
-            } else {
-                print!("{}", *grid.get(y as usize, x as usize).unwrap() as char);
-            }
-        }
-        println!();
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<u32, Error> {
+        let (longest_length, _) = longest_path(grid)?;
+
+        Ok(longest_length)
     }
-    */
+}
 
-    Ok(*longest_length)
+/// Draws the longest hike through the forest, with the slopes in between.
+impl Render for Part1 {
+    fn render(grid: &Grid<char>, _params: &()) -> Result<Image, Error> {
+        let mut image = grid.map(|&tile| match tile {
+            '#' => palette::WALL,
+            '.' => palette::BACKGROUND,
+            _ => palette::BACKGROUND.mix(palette::PATH, 0.4),
+        });
+        let (_, path) = longest_path(grid)?;
+        for pos in path {
+            image[(pos.x, pos.y)] = palette::PATH;
+        }
+        image[(1, 0)] = palette::START;
+        Ok(image)
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input).expect("found result");
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
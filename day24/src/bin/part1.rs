@@ -0,0 +1,6 @@
+fn main() {
+    let input = common::load_input!().expect("readable input");
+    let output = day24::part1::part1(&input, 200_000_000_000_000.0, 400_000_000_000_000.0)
+        .expect("found result");
+    dbg!(output);
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let input = common::load_input!().expect("readable input");
+    let output = day24::part2::part2(&input).expect("found result");
+    dbg!(output);
+}
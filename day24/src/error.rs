@@ -0,0 +1,13 @@
+use ndarray_linalg::error::LinalgError;
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("no rock speed hits every hailstone")]
+    NoSpeed,
+    #[error("the rock's starting position can't be solved: {0}")]
+    Solve(#[from] LinalgError),
+}
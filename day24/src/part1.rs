@@ -1,23 +1,29 @@
 use itertools::Itertools;
-use std::{
-    ops::{Add, Mul},
-    str::FromStr,
-};
+use std::ops::{Add, Mul};
 
+use crate::Error;
+use common::Solution;
+use log::trace;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{char, digit1, space1},
-    combinator::{map_res, opt, recognize},
-    multi::separated_list1,
-    sequence::{pair, preceded, separated_pair},
-    IResult,
+    character::complete::{char, space0},
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
 };
+use parsing::{int_parser, parse_lines, token, IResult};
+
+common::params! {
+    pub struct Params {
+        /// lower bound of the test area on the x and y axis
+        axis_min: f64 = 200_000_000_000_000.0,
+        /// upper bound of the test area on the x and y axis
+        axis_max: f64 = 400_000_000_000_000.0,
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
-struct Vec3 {
-    x: f64,
-    y: f64,
-    z: f64,
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
 }
 
 impl Mul<f64> for Vec3 {
@@ -44,71 +50,70 @@ impl Add<Vec3> for Vec3 {
     }
 }
 
-fn line_parser(i: &str) -> IResult<&str, (Vec3, Vec3)> {
-    separated_pair(vec3_parser, pair(tag(" @"), space1), vec3_parser)(i)
+fn line_parser(i: &str) -> IResult<'_, (Vec3, Vec3)> {
+    separated_pair(vec3_parser, pair(space0, token("@")), vec3_parser)(i)
 }
 
-fn vec3_parser(i: &str) -> IResult<&str, Vec3> {
-    let (i, numbers) = separated_list1(pair(char(','), space1), int_parser)(i)?;
-    Ok((
-        i,
-        Vec3 {
-            x: numbers[0],
-            y: numbers[1],
-            z: numbers[2],
-        },
-    ))
+fn vec3_parser(i: &str) -> IResult<'_, Vec3> {
+    let (i, (x, y, z)) = tuple((
+        preceded(space0, int_parser),
+        preceded(delimited(space0, char(','), space0), int_parser),
+        preceded(delimited(space0, char(','), space0), int_parser),
+    ))(i)?;
+    Ok((i, Vec3 { x, y, z }))
 }
 
-fn int_parser(i: &str) -> IResult<&str, f64> {
-    map_res(
-        recognize(preceded(opt(tag("-")), digit1)),
-        FromStr::from_str,
-    )(i)
-}
+pub struct Part1;
 
-fn part1(input: &str, axis_min: f64, axis_max: f64) -> usize {
-    let stones: Vec<(Vec3, Vec3)> = input
-        .lines()
-        .map(|line| line_parser(line).expect("valid input").1)
-        .collect();
-
-    stones
-        .iter()
-        .tuple_combinations()
-        .filter(|(&(pa1, va), &(pb1, vb))| {
-            // println!("Hailstone A: {:?} @ {:?}", pa1, va);
-            // println!("Hailstone B: {:?} @ {:?}", pb1, vb);
-
-            let pa2 = pa1 + va;
-            let pb2 = pb1 + vb;
-
-            // calculate line-line intersection with 2 points on each line
-            let ta = ((pa1.x - pb1.x) * (pb1.y - pb2.y) - (pa1.y - pb1.y) * (pb1.x - pb2.x))
-                / ((pa1.x - pa2.x) * (pb1.y - pb2.y) - (pa1.y - pa2.y) * (pb1.x - pb2.x));
-            let tb = ((pa1.x - pb1.x) * (pa1.y - pa2.y) - (pa1.y - pb1.y) * (pa1.x - pa2.x))
-                / ((pa1.x - pa2.x) * (pb1.y - pb2.y) - (pa1.y - pa2.y) * (pb1.x - pb2.x));
-
-            if ta < 0.0 {
-                // println!("Hailstones' paths crossed in the past for hailstone A.");
-                return false;
-            }
-            if tb < 0.0 {
-                // println!("Hailstones' paths crossed in the past for hailstone B.");
-                return false;
-            }
-
-            let cross = pa1 + va * ta;
-            // println!("Crossing at {:?}", cross);
-            cross.x >= axis_min && cross.x <= axis_max && cross.y >= axis_min && cross.y <= axis_max
-        })
-        .count()
+impl Solution for Part1 {
+    type Input<'a> = Vec<(Vec3, Vec3)>;
+    type Answer = usize;
+    type Params = Params;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<(Vec3, Vec3)>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(stones: &Vec<(Vec3, Vec3)>, params: &Params) -> Result<usize, Error> {
+        Ok(stones
+            .iter()
+            .tuple_combinations()
+            .filter(|(&(pa1, va), &(pb1, vb))| {
+                trace!("hailstone A: {:?} @ {:?}", pa1, va);
+                trace!("hailstone B: {:?} @ {:?}", pb1, vb);
+
+                let pa2 = pa1 + va;
+                let pb2 = pb1 + vb;
+
+                // calculate line-line intersection with 2 points on each line
+                let ta = ((pa1.x - pb1.x) * (pb1.y - pb2.y) - (pa1.y - pb1.y) * (pb1.x - pb2.x))
+                    / ((pa1.x - pa2.x) * (pb1.y - pb2.y) - (pa1.y - pa2.y) * (pb1.x - pb2.x));
+                let tb = ((pa1.x - pb1.x) * (pa1.y - pa2.y) - (pa1.y - pb1.y) * (pa1.x - pa2.x))
+                    / ((pa1.x - pa2.x) * (pb1.y - pb2.y) - (pa1.y - pa2.y) * (pb1.x - pb2.x));
+
+                if ta < 0.0 {
+                    trace!("paths crossed in the past for hailstone A");
+                    return false;
+                }
+                if tb < 0.0 {
+                    trace!("paths crossed in the past for hailstone B");
+                    return false;
+                }
+
+                let cross = pa1 + va * ta;
+                trace!("crossing at {:?}", cross);
+                cross.x >= params.axis_min
+                    && cross.x <= params.axis_max
+                    && cross.y >= params.axis_min
+                    && cross.y <= params.axis_max
+            })
+            .count())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input, 200_000_000_000_000.0, 400_000_000_000_000.0);
-    dbg!(output);
+pub fn part1(input: &str, axis_min: f64, axis_max: f64) -> Result<usize, Error> {
+    Part1::run(input, &Params { axis_min, axis_max })
 }
 
 #[cfg(test)]
@@ -116,7 +121,7 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"), 7.0, 27.0);
+        let result = part1(include_str!("input1_test.txt"), 7.0, 27.0).expect("run without errors");
         assert_eq!(result, 2);
     }
 
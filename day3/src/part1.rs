@@ -1,39 +1,25 @@
-use std::cmp::max;
+use crate::Error;
+use common::Solution;
+use grid::Grid;
+use log::trace;
 
 fn is_symbol(c: char) -> bool {
     c != '.' && !is_number(c)
 }
 
 fn is_number(c: char) -> bool {
-    c >= '0' && c <= '9'
+    c.is_ascii_digit()
 }
 
-static OFFSETS: &'static [(i32, i32)] = &[
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-];
-
-fn has_adjacent_symbol(grid: &Vec<Vec<char>>, cur_x: usize, cur_y: usize, digits: usize) -> bool {
+fn has_adjacent_symbol(grid: &Grid<char>, cur_x: usize, cur_y: usize, digits: usize) -> bool {
     let mut found_symbol = false;
-    let x_start = max(cur_x - digits, 0);
+    let x_start = cur_x.saturating_sub(digits);
     'outer: for x in x_start..cur_x {
-        for offset in OFFSETS {
-            let ny = cur_y as i32 + offset.1;
-            let nx = x as i32 + offset.0;
-            if nx < 0 || nx >= grid[0].len() as i32 || ny < 0 || ny >= grid.len() as i32 {
-                continue;
-            }
-
-            let c = grid[ny as usize][nx as usize];
+        for neighbour in grid.neighbours8((x, cur_y)) {
+            let c = grid[neighbour];
             if is_symbol(c) {
                 found_symbol = true;
-                println!("Found symbol {}", c);
+                trace!("found symbol {} next to ({}, {})", c, x, cur_y);
                 break 'outer;
             }
         }
@@ -41,51 +27,54 @@ fn has_adjacent_symbol(grid: &Vec<Vec<char>>, cur_x: usize, cur_y: usize, digits
     found_symbol
 }
 
-fn part1(input: &str) -> u32 {
-    let grid: Vec<Vec<char>> = input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(|line| line.chars().collect())
-        .collect();
-    let mut valid_numbers: Vec<u32> = vec![];
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Grid<char>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<u32, Error> {
+        let mut valid_numbers: Vec<u32> = vec![];
 
-    let width = grid[0].len();
-    for y in 0..grid.len() {
-        let mut current_number = 0;
-        let mut digits = 0;
+        let width = grid.width();
+        for (y, row) in grid.rows().enumerate() {
+            let mut current_number = 0;
+            let mut digits = 0;
 
-        for x in 0..width {
-            let c = grid[y][x];
-            if is_number(c) {
-                current_number *= 10;
-                current_number += c.to_digit(10).expect("valid digit");
-                digits += 1;
-            } else {
-                if current_number > 0 {
-                    dbg!(current_number);
-                    if has_adjacent_symbol(&grid, x, y, digits) {
-                        valid_numbers.push(current_number);
+            for (x, &c) in row.iter().enumerate() {
+                if is_number(c) {
+                    current_number *= 10;
+                    current_number += c.to_digit(10).expect("valid digit");
+                    digits += 1;
+                } else {
+                    if current_number > 0 {
+                        trace!("number {} ends at ({}, {})", current_number, x, y);
+                        if has_adjacent_symbol(grid, x, y, digits) {
+                            valid_numbers.push(current_number);
+                        }
                     }
+                    digits = 0;
+                    current_number = 0;
                 }
-                digits = 0;
-                current_number = 0;
             }
-        }
 
-        if current_number > 0 {
-            if has_adjacent_symbol(&grid, width - 1, y, digits) {
+            if current_number > 0 && has_adjacent_symbol(grid, width, y, digits) {
                 valid_numbers.push(current_number);
             }
         }
-    }
 
-    valid_numbers.iter().sum()
+        Ok(valid_numbers.iter().sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -94,7 +83,14 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 4361);
     }
+
+    #[test]
+    fn number_at_the_end_of_a_row() {
+        assert_eq!(part1("*.12\n....\n").expect("run without errors"), 0);
+        assert_eq!(part1("*12\n...\n").expect("run without errors"), 12);
+        assert_eq!(part1("6").expect("run without errors"), 0);
+    }
 }
@@ -1,39 +1,27 @@
-use std::{cmp::max, collections::BTreeMap};
+use std::collections::BTreeMap;
+
+use crate::Error;
+use common::Solution;
+use grid::Grid;
+use log::trace;
 
 fn is_number(c: char) -> bool {
-    c >= '0' && c <= '9'
+    c.is_ascii_digit()
 }
 
-static OFFSETS: &'static [(i32, i32)] = &[
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-];
-
 fn find_adjacent_gear(
-    grid: &Vec<Vec<char>>,
+    grid: &Grid<char>,
     cur_x: usize,
     cur_y: usize,
     digits: usize,
 ) -> Option<(usize, usize)> {
-    let x_start = max(cur_x - digits, 0);
+    let x_start = cur_x.saturating_sub(digits);
     for x in x_start..cur_x {
-        for offset in OFFSETS {
-            let ny = cur_y as i32 + offset.1;
-            let nx = x as i32 + offset.0;
-            if nx < 0 || nx >= grid[0].len() as i32 || ny < 0 || ny >= grid.len() as i32 {
-                continue;
-            }
-
-            let c = grid[ny as usize][nx as usize];
+        for neighbour in grid.neighbours8((x, cur_y)) {
+            let c = grid[neighbour];
             if c == '*' {
-                println!("Found symbol {}", c);
-                return Some((nx as usize, ny as usize));
+                trace!("found gear {} at {:?}", c, neighbour);
+                return Some(neighbour);
             }
         }
     }
@@ -41,7 +29,7 @@ fn find_adjacent_gear(
 }
 
 fn check_and_save_number(
-    grid: &Vec<Vec<char>>,
+    grid: &Grid<char>,
     gears: &mut BTreeMap<(usize, usize), Vec<u32>>,
     number: u32,
     x: usize,
@@ -49,58 +37,62 @@ fn check_and_save_number(
     digits: usize,
 ) {
     if number > 0 {
-        dbg!(number);
-        let gear = find_adjacent_gear(&grid, x, y, digits);
-        match gear {
-            Some(pos) => gears
+        trace!("number {} ends at ({}, {})", number, x, y);
+        let gear = find_adjacent_gear(grid, x, y, digits);
+        if let Some(pos) = gear {
+            gears
                 .entry(pos)
                 .or_insert(Vec::with_capacity(2))
-                .push(number),
-            _ => (),
+                .push(number)
         };
     }
 }
 
-fn part2(input: &str) -> u32 {
-    let grid: Vec<Vec<char>> = input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(|line| line.chars().collect())
-        .collect();
-    let mut gears: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
-
-    let width = grid[0].len();
-    for y in 0..grid.len() {
-        let mut current_number = 0;
-        let mut digits = 0;
-
-        for x in 0..width {
-            let c = grid[y][x];
-            if is_number(c) {
-                current_number *= 10;
-                current_number += c.to_digit(10).expect("valid digit");
-                digits += 1;
-            } else {
-                check_and_save_number(&grid, &mut gears, current_number, x, y, digits);
-                digits = 0;
-                current_number = 0;
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Grid<char>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn solve(grid: &Grid<char>, _params: &()) -> Result<u32, Error> {
+        let mut gears: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
+
+        let width = grid.width();
+        for (y, row) in grid.rows().enumerate() {
+            let mut current_number = 0;
+            let mut digits = 0;
+
+            for (x, &c) in row.iter().enumerate() {
+                if is_number(c) {
+                    current_number *= 10;
+                    current_number += c.to_digit(10).expect("valid digit");
+                    digits += 1;
+                } else {
+                    check_and_save_number(grid, &mut gears, current_number, x, y, digits);
+                    digits = 0;
+                    current_number = 0;
+                }
             }
+
+            check_and_save_number(grid, &mut gears, current_number, width, y, digits);
         }
 
-        check_and_save_number(&grid, &mut gears, current_number, width - 1, y, digits);
+        Ok(gears
+            .values()
+            .filter(|numbers| numbers.len() == 2)
+            .map(|numbers| numbers[0] * numbers[1])
+            .sum())
     }
-
-    gears
-        .values()
-        .filter(|numbers| numbers.len() == 2)
-        .map(|numbers| numbers[0] * numbers[1])
-        .sum()
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part2(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u32, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -109,7 +101,14 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part2(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 467835);
     }
+
+    #[test]
+    fn number_at_the_end_of_a_row() {
+        assert_eq!(part2("*.12\n3...\n").expect("run without errors"), 0);
+        assert_eq!(part2("*12\n3..\n").expect("run without errors"), 36);
+        assert_eq!(part2("6").expect("run without errors"), 0);
+    }
 }
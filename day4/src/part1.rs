@@ -1,79 +1,91 @@
 extern crate nom;
+use std::fmt;
+
+use crate::Error;
+use common::Solution;
+use log::trace;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{char, digit1, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult,
+    character::complete::space1,
+    sequence::{pair, preceded},
 };
+use parsing::{int_parser, number_list_parser, parse_lines, token, IResult};
 
 #[derive(Default, Debug, PartialEq, Eq)]
-struct Card {
-    id: u32,
-    winning_numbers: Vec<u32>,
-    card_numbers: Vec<u32>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Card {
+    pub id: u32,
+    pub winning_numbers: Vec<u32>,
+    pub card_numbers: Vec<u32>,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+        for number in &self.winning_numbers {
+            write!(f, " {}", number)?;
+        }
+        write!(f, " |")?;
+        for number in &self.card_numbers {
+            write!(f, " {}", number)?;
+        }
+        Ok(())
+    }
 }
 
-fn line_parser(i: &str) -> IResult<&str, Card> {
-    let (i, card) = separated_pair(tag("Card"), space1, digit1)(i)?;
-    let (i, _) = preceded(char(':'), space1)(i)?;
-    let (i, winning_numbers) = number_list_parser(i)?;
-    let (i, _) = preceded(tag(" |"), space1)(i)?;
-    let (i, card_numbers) = number_list_parser(i)?;
+fn line_parser(i: &str) -> IResult<'_, Card> {
+    let (i, id) = preceded(pair(token("Card"), space1), int_parser)(i)?;
+    let (i, winning_numbers) = preceded(token(":"), number_list_parser)(i)?;
+    let (i, card_numbers) = preceded(token("|"), number_list_parser)(i)?;
     Ok((
         i,
         Card {
-            id: card.1.parse().expect("valid game ID"),
+            id,
             winning_numbers,
             card_numbers,
         },
     ))
 }
 
-fn number_list_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    let (i, list) = separated_list1(space1, digit1)(i)?;
-    let numbers = list
-        .iter()
-        .map(|str| str.parse().expect("valid number"))
-        .collect();
-    Ok((i, numbers))
-}
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<Card>;
+    type Answer = i32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Card>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
 
-fn part1(input: &str) -> i32 {
-    input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(line_parser)
-        .map(|result| {
-            result
-                .as_ref()
-                .map(|(_, card): &(&str, Card)| -> i32 {
-                    let matches = card
-                        .card_numbers
-                        .iter()
-                        .map(|number| {
-                            if card.winning_numbers.contains(number) {
-                                1
-                            } else {
-                                0
-                            }
-                        })
-                        .sum::<i32>();
-                    if matches < 1 {
-                        return 0;
-                    }
-                    let points = 1 << (matches - 1);
-                    dbg!(points)
-                })
-                .expect("valid line")
-        })
-        .sum()
+    fn solve(cards: &Vec<Card>, _params: &()) -> Result<i32, Error> {
+        Ok(cards
+            .iter()
+            .map(|card| {
+                let matches = card
+                    .card_numbers
+                    .iter()
+                    .map(|number| {
+                        if card.winning_numbers.contains(number) {
+                            1
+                        } else {
+                            0
+                        }
+                    })
+                    .sum::<i32>();
+                if matches < 1 {
+                    return 0;
+                }
+                let points = 1 << (matches - 1);
+                trace!("{} matches are worth {} points", matches, points);
+                points
+            })
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<i32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -97,7 +109,21 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 13);
     }
+
+    #[test]
+    fn display_round_trip() {
+        let cards = Part1::parse(include_str!("input1_test.txt")).unwrap();
+        assert_eq!(
+            cards[0].to_string(),
+            "Card 1: 41 48 83 86 17 | 83 86 6 31 17 9 48 53"
+        );
+        for input in [include_str!("input1_test.txt"), include_str!("input1.txt")] {
+            let cards = Part1::parse(input).unwrap();
+            let text: String = cards.iter().map(|card| format!("{}\n", card)).collect();
+            assert_eq!(Part1::parse(&text).unwrap(), cards);
+        }
+    }
 }
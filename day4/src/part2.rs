@@ -1,32 +1,50 @@
 extern crate nom;
-use std::collections::{BTreeMap, VecDeque};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+};
 
+use crate::Error;
+use common::Solution;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{char, digit1, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult,
+    character::complete::space1,
+    sequence::{pair, preceded},
 };
+use parsing::{int_parser, number_list_parser, parse_lines, token, IResult};
 
 #[derive(Default, Debug, PartialEq, Eq)]
-struct Card {
-    id: u32,
-    wins: u32,
-    winning_numbers: Vec<u32>,
-    card_numbers: Vec<u32>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Card {
+    pub id: u32,
+    pub wins: u32,
+    pub winning_numbers: Vec<u32>,
+    pub card_numbers: Vec<u32>,
 }
 
-fn line_parser(i: &str) -> IResult<&str, Card> {
-    let (i, card) = separated_pair(tag("Card"), space1, digit1)(i)?;
-    let (i, _) = preceded(char(':'), space1)(i)?;
-    let (i, winning_numbers) = number_list_parser(i)?;
-    let (i, _) = preceded(tag(" |"), space1)(i)?;
-    let (i, card_numbers) = number_list_parser(i)?;
+/// The card as a line of the puzzle input, without the `wins` the solver
+/// counts.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+        for number in &self.winning_numbers {
+            write!(f, " {}", number)?;
+        }
+        write!(f, " |")?;
+        for number in &self.card_numbers {
+            write!(f, " {}", number)?;
+        }
+        Ok(())
+    }
+}
+
+fn line_parser(i: &str) -> IResult<'_, Card> {
+    let (i, id) = preceded(pair(token("Card"), space1), int_parser)(i)?;
+    let (i, winning_numbers) = preceded(token(":"), number_list_parser)(i)?;
+    let (i, card_numbers) = preceded(token("|"), number_list_parser)(i)?;
     Ok((
         i,
         Card {
-            id: card.1.parse().expect("valid game ID"),
+            id,
             winning_numbers,
             card_numbers,
             ..Default::default()
@@ -34,53 +52,48 @@ fn line_parser(i: &str) -> IResult<&str, Card> {
     ))
 }
 
-fn number_list_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    let (i, list) = separated_list1(space1, digit1)(i)?;
-    let numbers = list
-        .iter()
-        .map(|str| str.parse().expect("valid number"))
-        .collect();
-    Ok((i, numbers))
-}
+pub struct Part2;
 
-fn part1(input: &str) -> usize {
-    let cards: BTreeMap<u32, u32> = input
-        .split("\n")
-        .filter(|line| line.len() > 0)
-        .map(line_parser)
-        .map(|result| {
-            result
-                .as_ref()
-                .map(|(_, card): &(&str, Card)| {
-                    let wins = card
-                        .card_numbers
-                        .iter()
-                        .filter(|number| card.winning_numbers.contains(number))
-                        .count();
-                    (card.id, wins as u32)
-                })
-                .expect("valid line")
-        })
-        .collect();
+impl Solution for Part2 {
+    type Input<'a> = Vec<Card>;
+    type Answer = usize;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Card>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
 
-    let mut card_queue: VecDeque<u32> = VecDeque::new();
-    card_queue.extend(cards.keys());
-    let mut total_cards: usize = 0;
-    while !card_queue.is_empty() {
-        total_cards += 1;
-        let card_id = card_queue.pop_front().expect("should have elements");
-        let wins = cards[&card_id];
-        if wins > 0 {
-            card_queue.extend(card_id + 1..=card_id + wins);
+    fn solve(cards: &Vec<Card>, _params: &()) -> Result<usize, Error> {
+        let cards: BTreeMap<u32, u32> = cards
+            .iter()
+            .map(|card| {
+                let wins = card
+                    .card_numbers
+                    .iter()
+                    .filter(|number| card.winning_numbers.contains(number))
+                    .count();
+                (card.id, wins as u32)
+            })
+            .collect();
+
+        let mut card_queue: VecDeque<u32> = VecDeque::new();
+        card_queue.extend(cards.keys());
+        let mut total_cards: usize = 0;
+        while !card_queue.is_empty() {
+            total_cards += 1;
+            let card_id = card_queue.pop_front().expect("should have elements");
+            let wins = cards[&card_id];
+            if wins > 0 {
+                card_queue.extend(card_id + 1..=card_id + wins);
+            }
         }
+        Ok(total_cards)
     }
-    total_cards
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<usize, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -105,7 +118,21 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 30);
     }
+
+    #[test]
+    fn display_round_trip() {
+        let cards = Part2::parse(include_str!("input1_test.txt")).unwrap();
+        assert_eq!(
+            cards[0].to_string(),
+            "Card 1: 41 48 83 86 17 | 83 86 6 31 17 9 48 53"
+        );
+        for input in [include_str!("input1_test.txt"), include_str!("input1.txt")] {
+            let cards = Part2::parse(input).unwrap();
+            let text: String = cards.iter().map(|card| format!("{}\n", card)).collect();
+            assert_eq!(Part2::parse(&text).unwrap(), cards);
+        }
+    }
 }
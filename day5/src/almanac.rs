@@ -0,0 +1,211 @@
+extern crate nom;
+use std::fmt;
+
+use intervals::Interval;
+use nom::{
+    character::complete::{alpha1, newline, space1},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated, tuple},
+};
+use parsing::{int_parser, number_list_parser, token, IResult};
+
+/// A map between two categories, with the names of the categories as `S`:
+/// `&str` when borrowed from the puzzle text, `String` when owned.
+#[derive(Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Map<S> {
+    pub source: S,
+    pub destination: S,
+    pub ranges: Vec<MapRange>,
+}
+
+#[derive(Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Almanac<S> {
+    pub seeds: Vec<u32>,
+    pub maps: Vec<Map<S>>,
+}
+
+/// A line of a map, as in the almanac.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapRange {
+    pub source_start: u32,
+    pub destination_start: u32,
+    pub range_length: u32,
+}
+
+impl MapRange {
+    /// The numbers the range maps, which may end past the largest `u32`.
+    pub fn source(&self) -> Interval<u64> {
+        Interval::with_len(u64::from(self.source_start), u64::from(self.range_length))
+    }
+
+    /// Where the range sends `number`, if it maps it.
+    pub fn map(&self, number: u64) -> Option<u64> {
+        self.source()
+            .contains(number)
+            .then(|| number - u64::from(self.source_start) + u64::from(self.destination_start))
+    }
+}
+
+impl<S: AsRef<str>> Almanac<S> {
+    /// The almanac borrowing the names of its categories, to solve an owned one.
+    pub fn as_deref(&self) -> Almanac<&str> {
+        Almanac {
+            seeds: self.seeds.clone(),
+            maps: self
+                .maps
+                .iter()
+                .map(|map| Map {
+                    source: map.source.as_ref(),
+                    destination: map.destination.as_ref(),
+                    ranges: map.ranges.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Almanac<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {}", seed)?;
+        }
+        for map in &self.maps {
+            write!(f, "\n\n{}", map)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for Map<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-to-{} map:", self.source, self.destination)?;
+        for range in &self.ranges {
+            write!(f, "\n{}", range)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MapRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_start, self.source_start, self.range_length
+        )
+    }
+}
+
+pub(crate) fn almanac_parser(i: &str) -> IResult<'_, Almanac<&str>> {
+    let (i, (seeds, maps)) = separated_pair(
+        seeds_parser,
+        token("\n\n"),
+        separated_list1(token("\n\n"), map_parser),
+    )(i)?;
+    Ok((i, Almanac { seeds, maps }))
+}
+
+fn map_parser(i: &str) -> IResult<'_, Map<&str>> {
+    let (i, (source, destination)) = terminated(
+        separated_pair(alpha1, token("-to-"), alpha1),
+        token(" map:\n"),
+    )(i)?;
+    let (i, ranges) = separated_list1(newline, range_parser)(i)?;
+
+    Ok((
+        i,
+        Map {
+            source,
+            destination,
+            ranges,
+        },
+    ))
+}
+
+fn range_parser(i: &str) -> IResult<'_, MapRange> {
+    let (i, (destination_start, source_start, range_length)) = tuple((
+        int_parser,
+        preceded(space1, int_parser),
+        preceded(space1, int_parser),
+    ))(i)?;
+    Ok((
+        i,
+        MapRange {
+            source_start,
+            destination_start,
+            range_length,
+        },
+    ))
+}
+
+fn seeds_parser(i: &str) -> IResult<'_, Vec<u32>> {
+    preceded(token("seeds:"), number_list_parser)(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parsing::parse_all;
+
+    #[test]
+    fn parse_map() {
+        let result = map_parser(
+            "seed-to-soil map:
+50 98 2
+52 50 48
+",
+        )
+        .unwrap()
+        .1;
+        assert_eq!(
+            result,
+            Map {
+                source: "seed",
+                destination: "soil",
+                ranges: vec!(
+                    MapRange {
+                        destination_start: 50,
+                        source_start: 98,
+                        range_length: 2
+                    },
+                    MapRange {
+                        destination_start: 52,
+                        source_start: 50,
+                        range_length: 48
+                    },
+                )
+            }
+        );
+    }
+
+    #[test]
+    fn map_past_the_largest_number() {
+        let range = MapRange {
+            source_start: u32::MAX - 1,
+            destination_start: u32::MAX,
+            range_length: 3,
+        };
+        assert_eq!(range.source().len(), 3);
+        assert_eq!(range.map(u64::from(u32::MAX)), Some(1 << 32));
+        assert_eq!(range.map(1 << 32), Some((1 << 32) + 1));
+        assert_eq!(range.map(u64::from(u32::MAX - 2)), None);
+        assert_eq!(range.to_string(), "4294967295 4294967294 3");
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let example = include_str!("input1_test.txt");
+        let almanac = parse_all(example, almanac_parser).unwrap();
+        assert_eq!(almanac.to_string(), example.trim_end());
+        let input = include_str!("input1.txt");
+        let almanac = parse_all(input, almanac_parser).unwrap();
+        assert_eq!(
+            parse_all(&almanac.to_string(), almanac_parser).unwrap(),
+            almanac
+        );
+    }
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let input = common::load_input!().expect("readable input");
+    let output = day5::part1::part1(&input).expect("found result");
+    dbg!(output);
+}
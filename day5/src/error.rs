@@ -0,0 +1,14 @@
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("{0} seed range numbers, a start without a length")]
+    OddSeedRanges(usize),
+    #[error("no seeds to plant")]
+    NoSeeds,
+    #[error("location {0} does not fit in 32 bits")]
+    LocationOverflow(u64),
+}
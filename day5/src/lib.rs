@@ -0,0 +1,6 @@
+pub mod almanac;
+mod error;
+pub mod part1;
+pub mod part2;
+
+pub use error::Error;
@@ -1,118 +1,71 @@
-extern crate nom;
-use rayon::prelude::*;
-use std::ops::Range;
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, char, digit1, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, terminated},
-    IResult,
+use crate::{
+    almanac::{almanac_parser, Almanac, Map},
+    Error,
 };
-
-#[derive(Default, Debug, PartialEq, Eq)]
-struct Map<'a> {
-    source: &'a str,
-    destination: &'a str,
-    ranges: Vec<MapRange>,
-}
-
-#[derive(Default, Debug, PartialEq, Eq)]
-struct MapRange {
-    source_start: u32,
-    destination_start: u32,
-    range_length: u32,
-}
-
-fn map_parser(i: &str) -> IResult<&str, Map> {
-    let (i, (source, destination)) =
-        terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map:\n"))(i)?;
-    let (i, range_lists) = separated_list1(char('\n'), number_list_parser)(i)?;
-    let ranges = range_lists
-        .iter()
-        .map(|list| {
-            assert_eq!(list.len(), 3);
-            MapRange {
-                destination_start: list[0],
-                source_start: list[1],
-                range_length: list[2],
-            }
-        })
-        .collect();
-
-    Ok((
-        i,
-        Map {
-            source,
-            destination,
-            ranges,
-        },
-    ))
-}
-
-fn seeds_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    preceded(tag("seeds: "), number_list_parser)(i)
-}
-
-fn number_list_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    let (i, list) = separated_list1(space1, digit1)(i)?;
-    let numbers = list
-        .iter()
-        .map(|str| str.parse().expect("valid number"))
-        .collect();
-    Ok((i, numbers))
+use common::Solution;
+use intervals::{Interval, IntervalSet};
+use log::{debug, info};
+use parsing::parse_all;
+
+/// The numbers the map sends all of `numbers` to, range by range.
+fn map_numbers(numbers: &IntervalSet<u64>, map: &Map<&str>) -> IntervalSet<u64> {
+    let mut unmapped = numbers.clone();
+    let mut mapped = IntervalSet::new();
+    for range in &map.ranges {
+        let source = range.source().into();
+        let (from, to) = (
+            u64::from(range.source_start),
+            u64::from(range.destination_start),
+        );
+        mapped.extend(
+            unmapped
+                .intersection(&source)
+                .iter()
+                .map(|numbers| numbers.translate(from, to)),
+        );
+        unmapped = unmapped.difference(&source);
+    }
+    // numbers outside of every range keep their number
+    mapped.union(&unmapped)
 }
 
-fn part1(input: &str) -> u32 {
-    let parts: Vec<&str> = input.split("\n\n").collect();
+pub struct Part2;
 
-    let (_, seed_input) = seeds_parser(parts[0]).expect("valid list of seeds");
-    assert_eq!(seed_input.len() % 2, 0, "even number of seed range numbers");
-    let seed_ranges: Vec<Range<u32>> = seed_input
-        .chunks_exact(2)
-        .inspect(|w| println!("a: {}, b: {}", w[0], w[1]))
-        .map(|w| w[0]..w[0] + w[1])
-        .collect();
+impl Solution for Part2 {
+    type Input<'a> = Almanac<&'a str>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
 
-    println!("Seed range count: {}", seed_ranges.len());
-
-    let maps: Vec<Map> = parts[1..]
-        .iter()
-        .map(|part| map_parser(*part).expect("valid alamanac map").1)
-        .collect();
+    fn parse(input: &str) -> Result<Almanac<&str>, Error> {
+        Ok(parse_all(input, almanac_parser)?)
+    }
 
-    *seed_ranges
-        .par_iter()
-        .map(|range| {
-            range
-                .to_owned()
-                .map(|number| {
-                    let mut result = number;
-                    for map in maps.iter() {
-                        for range in map.ranges.iter() {
-                            if result >= range.source_start
-                                && result < range.source_start + range.range_length
-                            {
-                                result = result - range.source_start + range.destination_start;
-                                break;
-                            }
-                        }
-                    }
-                    result
-                })
-                .min()
-                .expect("should have a minimum height in thread")
-        })
-        .collect::<Vec<u32>>() // this makes it 30 seconds faster - why?
-        .iter()
-        .min()
-        .expect("should have a minimum height")
+    fn solve(almanac: &Almanac<&str>, _params: &()) -> Result<u32, Error> {
+        let seed_input = &almanac.seeds;
+        if !seed_input.len().is_multiple_of(2) {
+            return Err(Error::OddSeedRanges(seed_input.len()));
+        }
+        let seeds: IntervalSet<u64> = seed_input
+            .chunks_exact(2)
+            .inspect(|w| debug!("{} seeds from {}", w[1], w[0]))
+            .map(|w| Interval::with_len(u64::from(w[0]), u64::from(w[1])))
+            .collect();
+
+        info!("{} seed ranges", seeds.iter().len());
+
+        let locations = almanac.maps.iter().fold(seeds, |numbers, map| {
+            let mapped = map_numbers(&numbers, map);
+            debug!("{} ranges of {}", mapped.iter().len(), map.destination);
+            mapped
+        });
+        let lowest = locations.first().ok_or(Error::NoSeeds)?;
+        u32::try_from(lowest).map_err(|_| Error::LocationOverflow(lowest))
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u32, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -120,39 +73,23 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_map() {
-        let result = map_parser(
-            "seed-to-soil map:
-50 98 2
-52 50 48
-",
-        )
-        .unwrap()
-        .1;
-        assert_eq!(
-            result,
-            Map {
-                source: "seed",
-                destination: "soil",
-                ranges: vec!(
-                    MapRange {
-                        destination_start: 50,
-                        source_start: 98,
-                        range_length: 2
-                    },
-                    MapRange {
-                        destination_start: 52,
-                        source_start: 50,
-                        range_length: 48
-                    },
-                )
-            }
-        );
+    fn it_works() {
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
+        assert_eq!(result, 46);
     }
 
     #[test]
-    fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
-        assert_eq!(result, 46);
+    fn bad_seed_ranges() {
+        let example = include_str!("input1_test.txt");
+        let odd = example.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+        assert!(matches!(part2(&odd), Err(Error::OddSeedRanges(3))));
+        let empty = example.replacen("seeds: 79 14 55 13", "seeds: 79 0 55 0", 1);
+        assert!(matches!(part2(&empty), Err(Error::NoSeeds)));
+        // seed 1 is sent one past the largest location
+        let beyond = format!("seeds: 1 1\n\nseed-to-location map:\n{} 0 2\n", u32::MAX);
+        assert!(matches!(
+            part2(&beyond),
+            Err(Error::LocationOverflow(4294967296))
+        ));
     }
 }
@@ -1,57 +1,72 @@
 extern crate nom;
 
+use crate::Error;
+use common::{math::Sqrt, Solution};
+use log::debug;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{digit1, space1},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult,
+    character::complete::newline,
+    sequence::{preceded, separated_pair},
 };
+use parsing::{number_list_parser, parse_all, token, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    dbg!(i);
-    preceded(alt((tag("Time:"), tag("Distance:"))), number_list_parser)(i)
+fn input_parser(i: &str) -> IResult<'_, Vec<Vec<u32>>> {
+    let (i, (times, distances)) =
+        separated_pair(line_parser("Time:"), newline, line_parser("Distance:"))(i)?;
+    Ok((i, vec![times, distances]))
 }
 
-fn number_list_parser(i: &str) -> IResult<&str, Vec<u32>> {
-    let (i, list) = preceded(space1, separated_list1(space1, digit1))(i)?;
-    let numbers = list
-        .iter()
-        .map(|str| str.parse().expect("valid number"))
-        .collect();
-    Ok((i, numbers))
+fn line_parser<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<'a, Vec<u32>> {
+    preceded(token(name), number_list_parser)
 }
 
-fn part1(input: &str) -> u32 {
-    let parts: Vec<Vec<u32>> = input
-        .split("\n")
-        .filter(|l| l.len() > 0)
-        .map(line_parser)
-        .map(|l| l.expect("valid input line").1)
-        .collect();
-    assert_eq!(parts.len(), 2);
+/// Number of whole milliseconds to hold the button for to travel further than
+/// `record` in a race of `time`. Holding it for `r` wins if
+/// `r * (time - r) > record`, which is `(2r - time)^2 < time^2 - 4 * record`.
+fn ways_to_beat(time: u64, record: u64) -> u64 {
+    let (time, record) = (u128::from(time), u128::from(record));
+    let Some(discriminant) = (time * time).checked_sub(4 * record).filter(|&d| d > 0) else {
+        return 0;
+    };
+    // the largest |2r - time| that wins, with the parity of time
+    let reach = discriminant.sqrt_ceil() - 1;
+    let ways = if reach % 2 == time % 2 {
+        reach + 1
+    } else {
+        reach
+    };
+    ways as u64
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Vec<Vec<u32>>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
 
-    parts[0]
-        .iter()
-        .zip(parts[1].iter())
-        .map(|(time, best_distance)| {
-            let mut ways_to_beat_record: u32 = 0;
-            for r in 1..*time {
-                let distance = r * (time - r);
-                if distance > *best_distance {
-                    ways_to_beat_record += 1;
-                }
-            }
-            dbg!(ways_to_beat_record)
-        })
-        .product()
+    fn parse(input: &str) -> Result<Vec<Vec<u32>>, Error> {
+        Ok(parse_all(input, input_parser)?)
+    }
+
+    fn solve(parts: &Vec<Vec<u32>>, _params: &()) -> Result<u32, Error> {
+        Ok(parts[0]
+            .iter()
+            .zip(parts[1].iter())
+            .map(|(time, best_distance)| {
+                let ways_to_beat_record = ways_to_beat(*time as u64, *best_distance as u64) as u32;
+                debug!(
+                    "{} ways to beat {} in {}",
+                    ways_to_beat_record, best_distance, time
+                );
+                ways_to_beat_record
+            })
+            .product())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -60,7 +75,7 @@ mod tests {
 
     #[test]
     fn parse_line() {
-        let result = line_parser("Time:        46     80     78     66")
+        let result = line_parser("Time:")("Time:        46     80     78     66")
             .unwrap()
             .1;
         assert_eq!(result, vec!(46, 80, 78, 66),);
@@ -68,7 +83,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 288);
     }
 }
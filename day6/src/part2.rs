@@ -1,53 +1,75 @@
 extern crate nom;
 
+use crate::Error;
+use common::{math::Sqrt, Solution};
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{digit1, space1},
+    character::complete::{digit1, newline, space0, space1},
+    combinator::map_res,
+    error::context,
     multi::separated_list1,
-    sequence::preceded,
-    IResult,
+    sequence::{preceded, separated_pair},
 };
+use parsing::{parse_all, token, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, u64> {
-    dbg!(i);
-    preceded(
-        alt((tag("Time:"), tag("Distance:"))),
-        number_with_spaces_parser,
+fn input_parser(i: &str) -> IResult<'_, Vec<u64>> {
+    let (i, (time, distance)) =
+        separated_pair(line_parser("Time:"), newline, line_parser("Distance:"))(i)?;
+    Ok((i, vec![time, distance]))
+}
+
+fn line_parser<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<'a, u64> {
+    preceded(token(name), number_with_spaces_parser)
+}
+
+fn number_with_spaces_parser(i: &str) -> IResult<'_, u64> {
+    context(
+        "number",
+        map_res(
+            preceded(space0, separated_list1(space1, digit1)),
+            |digits: Vec<&str>| digits.concat().parse(),
+        ),
     )(i)
 }
 
-fn number_with_spaces_parser(i: &str) -> IResult<&str, u64> {
-    let (i, list) = preceded(space1, separated_list1(space1, digit1))(i)?;
-    let numbers = list.join("").parse().expect("valid number");
-    Ok((i, numbers))
+/// Number of whole milliseconds to hold the button for to travel further than
+/// `record` in a race of `time`. Holding it for `r` wins if
+/// `r * (time - r) > record`, which is `(2r - time)^2 < time^2 - 4 * record`.
+fn ways_to_beat(time: u64, record: u64) -> u64 {
+    let (time, record) = (u128::from(time), u128::from(record));
+    let Some(discriminant) = (time * time).checked_sub(4 * record).filter(|&d| d > 0) else {
+        return 0;
+    };
+    // the largest |2r - time| that wins, with the parity of time
+    let reach = discriminant.sqrt_ceil() - 1;
+    let ways = if reach % 2 == time % 2 {
+        reach + 1
+    } else {
+        reach
+    };
+    ways as u64
 }
 
-fn part1(input: &str) -> u64 {
-    let parts: Vec<u64> = input
-        .split("\n")
-        .filter(|l| l.len() > 0)
-        .map(line_parser)
-        .map(|l| l.expect("valid input line").1)
-        .collect();
-    assert_eq!(parts.len(), 2);
+pub struct Part2;
 
-    let time = parts[0];
-    let best_distance = parts[1];
-    let mut ways_to_beat_record: u64 = 0;
-    for r in 1..time {
-        let distance = r * (time - r);
-        if distance > best_distance {
-            ways_to_beat_record += 1;
-        }
+impl Solution for Part2 {
+    type Input<'a> = Vec<u64>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<u64>, Error> {
+        Ok(parse_all(input, input_parser)?)
+    }
+
+    fn solve(parts: &Vec<u64>, _params: &()) -> Result<u64, Error> {
+        let time = parts[0];
+        let best_distance = parts[1];
+        Ok(ways_to_beat(time, best_distance))
     }
-    dbg!(ways_to_beat_record)
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -56,7 +78,7 @@ mod tests {
 
     #[test]
     fn parse_line() {
-        let result = line_parser("Time:        46     80     78     66")
+        let result = line_parser("Time:")("Time:        46     80     78     66")
             .unwrap()
             .1;
         assert_eq!(result, 46807866);
@@ -64,7 +86,20 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 71503);
     }
+
+    #[test]
+    fn solve_the_quadratic() {
+        for time in 0..40 {
+            for record in 0..time * time / 4 + 2 {
+                let tried = (1..time).filter(|r| r * (time - r) > record).count() as u64;
+                assert_eq!(ways_to_beat(time, record), tried, "{} {}", time, record);
+            }
+        }
+        // distances near the limit of 64 bits
+        assert_eq!(ways_to_beat(u32::MAX as u64, 0), u32::MAX as u64 - 1);
+        assert_eq!(ways_to_beat(1 << 33, u64::MAX), 1);
+    }
 }
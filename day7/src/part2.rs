@@ -2,24 +2,28 @@ extern crate nom;
 
 use std::{cmp::Ordering, collections::HashMap};
 
+use crate::Error;
+use common::Solution;
+use log::{trace, warn};
 use nom::{
-    character::complete::{anychar, space1, u32},
+    character::complete::{one_of, space1},
+    error::context,
     multi::count,
     sequence::separated_pair,
-    IResult,
 };
+use parsing::{int_parser, parse_lines, IResult};
 
 // jokers are now weakest cards
-static CARDS: &'static [char] = &[
+static CARDS: &[char] = &[
     'J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K', 'A',
 ];
 
-fn line_parser(i: &str) -> IResult<&str, (Vec<char>, u32)> {
-    separated_pair(hand_parser, space1, u32)(i)
+fn line_parser(i: &str) -> IResult<'_, (Vec<char>, u32)> {
+    separated_pair(hand_parser, space1, int_parser)(i)
 }
 
-fn hand_parser(i: &str) -> IResult<&str, Vec<char>> {
-    count(anychar, 5)(i)
+fn hand_parser(i: &str) -> IResult<'_, Vec<char>> {
+    count(context("card", one_of("23456789TJQKA")), 5)(i)
 }
 
 fn get_hand_type(cards: &Vec<char>) -> u32 {
@@ -33,10 +37,10 @@ fn get_hand_type(cards: &Vec<char>) -> u32 {
     let jokers = char_amounts.remove(&'J').unwrap_or(0);
     let amounts: Vec<u32> = char_amounts.values().cloned().collect();
 
-    let has_4 = amounts.iter().any(|a| *a == 4);
-    let has_3 = amounts.iter().any(|a| *a == 3);
-    let has_2 = amounts.iter().any(|a| *a == 2);
-    let has_1 = amounts.iter().any(|a| *a == 1);
+    let has_4 = amounts.contains(&4);
+    let has_3 = amounts.contains(&3);
+    let has_2 = amounts.contains(&2);
+    let has_1 = amounts.contains(&1);
     let pairs = amounts.iter().filter(|a| **a == 2).count();
 
     if amounts.len() < 2 {
@@ -53,62 +57,70 @@ fn get_hand_type(cards: &Vec<char>) -> u32 {
         return 1; // one pair
     }
 
-    return 0; // high card
+    0 // high card
 }
 
-fn part1(input: &str) -> u32 {
-    let mut hands: Vec<(Vec<char>, u32, u32)> = input
-        .split("\n")
-        .filter(|l| l.len() > 0)
-        .map(line_parser)
-        .map(|l| l.expect("valid input line").1)
-        .map(|hand| {
-            let hand_type = get_hand_type(&hand.0);
-            (hand.0, hand.1, hand_type)
-        })
-        .collect();
-
-    hands.sort_by(|a, b| {
-        if a.2 != b.2 {
-            return a.2.cmp(&b.2);
-        }
-
-        for i in 0..b.0.len() {
-            let value_a = CARDS.iter().position(|c| *c == a.0[i]).expect("valid card");
-            let value_b = CARDS.iter().position(|c| *c == b.0[i]).expect("valid card");
-            if value_a != value_b {
-                return value_a.cmp(&value_b);
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Vec<(Vec<char>, u32)>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<(Vec<char>, u32)>, Error> {
+        Ok(parse_lines(input, line_parser)?)
+    }
+
+    fn solve(hands: &Vec<(Vec<char>, u32)>, _params: &()) -> Result<u32, Error> {
+        let mut hands: Vec<(Vec<char>, u32, u32)> = hands
+            .iter()
+            .map(|hand| {
+                let hand_type = get_hand_type(&hand.0);
+                (hand.0.clone(), hand.1, hand_type)
+            })
+            .collect();
+
+        hands.sort_by(|a, b| {
+            if a.2 != b.2 {
+                return a.2.cmp(&b.2);
             }
-        }
-
-        eprintln!(
-            "Hands are equal?! {} {}",
-            a.0.iter().collect::<String>(),
-            b.0.iter().collect::<String>()
-        );
-        return Ordering::Equal;
-    });
-
-    hands
-        .iter()
-        .enumerate()
-        .inspect(|(i, (cards, bet, hand_type))| {
-            println!(
-                "Hand {}: Cards {} Bet {} Hand Type {}",
-                i,
-                cards.iter().collect::<String>(),
-                bet,
-                hand_type
-            )
-        })
-        .map(|(i, (_cards, bet, _hand_type))| (i as u32 + 1) * bet)
-        .sum()
+
+            for i in 0..b.0.len() {
+                let value_a = CARDS.iter().position(|c| *c == a.0[i]).expect("valid card");
+                let value_b = CARDS.iter().position(|c| *c == b.0[i]).expect("valid card");
+                if value_a != value_b {
+                    return value_a.cmp(&value_b);
+                }
+            }
+
+            warn!(
+                "hands {} and {} are equal",
+                a.0.iter().collect::<String>(),
+                b.0.iter().collect::<String>()
+            );
+            Ordering::Equal
+        });
+
+        Ok(hands
+            .iter()
+            .enumerate()
+            .inspect(|(i, (cards, bet, hand_type))| {
+                trace!(
+                    "hand {}: cards {} bet {} hand type {}",
+                    i,
+                    cards.iter().collect::<String>(),
+                    bet,
+                    hand_type
+                )
+            })
+            .map(|(i, (_cards, bet, _hand_type))| (i as u32 + 1) * bet)
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<u32, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -131,7 +143,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 5905);
     }
 }
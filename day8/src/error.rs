@@ -0,0 +1,18 @@
+use parsing::ParseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("invalid direction {0:?}")]
+    InvalidDirection(char),
+    #[error("no node named {0}")]
+    UnknownNode(String),
+    #[error("the ghosts never arrive together")]
+    NeverTogether,
+    #[error("no node ending in A for a ghost to start at")]
+    NoStartNodes,
+    #[error("the ghosts arrive together too late: {0}")]
+    Overflow(#[from] common::math::Overflow),
+}
@@ -2,70 +2,99 @@ extern crate nom;
 
 use std::collections::HashMap;
 
+use crate::Error;
+use common::Solution;
+use log::trace;
 use nom::{
-    bytes::complete::tag,
-    character::complete::alpha1,
+    character::complete::{alpha1, newline, one_of},
+    multi::{many1, separated_list1},
     sequence::{delimited, separated_pair},
-    IResult,
 };
+use parsing::{parse_all, token, IResult};
 
-fn line_parser(i: &str) -> IResult<&str, (&str, (&str, &str))> {
+#[derive(Debug, PartialEq, Eq)]
+pub struct Network<'a> {
+    pub steps: Vec<char>,
+    pub nodes: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+fn network_parser(i: &str) -> IResult<'_, Network<'_>> {
+    let (i, (steps, nodes)) = separated_pair(
+        many1(one_of("LR")),
+        token("\n\n"),
+        separated_list1(newline, line_parser),
+    )(i)?;
+    Ok((
+        i,
+        Network {
+            steps,
+            nodes: nodes.into_iter().collect(),
+        },
+    ))
+}
+
+fn line_parser(i: &str) -> IResult<'_, (&str, (&str, &str))> {
     separated_pair(
         alpha1,
-        tag(" = "),
+        token(" = "),
         delimited(
-            tag("("),
-            separated_pair(alpha1, tag(", "), alpha1),
-            tag(")"),
+            token("("),
+            separated_pair(alpha1, token(", "), alpha1),
+            token(")"),
         ),
     )(i)
 }
 
-fn part1(input: &str) -> u32 {
-    let parts: Vec<&str> = input.split("\n\n").collect();
-    assert_eq!(parts.len(), 2);
-    let steps: Vec<char> = parts[0].chars().collect();
-
-    let nodes: HashMap<&str, (&str, &str)> = parts[1]
-        .split("\n")
-        .filter(|l| l.len() > 0)
-        .map(line_parser)
-        .map(|l| l.expect("valid input line").1)
-        .collect();
-    assert_ne!(nodes.get("AAA"), None);
-    assert_ne!(nodes.get("ZZZ"), None);
-
-    let mut current_node: &str = "AAA";
-    let mut step_index: usize = 0;
-    let mut step_counter: u32 = 0;
-    while current_node != "ZZZ" {
-        let direction = steps[step_index];
-        step_index += 1;
-        if step_index >= steps.len() {
-            step_index = 0;
+pub struct Part1;
+
+impl Solution for Part1 {
+    type Input<'a> = Network<'a>;
+    type Answer = u32;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Network<'_>, Error> {
+        Ok(parse_all(input, network_parser)?)
+    }
+
+    fn solve(network: &Network, _params: &()) -> Result<u32, Error> {
+        let Network { steps, nodes } = network;
+        if let Some(missing) = ["AAA", "ZZZ"]
+            .iter()
+            .find(|node| !nodes.contains_key(*node))
+        {
+            return Err(Error::UnknownNode(missing.to_string()));
         }
 
-        let next_nodes = nodes.get(current_node).expect("node found");
-        current_node = match direction {
-            'L' => next_nodes.0,
-            'R' => next_nodes.1,
-            _ => {
-                eprintln!("Invalid direction {}", direction);
-                "INVALID"
+        let mut current_node: &str = "AAA";
+        let mut step_index: usize = 0;
+        let mut step_counter: u32 = 0;
+        while current_node != "ZZZ" {
+            let direction = steps[step_index];
+            step_index += 1;
+            if step_index >= steps.len() {
+                step_index = 0;
             }
-        };
 
-        step_counter += 1;
-        // println!("Visiting {}, went {}", current_node, direction);
-    }
+            let next_nodes = nodes
+                .get(current_node)
+                .ok_or_else(|| Error::UnknownNode(current_node.to_string()))?;
+            current_node = match direction {
+                'L' => next_nodes.0,
+                'R' => next_nodes.1,
+                direction => return Err(Error::InvalidDirection(direction)),
+            };
 
-    step_counter
+            step_counter += 1;
+            trace!("went {} to {}", direction, current_node);
+        }
+
+        Ok(step_counter)
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    Part1::run(input, &())
 }
 
 #[cfg(test)]
@@ -80,9 +109,9 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input1_test.txt"));
+        let result = part1(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 2);
-        let result = part1(include_str!("input2_test.txt"));
+        let result = part1(include_str!("input2_test.txt")).expect("run without errors");
         assert_eq!(result, 6);
     }
 }
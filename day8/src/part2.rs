@@ -1,70 +1,234 @@
 extern crate nom;
 
-use num::integer::lcm;
 use std::collections::BTreeMap;
 
+use crate::Error;
+use common::{
+    cycle::History,
+    diagnostics,
+    math::{self, Congruence, Periodic},
+    Solution,
+};
+use graph::{Graph, NodeId};
+use log::debug;
 use nom::{
-    bytes::complete::tag,
-    character::complete::alphanumeric1,
+    character::complete::{alphanumeric1, newline, one_of},
+    multi::{many1, separated_list1},
     sequence::{delimited, separated_pair},
-    IResult,
 };
+use parsing::{parse_all, token, IResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Network<'a> {
+    pub steps: Vec<char>,
+    pub nodes: BTreeMap<&'a str, (&'a str, &'a str)>,
+}
 
-fn line_parser(i: &str) -> IResult<&str, (&str, (&str, &str))> {
+fn network_parser(i: &str) -> IResult<'_, Network<'_>> {
+    let (i, (steps, nodes)) = separated_pair(
+        many1(one_of("LR")),
+        token("\n\n"),
+        separated_list1(newline, line_parser),
+    )(i)?;
+    Ok((
+        i,
+        Network {
+            steps,
+            nodes: nodes.into_iter().collect(),
+        },
+    ))
+}
+
+fn line_parser(i: &str) -> IResult<'_, (&str, (&str, &str))> {
     separated_pair(
         alphanumeric1,
-        tag(" = "),
+        token(" = "),
         delimited(
-            tag("("),
-            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
-            tag(")"),
+            token("("),
+            separated_pair(alphanumeric1, token(", "), alphanumeric1),
+            token(")"),
         ),
     )(i)
 }
 
-fn part1(input: &str) -> u64 {
-    let parts: Vec<&str> = input.split("\n\n").collect();
-    assert_eq!(parts.len(), 2);
-    let steps: Vec<char> = parts[0].chars().collect();
-
-    let nodes: BTreeMap<&str, (&str, &str)> = parts[1]
-        .split("\n")
-        .filter(|l| l.len() > 0)
-        .map(line_parser)
-        .map(|l| l.expect("valid input line").1)
-        .collect();
-
-    let start_nodes: Vec<&str> = nodes.keys().filter(|k| k.ends_with("A")).cloned().collect();
-    let step_counts = start_nodes.iter().map(|start_node| {
-        let mut current_node: &str = start_node;
-        let mut step_counter: u32 = 0;
-
-        while !current_node.ends_with("Z") {
-            let direction = steps[step_counter as usize % steps.len()];
-
-            let next_nodes = nodes.get(current_node).expect("node found");
-            current_node = match direction {
-                'L' => next_nodes.0,
-                'R' => next_nodes.1,
-                _ => {
-                    eprintln!("Invalid direction {}", direction);
-                    "INVALID"
-                }
-            };
-
-            step_counter += 1;
+pub struct Part2;
+
+impl Solution for Part2 {
+    type Input<'a> = Network<'a>;
+    type Answer = u64;
+    type Params = ();
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Network<'_>, Error> {
+        Ok(parse_all(input, network_parser)?)
+    }
+
+    fn solve(network: &Network, _params: &()) -> Result<u64, Error> {
+        let Network { steps, nodes } = network;
+
+        // every node has an edge for each direction, weighed with it
+        let mut graph: Graph<&str, char> = Graph::directed();
+        for (&node, &(left, right)) in nodes {
+            graph.add_edge(node, left, 'L');
+            graph.add_edge(node, right, 'R');
         }
-        step_counter
-    }).inspect(|c| print!("{}, ", c));
 
-    // calculate lowest common multiple of all step counts
-    step_counts.fold(1, |acc, count| lcm(acc, count as u64))
+        let mut start_nodes: Vec<_> = graph
+            .ids()
+            .filter(|&id| graph.node(id).ends_with("A"))
+            .collect();
+        start_nodes.sort_by_key(|&id| graph.node(id));
+        let ghosts = start_nodes
+            .into_iter()
+            .map(|start| ghost_arrivals(&graph, steps, start))
+            .collect::<Result<Vec<Arrivals>, Error>>()?;
+        diagnostics::report(
+            "ghost_steps",
+            ghosts
+                .iter()
+                .filter_map(Arrivals::first)
+                .collect::<Vec<_>>(),
+        );
+        diagnostics::report(
+            "ghost_periods",
+            ghosts.iter().map(|g| g.period).collect::<Vec<_>>(),
+        );
+
+        first_together(&ghosts)?.ok_or(Error::NeverTogether)
+    }
+}
+
+/// The node the ghost at `node` goes to next in the direction.
+fn next_node(graph: &Graph<&str, char>, node: NodeId, direction: char) -> Result<NodeId, Error> {
+    // nodes only named as the next node of another have no edges
+    let edges = graph.edges(node);
+    if edges.is_empty() {
+        return Err(Error::UnknownNode(graph.node(node).to_string()));
+    }
+    edges
+        .iter()
+        .find(|&&(_, edge_direction)| edge_direction == direction)
+        .map(|&(next, _)| next)
+        .ok_or(Error::InvalidDirection(direction))
+}
+
+/// The steps after which a ghost is at a Z node.
+#[derive(Debug)]
+struct Arrivals {
+    /// Arrivals before the ghost starts going round in circles.
+    once: Vec<u64>,
+    /// Arrivals on the circle, repeating with it.
+    repeated: Vec<Periodic>,
+    /// Steps once round the circle.
+    period: u64,
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part1(input);
-    dbg!(output);
+impl Arrivals {
+    fn first(&self) -> Option<u64> {
+        let repeated = self.repeated.iter().map(|p| p.offset);
+        self.once.iter().copied().chain(repeated).min()
+    }
+
+    fn at(&self, step: u64) -> bool {
+        self.once.contains(&step)
+            || self
+                .repeated
+                .iter()
+                .any(|p| step >= p.offset && (step - p.offset).is_multiple_of(p.period))
+    }
+}
+
+/// Walk the ghost from `start` until it is at a node at the same point of
+/// the directions as before, from where it goes round the same circle
+/// forever.
+fn ghost_arrivals(
+    graph: &Graph<&str, char>,
+    steps: &[char],
+    start: NodeId,
+) -> Result<Arrivals, Error> {
+    let mut history = History::new();
+    let mut arrivals = vec![];
+    let mut current_node = start;
+    let mut step_counter: u64 = 0;
+
+    let cycle = loop {
+        let position = step_counter as usize % steps.len();
+        if let Some(cycle) = history.push((current_node, position)) {
+            break cycle;
+        }
+        if graph.node(current_node).ends_with("Z") {
+            debug!("a ghost arrives after {} steps", step_counter);
+            arrivals.push(step_counter);
+        }
+        current_node = next_node(graph, current_node, steps[position])?;
+        step_counter += 1;
+    };
+
+    let (once, repeated) = arrivals.into_iter().partition(|&step| step < cycle.start);
+    Ok(Arrivals {
+        once,
+        repeated: repeated
+            .into_iter()
+            .map(|offset| Periodic::new(offset, cycle.length))
+            .collect::<Vec<_>>(),
+        period: cycle.length,
+    })
+}
+
+/// The first step all ghosts are at a Z node at once.
+fn first_together(ghosts: &[Arrivals]) -> Result<Option<u64>, Error> {
+    if ghosts.is_empty() {
+        return Err(Error::NoStartNodes);
+    }
+    // all arrivals on circles come after the ones before
+    let before_circles = ghosts
+        .iter()
+        .flat_map(|g| g.once.iter().copied())
+        .filter(|&step| ghosts.iter().all(|g| g.at(step)))
+        .min();
+    if before_circles.is_some() {
+        return Ok(before_circles);
+    }
+
+    // a ghost first arriving after once round its circle, and only then, is
+    // at a Z node after every multiple of the circle, as on the puzzle input
+    let (single, several): (Vec<&Arrivals>, Vec<&Arrivals>) = ghosts
+        .iter()
+        .partition(|g| matches!(g.repeated[..], [p] if p.offset == p.period));
+    let circles = math::lcm_all(single.iter().map(|g| g.period))?;
+    let mut together = vec![Congruence::new(0, circles)];
+
+    // the other ghosts one at a time, keeping the steps at which a repeated
+    // arrival of each ghost so far comes together with the others
+    for ghost in several {
+        let mut merged = vec![];
+        for steps in &together {
+            for arrival in &ghost.repeated {
+                let arrival = Congruence::new(arrival.offset, arrival.period);
+                merged.extend(steps.combine(arrival)?);
+            }
+        }
+        merged.sort_unstable_by_key(|c| (c.residue, c.modulus));
+        merged.dedup();
+        together = merged;
+    }
+
+    // once every ghost is on its circle
+    let start = ghosts
+        .iter()
+        .flat_map(|g| g.repeated.iter().map(|p| p.offset))
+        .max()
+        .unwrap_or(0);
+    let mut first = None;
+    for steps in together {
+        let step = steps.first_from(start)?;
+        first = Some(first.map_or(step, |first: u64| first.min(step)));
+    }
+    Ok(first)
+}
+
+pub fn part2(input: &str) -> Result<u64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -79,7 +243,53 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part1(include_str!("input3_test.txt"));
+        let result = part2(include_str!("input3_test.txt")).expect("run without errors");
         assert_eq!(result, 6);
     }
+
+    #[test]
+    fn arrivals_with_offsets() {
+        // one ghost is at 11Z after 2, 4, 6, ... steps, the other at 22Z
+        // after 1, 4, 7, ..., so they are apart at the lcm of their first
+        // arrivals
+        let network = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n\
+            11C = (11Z, 11Z)\n22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22C, 22C)\n\
+            22C = (22Z, 22Z)";
+        assert_eq!(part2(network).expect("run without errors"), 4);
+
+        let apart = "L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\n\
+            22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22C, 22C)\n22C = (22Z, 22Z)";
+        assert!(matches!(part2(apart), Err(Error::NeverTogether)));
+
+        let no_ghosts = "L\n\n11B = (11Z, 11Z)\n11Z = (11B, 11B)";
+        assert!(matches!(part2(no_ghosts), Err(Error::NoStartNodes)));
+    }
+
+    #[test]
+    fn many_generated_ghosts() {
+        // every ghost arrives after each multiple of its cycle, which is
+        // shorter than the directions, so they meet at the lcm of the cycles
+        let generator = generate::find(8).expect("generator for day 8");
+        for seed in 0..3 {
+            let input = generator
+                .generate(seed, &[("ghosts", "7")])
+                .expect("valid parameters");
+            let network = Part2::parse(&input).expect("parse generated network");
+            let cycles = network
+                .nodes
+                .keys()
+                .filter(|node| node.ends_with('A'))
+                .map(|&start| {
+                    let mut node = start;
+                    let mut steps = 0;
+                    while steps == 0 || !node.ends_with('Z') {
+                        node = network.nodes[node].0;
+                        steps += 1;
+                    }
+                    steps
+                });
+            let expected = math::lcm_all(cycles).unwrap();
+            assert_eq!(part2(&input).expect("run without errors"), expected);
+        }
+    }
 }
@@ -1,63 +1,59 @@
 extern crate nom;
 
-use std::str::FromStr;
+use crate::Error;
+use common::Solution;
+use log::trace;
+use nom::{character::complete::newline, multi::separated_list1};
+use parsing::{number_list_parser, parse_all, IResult};
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, newline, space1},
-    combinator::{map_res, opt, recognize},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult,
-};
-
-fn input_parser(i: &str) -> IResult<&str, Vec<Vec<i64>>> {
+fn input_parser(i: &str) -> IResult<'_, Vec<Vec<i64>>> {
     separated_list1(newline, number_list_parser)(i)
 }
 
-fn number_list_parser(i: &str) -> IResult<&str, Vec<i64>> {
-    separated_list1(space1, int_parser)(i)
-}
+pub struct Part2;
 
-fn int_parser(i: &str) -> IResult<&str, i64> {
-    map_res(
-        recognize(preceded(opt(tag("-")), digit1)),
-        FromStr::from_str,
-    )(i)
-}
+impl Solution for Part2 {
+    type Input<'a> = Vec<Vec<i64>>;
+    type Answer = i64;
+    type Params = ();
+    type Error = Error;
 
-fn part2(input: &str) -> i64 {
-    let histories = input_parser(input).expect("valid input").1;
-    histories
-        .into_iter()
-        .map(|history| {
-            let mut first_numbers: Vec<i64> = vec![history[0]];
-            let mut prev_sequence = history;
-            loop {
-                let sequence: Vec<i64> = prev_sequence.windows(2).map(|w| w[1] - w[0]).collect();
-                let all_zeroes = sequence.iter().all(|n| *n == 0);
+    fn parse(input: &str) -> Result<Vec<Vec<i64>>, Error> {
+        Ok(parse_all(input, input_parser)?)
+    }
+
+    fn solve(histories: &Vec<Vec<i64>>, _params: &()) -> Result<i64, Error> {
+        Ok(histories
+            .iter()
+            .map(|history| {
+                let mut first_numbers: Vec<i64> = vec![history[0]];
+                let mut prev_sequence = history.clone();
+                loop {
+                    let sequence: Vec<i64> =
+                        prev_sequence.windows(2).map(|w| w[1] - w[0]).collect();
+                    let all_zeroes = sequence.iter().all(|n| *n == 0);
 
-                if all_zeroes {
-                    break;
-                } else {
-                    first_numbers.push(*sequence.first().expect("non-empty sequence"));
-                    prev_sequence = sequence;
+                    if all_zeroes {
+                        break;
+                    } else {
+                        first_numbers.push(*sequence.first().expect("non-empty sequence"));
+                        prev_sequence = sequence;
+                    }
                 }
-            }
 
-            let next_value = first_numbers
-                .iter()
-                .rev()
-                .fold(0, |acc, first_number| first_number - acc);
-            dbg!(next_value)
-        })
-        .sum()
+                let next_value = first_numbers
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, first_number| first_number - acc);
+                trace!("next value {}", next_value);
+                next_value
+            })
+            .sum())
+    }
 }
 
-fn main() {
-    let input = include_str!("./input1.txt");
-    let output = part2(input);
-    dbg!(output);
+pub fn part2(input: &str) -> Result<i64, Error> {
+    Part2::run(input, &())
 }
 
 #[cfg(test)]
@@ -77,7 +73,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let result = part2(include_str!("input1_test.txt"));
+        let result = part2(include_str!("input1_test.txt")).expect("run without errors");
         assert_eq!(result, 2);
     }
 }
@@ -0,0 +1,50 @@
+//! Calibration document: lines of letters with digits, some spelled out.
+
+use crate::{Generate, Rng};
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+common::params! {
+    pub struct Params {
+        lines: usize = 1000,
+        /// length a line grows to before it ends
+        length: usize = 40,
+        /// chance of a digit in place of a letter
+        digits: f64 = 0.1,
+        /// chance of a spelled out digit in place of a letter
+        spelled: f64 = 0.1,
+    }
+}
+
+pub struct Calibration;
+
+impl Generate for Calibration {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut text = String::new();
+        for _ in 0..params.lines {
+            let length = rng.range(1..=params.length.max(1) as i64) as usize;
+            let mut line = String::new();
+            while line.len() < length {
+                if rng.chance(params.digits) {
+                    line.push(char::from(b'1' + rng.below(9) as u8));
+                } else if rng.chance(params.spelled) {
+                    line += *rng.choose(&DIGIT_NAMES);
+                } else {
+                    line.push(char::from(b'a' + rng.below(26) as u8));
+                }
+            }
+            // every line needs a digit for the first part
+            if !line.bytes().any(|c| c.is_ascii_digit()) {
+                let at = rng.index(line.len() + 1);
+                line.insert(at, char::from(b'1' + rng.below(9) as u8));
+            }
+            text += &line;
+            text.push('\n');
+        }
+        text
+    }
+}
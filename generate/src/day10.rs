@@ -0,0 +1,84 @@
+//! Pipe maze: one loop of pipes through a field of junk pipes.
+
+use grid::{
+    Direction::{self, *},
+    Grid,
+};
+
+use crate::{
+    shapes::{outline, towards},
+    Generate, Rng,
+};
+
+const JUNK: &[char] = &['|', '-', 'L', 'J', '7', 'F', '.'];
+
+common::params! {
+    pub struct Params {
+        width: usize = 140,
+        height: usize = 140,
+        /// chance of a junk pipe on a tile off the loop
+        junk: f64 = 0.8,
+    }
+}
+
+pub struct PipeMaze;
+
+/// The pipe connecting two sides of a tile.
+fn pipe(a: Direction, b: Direction) -> char {
+    match (a.min(b), a.max(b)) {
+        (North, South) => '|',
+        (East, West) => '-',
+        (North, East) => 'L',
+        (North, West) => 'J',
+        (South, West) => '7',
+        (East, South) => 'F',
+        sides => unreachable!("no pipe connects {:?}", sides),
+    }
+}
+
+fn openings(tile: char) -> &'static [Direction] {
+    match tile {
+        '|' => &[North, South],
+        '-' => &[East, West],
+        'L' => &[North, East],
+        'J' => &[North, West],
+        '7' => &[South, West],
+        'F' => &[East, South],
+        _ => &[],
+    }
+}
+
+impl Generate for PipeMaze {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let (width, height) = (params.width.max(3), params.height.max(3));
+        let mut grid = Grid::new(width, height, '.');
+        for y in 0..height {
+            for x in 0..width {
+                if rng.chance(params.junk) {
+                    grid[(x, y)] = *rng.choose(JUNK);
+                }
+            }
+        }
+
+        // a blob two tiles thick, to leave room for tiles inside the loop
+        let points = outline(rng, width.div_ceil(4), height.div_ceil(4), 2);
+        let n = points.len();
+        for (i, &at) in points.iter().enumerate() {
+            let (before, after) = (points[(i + n - 1) % n], points[(i + 1) % n]);
+            grid[at] = pipe(towards(at, before), towards(at, after));
+        }
+
+        let start = points[rng.index(n)];
+        grid[start] = 'S';
+        // junk next to the start must not look like a third way out of it
+        let neighbours: Vec<_> = grid.neighbours(start).collect();
+        for (direction, next) in neighbours {
+            if !points.contains(&next) && openings(grid[next]).contains(&direction.opposite()) {
+                grid[next] = '.';
+            }
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,40 @@
+//! Telescope image: galaxies, with some rows and columns left empty.
+
+use grid::Grid;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        width: usize = 140,
+        height: usize = 140,
+        /// chance of a galaxy on a tile
+        galaxies: f64 = 0.025,
+        /// chance of a row or column without any galaxy
+        empty: f64 = 0.05,
+    }
+}
+
+pub struct Image;
+
+impl Generate for Image {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let empty_rows: Vec<bool> = (0..params.height)
+            .map(|_| rng.chance(params.empty))
+            .collect();
+        let empty_columns: Vec<bool> = (0..params.width)
+            .map(|_| rng.chance(params.empty))
+            .collect();
+        let mut grid = Grid::new(params.width, params.height, '.');
+        for y in 0..params.height {
+            for x in 0..params.width {
+                if !empty_rows[y] && !empty_columns[x] && rng.chance(params.galaxies) {
+                    grid[(x, y)] = '#';
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
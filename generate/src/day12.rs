@@ -0,0 +1,57 @@
+//! Spring records: rows of springs, some unknown, and their damaged groups.
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        rows: usize = 1000,
+        /// longest row
+        length: usize = 20,
+        /// largest group of damaged springs
+        group: usize = 6,
+        /// chance of a spring being unknown
+        unknown: f64 = 0.5,
+        /// chance of a row of nothing but unknown springs
+        all_unknown: f64 = 0.02,
+    }
+}
+
+pub struct Springs;
+
+impl Generate for Springs {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut text = String::new();
+        for _ in 0..params.rows {
+            let length = rng.range(1..=params.length.max(1) as i64) as usize;
+            let mut springs = vec!['.'; length];
+            let mut groups = vec![];
+            let mut at = rng.range(0..=2) as usize;
+            while at < length {
+                let size = rng.range(1..=params.group.max(1).min(length - at) as i64) as usize;
+                springs[at..at + size].fill('#');
+                groups.push(size.to_string());
+                at += size + rng.range(1..=3) as usize;
+            }
+            if groups.is_empty() {
+                springs[rng.index(length)] = '#';
+                groups.push("1".to_owned());
+            }
+
+            let all_unknown = rng.chance(params.all_unknown);
+            let springs: String = springs
+                .into_iter()
+                .map(|spring| {
+                    if all_unknown || rng.chance(params.unknown) {
+                        '?'
+                    } else {
+                        spring
+                    }
+                })
+                .collect();
+            text += &format!("{} {}\n", springs, groups.join(","));
+        }
+        text
+    }
+}
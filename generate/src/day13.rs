@@ -0,0 +1,140 @@
+//! Patterns of ash and rocks, each with one mirror and one smudged mirror.
+//!
+//! The rows on both sides of the two mirrors must match, which ties rows
+//! together into classes of equal rows. One pair of rows at the smudged
+//! mirror is left out and made to differ in a single tile.
+
+use crate::{Generate, Rng};
+
+type Pattern = Vec<Vec<bool>>;
+
+common::params! {
+    pub struct Params {
+        patterns: usize = 100,
+        /// largest width and height, at least 5
+        size: usize = 17,
+    }
+}
+
+pub struct Patterns;
+
+/// Pairs of rows mirrored at the line above row `line`.
+fn mirrored(line: usize, len: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..line).rev().zip(line..len)
+}
+
+fn mismatches(rows: &Pattern, line: usize) -> usize {
+    mirrored(line, rows.len())
+        .map(|(a, b)| rows[a].iter().zip(&rows[b]).filter(|(x, y)| x != y).count())
+        .sum()
+}
+
+fn transpose(rows: &Pattern) -> Pattern {
+    (0..rows[0].len())
+        .map(|x| rows.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// How many lines of both axes mirror perfectly, and how many with one smudge.
+fn reflections(rows: &Pattern) -> (usize, usize) {
+    let columns = transpose(rows);
+    let counts: Vec<usize> = (1..rows.len())
+        .map(|line| mismatches(rows, line))
+        .chain((1..columns.len()).map(|line| mismatches(&columns, line)))
+        .collect();
+    (
+        counts.iter().filter(|&&count| count == 0).count(),
+        counts.iter().filter(|&&count| count == 1).count(),
+    )
+}
+
+fn pattern(rng: &mut Rng, size: usize) -> Pattern {
+    let size = size.max(5) as i64;
+    loop {
+        let height = rng.range(5..=size) as usize;
+        let width = rng.range(5..=size) as usize;
+        let perfect = rng.range(1..=height as i64 - 1) as usize;
+        let smudged = rng.range(1..=height as i64 - 1) as usize;
+        if smudged == perfect {
+            continue;
+        }
+
+        let mut class: Vec<usize> = (0..height).collect();
+        let find = |class: &Vec<usize>, mut row: usize| {
+            while class[row] != row {
+                row = class[row];
+            }
+            row
+        };
+        let mut join = |a: usize, b: usize| {
+            let (a, b) = (find(&class, a), find(&class, b));
+            class[a] = b;
+        };
+        for (a, b) in mirrored(perfect, height) {
+            join(a, b);
+        }
+        let pairs: Vec<_> = mirrored(smudged, height).collect();
+        let (p, q) = pairs[rng.index(pairs.len())];
+        for &(a, b) in pairs.iter().filter(|&&pair| pair != (p, q)) {
+            join(a, b);
+        }
+        if find(&class, p) == find(&class, q) {
+            continue;
+        }
+
+        let mut rows: Vec<Vec<bool>> = (0..height)
+            .map(|_| (0..width).map(|_| rng.chance(0.5)).collect())
+            .collect();
+        let (p, q) = (find(&class, p), find(&class, q));
+        rows[q] = rows[p].clone();
+        let smudge = rng.index(width);
+        rows[q][smudge] = !rows[q][smudge];
+        let rows: Pattern = (0..height).map(|y| rows[find(&class, y)].clone()).collect();
+
+        if reflections(&rows) == (1, 1) {
+            return if rng.chance(0.5) {
+                transpose(&rows)
+            } else {
+                rows
+            };
+        }
+    }
+}
+
+impl Generate for Patterns {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        (0..params.patterns)
+            .map(|_| {
+                pattern(rng, params.size)
+                    .iter()
+                    .map(|row| {
+                        let mut line: String = row
+                            .iter()
+                            .map(|&rock| if rock { '#' } else { '.' })
+                            .collect();
+                        line.push('\n');
+                        line
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_mirror_and_one_smudge() {
+        let mut rng = Rng::new(13);
+        for _ in 0..50 {
+            let rows = pattern(&mut rng, 9);
+            assert!((5..=9).contains(&rows.len()));
+            assert_eq!(reflections(&rows), (1, 1));
+        }
+    }
+}
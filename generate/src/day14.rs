@@ -0,0 +1,36 @@
+//! Reflector dish platform: rounded and cube-shaped rocks.
+
+use grid::Grid;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        width: usize = 100,
+        height: usize = 100,
+        /// chance of a rounded rock on a tile
+        rounded: f64 = 0.2,
+        /// chance of a cube-shaped rock on a tile
+        cubes: f64 = 0.15,
+    }
+}
+
+pub struct Platform;
+
+impl Generate for Platform {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut grid = Grid::new(params.width, params.height, '.');
+        for y in 0..params.height {
+            for x in 0..params.width {
+                if rng.chance(params.rounded) {
+                    grid[(x, y)] = 'O';
+                } else if rng.chance(params.cubes) {
+                    grid[(x, y)] = '#';
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
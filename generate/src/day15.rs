@@ -0,0 +1,39 @@
+//! Initialization sequence: comma-separated steps on labelled lenses.
+
+use crate::{word, Generate, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+common::params! {
+    pub struct Params {
+        steps: usize = 4000,
+        /// number of different labels
+        labels: usize = 500,
+    }
+}
+
+pub struct InitSequence;
+
+impl Generate for InitSequence {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let labels: Vec<String> = (0..params.labels.max(1))
+            .map(|_| {
+                let len = rng.range(2..=6) as usize;
+                word(rng, LETTERS, len)
+            })
+            .collect();
+        let steps: Vec<String> = (0..params.steps.max(1))
+            .map(|_| {
+                let label = rng.choose(&labels);
+                if rng.chance(0.5) {
+                    format!("{}-", label)
+                } else {
+                    format!("{}={}", label, rng.range(1..=9))
+                }
+            })
+            .collect();
+        steps.join(",") + "\n"
+    }
+}
@@ -0,0 +1,34 @@
+//! Contraption: mirrors and splitters on an empty floor.
+
+use grid::Grid;
+
+use crate::{Generate, Rng};
+
+const DEVICES: &[char] = &['/', '\\', '|', '-'];
+
+common::params! {
+    pub struct Params {
+        width: usize = 110,
+        height: usize = 110,
+        /// chance of a mirror or splitter on a tile
+        devices: f64 = 0.1,
+    }
+}
+
+pub struct Contraption;
+
+impl Generate for Contraption {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut grid = Grid::new(params.width, params.height, '.');
+        for y in 0..params.height {
+            for x in 0..params.width {
+                if rng.chance(params.devices) {
+                    grid[(x, y)] = *rng.choose(DEVICES);
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,28 @@
+//! Heat loss map: a digit from 1 to 9 on every city block.
+
+use grid::Grid;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        width: usize = 141,
+        height: usize = 141,
+    }
+}
+
+pub struct HeatMap;
+
+impl Generate for HeatMap {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut grid = Grid::new(params.width.max(1), params.height.max(1), 0);
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                grid[(x, y)] = rng.range(1..=9);
+            }
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,93 @@
+//! Dig plan: a loop of trenches, with short lengths for the first part and
+//! long ones hidden in the color for the second.
+//!
+//! Both loops follow the outline of the same random blob, every unit of the
+//! outline just stands for a different distance.
+
+use grid::Direction::{self, *};
+
+use crate::{
+    shapes::{outline, towards},
+    Generate, Rng,
+};
+
+/// The longest trench a color can hold, five hex digits.
+const LONGEST: usize = 0xfffff;
+
+common::params! {
+    pub struct Params {
+        /// width of the blob whose outline the loop follows
+        width: usize = 16,
+        /// height of the blob whose outline the loop follows
+        height: usize = 16,
+        /// longest distance a unit of the outline stands for in the first part
+        step: usize = 6,
+    }
+}
+
+pub struct DigPlan;
+
+type Pos = (usize, usize);
+
+/// Where the lattice points end up when every unit between them stands for a
+/// random distance up to `longest`, and at least 2, so trenches on either side
+/// of a unit never lie right next to each other.
+fn offsets(rng: &mut Rng, units: usize, longest: usize) -> Vec<i64> {
+    let mut offsets = vec![0];
+    for _ in 0..units {
+        let last = *offsets.last().unwrap();
+        offsets.push(last + rng.range(2..=longest.max(2) as i64));
+    }
+    offsets
+}
+
+impl Generate for DigPlan {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let (width, height) = (params.width.max(1), params.height.max(1));
+        let points = outline(rng, width, height, 1);
+        let (units_x, units_y) = (2 * width - 1, 2 * height - 1);
+        let short = (
+            offsets(rng, units_x, params.step),
+            offsets(rng, units_y, params.step),
+        );
+        let longest = LONGEST / units_x.max(units_y);
+        let long = (
+            offsets(rng, units_x, longest),
+            offsets(rng, units_y, longest),
+        );
+
+        // straight runs along the outline, which starts on a corner
+        let mut runs: Vec<(Direction, Pos, Pos)> = vec![];
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            let direction = towards(from, to);
+            match runs.last_mut() {
+                Some((last, _, end)) if *last == direction => *end = to,
+                _ => runs.push((direction, from, to)),
+            }
+        }
+
+        let distance = |(xs, ys): &(Vec<i64>, Vec<i64>), from: Pos, to: Pos| {
+            (xs[to.0] - xs[from.0]).abs() + (ys[to.1] - ys[from.1]).abs()
+        };
+        let mut text = String::new();
+        for (direction, from, to) in runs {
+            let (letter, digit) = match direction {
+                East => ('R', 0),
+                South => ('D', 1),
+                West => ('L', 2),
+                North => ('U', 3),
+            };
+            text += &format!(
+                "{} {} (#{:05x}{})\n",
+                letter,
+                distance(&short, from, to),
+                distance(&long, from, to),
+                digit
+            );
+        }
+        text
+    }
+}
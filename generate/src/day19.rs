@@ -0,0 +1,84 @@
+//! Workflows sorting machine parts, and the parts.
+//!
+//! The workflows form a tree below `in`, so every part ends up accepted or
+//! rejected.
+
+use std::collections::VecDeque;
+
+use crate::{word, Generate, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const CATEGORIES: &[char] = &['x', 'm', 'a', 's'];
+
+common::params! {
+    pub struct Params {
+        workflows: usize = 550,
+        parts: usize = 200,
+        /// most rules in a workflow before the one that always matches
+        rules: usize = 4,
+    }
+}
+
+pub struct Workflows;
+
+impl Generate for Workflows {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut names: Vec<String> = vec![];
+        while names.len() + 1 < params.workflows {
+            let len = rng.range(2..=3) as usize;
+            let name = word(rng, LETTERS, len);
+            if name != "in" && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        let mut names = names.into_iter();
+
+        let mut workflows = vec![];
+        let mut queue = VecDeque::from(["in".to_owned()]);
+        while let Some(name) = queue.pop_front() {
+            let rules = rng.range(1..=params.rules.max(1) as i64) as usize;
+            let mut targets: Vec<String> = vec![];
+            for i in 0..=rules {
+                // keep the tree growing as long as there are names left
+                let grow = queue.is_empty() && i == 0 || rng.chance(0.4);
+                match if grow { names.next() } else { None } {
+                    Some(next) => {
+                        queue.push_back(next.clone());
+                        targets.push(next);
+                    }
+                    None if rng.chance(0.5) => targets.push("A".to_owned()),
+                    None => targets.push("R".to_owned()),
+                }
+            }
+            let fallback = targets.pop().unwrap();
+            let rules: Vec<String> = targets
+                .into_iter()
+                .map(|target| {
+                    format!(
+                        "{}{}{}:{}",
+                        rng.choose(CATEGORIES),
+                        if rng.chance(0.5) { '<' } else { '>' },
+                        rng.range(1..=4000),
+                        target
+                    )
+                })
+                .collect();
+            workflows.push(format!("{}{{{},{}}}", name, rules.join(","), fallback));
+        }
+        rng.shuffle(&mut workflows);
+
+        let mut text = workflows.join("\n") + "\n\n";
+        for _ in 0..params.parts {
+            text += &format!(
+                "{{x={},m={},a={},s={}}}\n",
+                rng.range(1..=4000),
+                rng.range(1..=4000),
+                rng.range(1..=4000),
+                rng.range(1..=4000)
+            );
+        }
+        text
+    }
+}
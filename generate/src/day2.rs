@@ -0,0 +1,37 @@
+//! Game log: the cubes drawn from the bag in every game.
+
+use crate::{sample, Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        games: usize = 100,
+        /// most draws in a game
+        draws: usize = 6,
+        /// most cubes of one color in a draw
+        cubes: usize = 20,
+    }
+}
+
+pub struct Games;
+
+impl Generate for Games {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut text = String::new();
+        for id in 1..=params.games {
+            let draws: Vec<String> = (0..rng.range(1..=params.draws.max(1) as i64))
+                .map(|_| {
+                    let colors = rng.range(1..=3) as usize;
+                    sample(rng, &["red", "green", "blue"], colors)
+                        .into_iter()
+                        .map(|color| format!("{} {}", rng.range(1..=params.cubes as i64), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            text += &format!("Game {}: {}\n", id, draws.join("; "));
+        }
+        text
+    }
+}
@@ -0,0 +1,74 @@
+//! Module network: flip-flops and conjunctions behind a broadcaster.
+//!
+//! Like the real inputs, the broadcaster feeds a few binary counters of
+//! flip-flops. A conjunction on every counter watches the bits set in a prime
+//! number, resets the counter when it gets there and pulses the conjunction
+//! in front of `rx`, which sees all counters reset in the same button press
+//! after the product of the primes.
+
+use crate::{primes, unique_words, Generate, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+common::params! {
+    pub struct Params {
+        counters: usize = 4,
+        /// flip-flops in every counter, between 2 and 20
+        bits: u32 = 12,
+    }
+}
+
+pub struct Modules;
+
+impl Generate for Modules {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let bits = params.bits.clamp(2, 20);
+        let counters = params.counters.max(1);
+        let mut primes: Vec<u64> = primes((1 << bits) - 1)
+            .into_iter()
+            .filter(|&p| p > 1 << (bits - 1))
+            .collect();
+        rng.shuffle(&mut primes);
+        // the few bits of tiny counters leave no choice but to repeat primes
+        let periods: Vec<u64> = (0..counters).map(|i| primes[i % primes.len()]).collect();
+
+        let per_counter = bits as usize + 2;
+        let names = unique_words(rng, LETTERS, 2, counters * per_counter + 1, &["rx", "in"]);
+        let last = &names[counters * per_counter];
+
+        let mut modules = vec![format!("&{} -> rx", last)];
+        let mut starts = vec![];
+        for (names, &period) in names.chunks_exact(per_counter).zip(&periods) {
+            let (flip_flops, hub, inverter) = (
+                &names[..bits as usize],
+                &names[bits as usize],
+                &names[bits as usize + 1],
+            );
+            starts.push(flip_flops[0].as_str());
+
+            let mut hub_outputs = vec![inverter.as_str(), flip_flops[0].as_str()];
+            for (bit, flip_flop) in flip_flops.iter().enumerate() {
+                let mut outputs = vec![];
+                if let Some(next) = flip_flops.get(bit + 1) {
+                    outputs.push(next.as_str());
+                }
+                if period & 1 << bit != 0 {
+                    outputs.push(hub.as_str());
+                } else {
+                    hub_outputs.push(flip_flop.as_str());
+                }
+                rng.shuffle(&mut outputs);
+                modules.push(format!("%{} -> {}", flip_flop, outputs.join(", ")));
+            }
+            rng.shuffle(&mut hub_outputs);
+            modules.push(format!("&{} -> {}", hub, hub_outputs.join(", ")));
+            modules.push(format!("&{} -> {}", inverter, last));
+        }
+        modules.push(format!("broadcaster -> {}", starts.join(", ")));
+        rng.shuffle(&mut modules);
+
+        modules.join("\n") + "\n"
+    }
+}
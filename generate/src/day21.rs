@@ -0,0 +1,38 @@
+//! Garden: rocks on a square map with the start in the middle.
+//!
+//! The border and the row and column through the start stay free of rocks,
+//! like in the real inputs, which the second part relies on.
+
+use grid::Grid;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        /// width and height, made odd to have a middle
+        size: usize = 131,
+        /// chance of a rock on a tile
+        rocks: f64 = 0.15,
+    }
+}
+
+pub struct Garden;
+
+impl Generate for Garden {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let size = params.size.max(3) | 1;
+        let middle = size / 2;
+        let mut grid = Grid::new(size, size, '.');
+        for y in 1..size - 1 {
+            for x in 1..size - 1 {
+                if x != middle && y != middle && rng.chance(params.rocks) {
+                    grid[(x, y)] = '#';
+                }
+            }
+        }
+        grid[(middle, middle)] = 'S';
+        grid.to_string()
+    }
+}
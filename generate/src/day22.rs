@@ -0,0 +1,72 @@
+//! Snapshot of falling bricks, none of them overlapping.
+
+use std::collections::HashSet;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        bricks: usize = 1200,
+        width: usize = 10,
+        depth: usize = 10,
+        /// longest brick
+        length: usize = 4,
+        /// chance of a brick written end first, which the real inputs never do
+        reversed: f64 = 0.0,
+    }
+}
+
+pub struct Bricks;
+
+impl Generate for Bricks {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let (width, depth) = (params.width.max(1), params.depth.max(1));
+        // about a tenth of the space above the ground is taken
+        let top = (25 * params.bricks / (width * depth)).max(1) + 1;
+        let size = [width, depth, top];
+
+        let mut taken = HashSet::new();
+        let mut text = String::new();
+        let mut placed = 0;
+        while placed < params.bricks {
+            let axis = rng.index(3);
+            let length = rng.range(1..=params.length.max(1).min(size[axis]) as i64) as usize;
+            let mut start = [0; 3];
+            for (i, start) in start.iter_mut().enumerate() {
+                let room = if i == axis {
+                    size[i] - length
+                } else {
+                    size[i] - 1
+                };
+                *start = rng.range(0..=room as i64) as usize;
+            }
+            // nothing lies on the ground itself
+            start[2] += 1;
+
+            let cubes: Vec<[usize; 3]> = (0..length)
+                .map(|i| {
+                    let mut cube = start;
+                    cube[axis] += i;
+                    cube
+                })
+                .collect();
+            if cubes.iter().any(|cube| taken.contains(cube)) {
+                continue;
+            }
+            taken.extend(cubes.iter().copied());
+            placed += 1;
+
+            let (mut from, mut to) = (cubes[0], cubes[length - 1]);
+            if rng.chance(params.reversed) {
+                (from, to) = (to, from);
+            }
+            text += &format!(
+                "{},{},{}~{},{},{}\n",
+                from[0], from[1], from[2], to[0], to[1], to[2]
+            );
+        }
+        text
+    }
+}
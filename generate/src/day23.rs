@@ -0,0 +1,157 @@
+//! Hiking trails: a maze of forest with slopes next to the junctions.
+//!
+//! The maze is a spanning tree with a few walls knocked out for loops and the
+//! dead ends filled in again, leaving long trails between the junctions. The
+//! slopes only point away from the start, so the shortest hike is never
+//! blocked by one.
+
+use std::collections::VecDeque;
+
+use grid::Grid;
+
+use crate::{
+    shapes::{spanning_tree, towards},
+    Generate, Rng,
+};
+
+common::params! {
+    pub struct Params {
+        /// width of the map, made odd
+        width: usize = 141,
+        /// height of the map, made odd
+        height: usize = 141,
+        /// walls knocked out of the maze, each one adds a loop
+        loops: usize = 30,
+    }
+}
+
+pub struct Trails;
+
+type Pos = (usize, usize);
+
+fn open_neighbours(grid: &Grid<char>, pos: Pos) -> Vec<Pos> {
+    grid.neighbours(pos)
+        .map(|(_, next)| next)
+        .filter(|&next| grid[next] != '#')
+        .collect()
+}
+
+/// Every trail leading out of a junction, from the junction to the junction
+/// or the end it leads to. Trails back to the start are left out.
+fn trails(grid: &Grid<char>, end: Pos) -> Vec<Vec<Pos>> {
+    let is_junction = |pos| open_neighbours(grid, pos).len() >= 3;
+    let mut trails = vec![];
+    for junction in grid
+        .positions()
+        .filter(|&pos| grid[pos] != '#' && is_junction(pos))
+    {
+        for first in open_neighbours(grid, junction) {
+            let mut trail = vec![junction, first];
+            let mut at = first;
+            while at != end && !is_junction(at) {
+                let previous = trail[trail.len() - 2];
+                match open_neighbours(grid, at)
+                    .into_iter()
+                    .find(|&next| next != previous)
+                {
+                    Some(next) => {
+                        trail.push(next);
+                        at = next;
+                    }
+                    // the start
+                    None => break,
+                }
+            }
+            if open_neighbours(grid, at).len() > 1 || at == end {
+                trails.push(trail);
+            }
+        }
+    }
+    trails
+}
+
+impl Generate for Trails {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let (width, height) = (params.width.max(5) | 1, params.height.max(5) | 1);
+        let mut grid = Grid::new(width, height, '#');
+        let (nodes_x, nodes_y) = ((width - 1) / 2, (height - 1) / 2);
+        for y in 0..nodes_y {
+            for x in 0..nodes_x {
+                grid[(2 * x + 1, 2 * y + 1)] = '.';
+            }
+        }
+        for ((ax, ay), (bx, by)) in spanning_tree(rng, nodes_x, nodes_y) {
+            grid[(ax + bx + 1, ay + by + 1)] = '.';
+        }
+
+        let mut walls: Vec<Pos> = grid
+            .positions()
+            .filter(|&(x, y)| x > 0 && y > 0 && x < width - 1 && y < height - 1)
+            .filter(|&(x, y)| (x + y) % 2 == 1 && grid[(x, y)] == '#')
+            .collect();
+        rng.shuffle(&mut walls);
+        for &wall in walls.iter().take(params.loops) {
+            grid[wall] = '.';
+        }
+
+        let (start, end) = ((1, 0), (width - 2, height - 1));
+        grid[start] = '.';
+        grid[end] = '.';
+        let mut dead_ends: Vec<Pos> = grid.positions().collect();
+        while let Some(pos) = dead_ends.pop() {
+            if grid[pos] == '#' || pos == start || pos == end {
+                continue;
+            }
+            let neighbours = open_neighbours(&grid, pos);
+            if neighbours.len() <= 1 {
+                grid[pos] = '#';
+                dead_ends.extend(neighbours);
+            }
+        }
+
+        let mut distance = Grid::new(width, height, usize::MAX);
+        distance[start] = 0;
+        let mut queue = VecDeque::from([start]);
+        while let Some(pos) = queue.pop_front() {
+            for next in open_neighbours(&grid, pos) {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[pos] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // trails between junctions just as far from the start would make a
+        // loop the slopes cannot point out of, so they grow back over
+        for trail in trails(&grid, end) {
+            let (from, to) = (trail[0], trail[trail.len() - 1]);
+            if to != end && distance[from] == distance[to] {
+                for &pos in &trail[1..trail.len() - 1] {
+                    grid[pos] = '#';
+                }
+            }
+        }
+
+        // slopes on both ends of the other trails, pointing away from the
+        // start, so there is no way back to a junction already passed
+        let mut slopes = vec![];
+        for trail in trails(&grid, end) {
+            let (from, to) = (trail[0], trail[trail.len() - 1]);
+            if distance[to] <= distance[from] {
+                continue;
+            }
+            let first = trail[1];
+            slopes.push((first, towards(from, first)));
+            if to != end {
+                let last = trail[trail.len() - 2];
+                slopes.push((last, towards(last, to)));
+            }
+        }
+        for (pos, direction) in slopes {
+            grid[pos] = direction.arrow();
+        }
+        grid.to_string()
+    }
+}
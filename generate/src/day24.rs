@@ -0,0 +1,53 @@
+//! Hailstones, all of them hit by one rock thrown from the right spot.
+
+use std::collections::HashSet;
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        stones: usize = 300,
+        /// fastest a stone or the rock moves along an axis
+        speed: i64 = 300,
+    }
+}
+
+pub struct Hailstones;
+
+impl Generate for Hailstones {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let speed = params.speed.max(2);
+        // a velocity without zeros, differing from `other` on every axis
+        let velocity = |rng: &mut Rng, other: [i64; 3]| {
+            other.map(|other| loop {
+                let v = rng.range(-speed..=speed);
+                if v != 0 && v != other {
+                    break v;
+                }
+            })
+        };
+
+        let rock = [(); 3].map(|_| rng.range(250_000_000_000_000..=350_000_000_000_000));
+        let rock_velocity = velocity(rng, [0; 3]);
+
+        let mut times = HashSet::new();
+        let mut text = String::new();
+        while times.len() < params.stones {
+            let time = rng.range(100_000_000_000..=1_000_000_000_000);
+            if !times.insert(time) {
+                continue;
+            }
+            let v = velocity(rng, rock_velocity);
+            let p: Vec<i64> = (0..3)
+                .map(|axis| rock[axis] + (rock_velocity[axis] - v[axis]) * time)
+                .collect();
+            text += &format!(
+                "{}, {}, {} @ {}, {}, {}\n",
+                p[0], p[1], p[2], v[0], v[1], v[2]
+            );
+        }
+        text
+    }
+}
@@ -0,0 +1,53 @@
+//! Engine schematic: part numbers and symbols on a grid of dots.
+
+use crate::{Generate, Rng};
+
+const SYMBOLS: &[u8] = b"*#+$/@%=&-";
+
+common::params! {
+    pub struct Params {
+        width: usize = 140,
+        height: usize = 140,
+        /// chance of a number starting on a tile
+        numbers: f64 = 0.12,
+        /// chance of a symbol on a tile
+        symbols: f64 = 0.08,
+    }
+}
+
+pub struct Schematic;
+
+impl Generate for Schematic {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut text = String::new();
+        for _ in 0..params.height {
+            let mut line = String::new();
+            while line.len() < params.width {
+                if rng.chance(params.numbers) {
+                    // numbers run up to the end of the row, but never into
+                    // another number
+                    let digits = rng.range(1..=3).min((params.width - line.len()) as i64) as u32;
+                    line += &rng
+                        .range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1)
+                        .to_string();
+                    if line.len() < params.width {
+                        line.push(if rng.chance(params.symbols) {
+                            *rng.choose(SYMBOLS) as char
+                        } else {
+                            '.'
+                        });
+                    }
+                } else if rng.chance(params.symbols) {
+                    line.push(*rng.choose(SYMBOLS) as char);
+                } else {
+                    line.push('.');
+                }
+            }
+            text += &line;
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,66 @@
+//! Scratchcards: winning numbers and the numbers you have.
+
+use crate::{sample, Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        cards: usize = 200,
+        winning: usize = 10,
+        numbers: usize = 25,
+        /// the wins stop once the cards won add up to about this many
+        max_copies: usize = 10_000_000,
+    }
+}
+
+pub struct Scratchcards;
+
+impl Generate for Scratchcards {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let pool: Vec<u32> = (1..100).collect();
+        let id_width = params.cards.to_string().len();
+        let numbers = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let mut copies = vec![1_usize; params.cards + 1];
+        let mut total: usize = 0;
+        let mut text = String::new();
+        for id in 1..=params.cards {
+            total = total.saturating_add(copies[id]);
+            // a card never wins copies of cards past the end of the table
+            let most = params.winning.min(params.numbers).min(params.cards - id);
+            let wins = if total > params.max_copies / 2 {
+                0
+            } else {
+                rng.range(0..=most as i64) as usize
+            };
+            for won in id + 1..=id + wins {
+                copies[won] = copies[won].saturating_add(copies[id]);
+            }
+
+            let winning = sample(rng, &pool, params.winning);
+            let others: Vec<u32> = pool
+                .iter()
+                .filter(|n| !winning.contains(n))
+                .copied()
+                .collect();
+            let mut have = sample(rng, &winning, wins);
+            have.extend(sample(rng, &others, params.numbers - wins));
+            rng.shuffle(&mut have);
+
+            text += &format!(
+                "Card {:>id_width$}: {} | {}\n",
+                id,
+                numbers(&winning),
+                numbers(&have)
+            );
+        }
+        text
+    }
+}
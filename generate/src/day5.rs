@@ -0,0 +1,63 @@
+//! Almanac: seed ranges and the maps from seeds to locations.
+
+use crate::{Generate, Rng};
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+common::params! {
+    pub struct Params {
+        /// number of seed ranges, every range is two numbers
+        seed_ranges: usize = 10,
+        /// longest seed range
+        seed_range: u32 = 100_000_000,
+        /// most ranges in a map
+        ranges: usize = 40,
+    }
+}
+
+pub struct Almanac;
+
+impl Generate for Almanac {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let seeds: Vec<String> = (0..params.seed_ranges)
+            .flat_map(|_| {
+                let length = rng.range(1..=params.seed_range.max(1) as i64);
+                let start = rng.range(0..=u32::MAX as i64 - length - 1);
+                [start.to_string(), length.to_string()]
+            })
+            .collect();
+        let mut text = format!("seeds: {}\n", seeds.join(" "));
+
+        for categories in CATEGORIES.windows(2) {
+            text += &format!("\n{}-to-{} map:\n", categories[0], categories[1]);
+
+            // consecutive source ranges, mapped to the same ranges shuffled
+            let mut cuts: Vec<i64> = (0..=rng.range(1..=params.ranges.max(1) as i64))
+                .map(|_| rng.range(0..=u32::MAX as i64))
+                .collect();
+            cuts.sort();
+            cuts.dedup();
+            let mut ranges: Vec<(i64, i64)> =
+                cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+            let total: i64 = ranges.iter().map(|&(_, length)| length).sum();
+            let mut destination = rng.range(0..=u32::MAX as i64 - total);
+            rng.shuffle(&mut ranges);
+            for (source, length) in ranges {
+                text += &format!("{} {} {}\n", destination, source, length);
+                destination += length;
+            }
+        }
+        text
+    }
+}
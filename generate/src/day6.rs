@@ -0,0 +1,61 @@
+//! Boat races: the time of every race and the record distance.
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        races: usize = 4,
+        /// longest race
+        time: u32 = 99,
+    }
+}
+
+pub struct Races;
+
+/// Farthest distance a boat gets in a race of `time`.
+fn best_distance(time: u128) -> u128 {
+    (time / 2) * (time - time / 2)
+}
+
+impl Generate for Races {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let (times, distances) = loop {
+            let times: Vec<u128> = (0..params.races.max(1))
+                .map(|_| rng.range(2..=params.time.max(2) as i64) as u128)
+                .collect();
+            let distances: Vec<u128> = times
+                .iter()
+                .map(|&time| {
+                    let best = best_distance(time) as i64;
+                    rng.range(best / 2..=best - 1) as u128
+                })
+                .collect();
+
+            // the record of the one long race in the second part must be
+            // beatable as well
+            let concat = |numbers: &[u128]| {
+                numbers
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<String>()
+                    .parse::<u128>()
+            };
+            if let (Ok(time), Ok(distance)) = (concat(&times), concat(&distances)) {
+                if distance <= u64::MAX as u128 && distance < best_distance(time) {
+                    break (times, distances);
+                }
+            }
+        };
+
+        let mut time_line = "Time:    ".to_owned();
+        let mut distance_line = "Distance:".to_owned();
+        for (time, distance) in times.iter().zip(&distances) {
+            let width = distance.to_string().len() + 3;
+            time_line += &format!("{:>width$}", time);
+            distance_line += &format!("{:>width$}", distance);
+        }
+        format!("{}\n{}\n", time_line, distance_line)
+    }
+}
@@ -0,0 +1,55 @@
+//! Camel Cards: hands and their bids.
+
+use std::collections::HashSet;
+
+use crate::{sample, Generate, Rng};
+
+const CARDS: &[char] = &[
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+/// How often each card of a hand appears, from five of a kind to high card.
+const TYPES: [&[usize]; 7] = [
+    &[5],
+    &[4, 1],
+    &[3, 2],
+    &[3, 1, 1],
+    &[2, 2, 1],
+    &[2, 1, 1, 1],
+    &[1, 1, 1, 1, 1],
+];
+
+common::params! {
+    pub struct Params {
+        hands: usize = 1000,
+        max_bid: u32 = 1000,
+    }
+}
+
+pub struct Hands;
+
+impl Generate for Hands {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        // there are only so many different hands
+        let hands = params.hands.min(200_000);
+        let mut seen = HashSet::new();
+        let mut text = String::new();
+        while seen.len() < hands {
+            let counts = rng.choose(&TYPES);
+            let cards = sample(rng, CARDS, counts.len());
+            let mut hand: Vec<char> = counts
+                .iter()
+                .zip(cards)
+                .flat_map(|(&count, card)| std::iter::repeat_n(card, count))
+                .collect();
+            rng.shuffle(&mut hand);
+            let hand: String = hand.into_iter().collect();
+            if seen.insert(hand.clone()) {
+                text += &format!("{} {}\n", hand, rng.range(1..=params.max_bid.max(1) as i64));
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,85 @@
+//! Haunted wasteland network: left/right instructions and the nodes.
+//!
+//! Every ghost walks a chain of node pairs from its `..A` node to its `..Z`
+//! node, where both nodes of a pair lead on to the next pair, so the
+//! instructions pick the path but not how long it is. From the `..Z` node the
+//! chain starts over, which makes the first arrival also the cycle length the
+//! second part takes the least common multiple of.
+
+use crate::{primes, unique_words, word, Generate, Rng};
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+common::params! {
+    pub struct Params {
+        instructions: usize = 270,
+        /// ghosts starting on a `..A` node, the first on `AAA`
+        ghosts: usize = 6,
+        /// longest cycle, the cycles are different primes up to this and above
+        /// half of it
+        cycle: u64 = 80,
+    }
+}
+
+pub struct Network;
+
+impl Generate for Network {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let instructions: String = (0..params.instructions.max(1))
+            .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+            .collect();
+
+        // the larger half of the primes, unless there are too few of them
+        let mut cycles = primes(params.cycle.max(2));
+        let larger = cycles.partition_point(|&p| p <= params.cycle / 2);
+        if cycles.len() - larger >= params.ghosts {
+            cycles.drain(..larger);
+        }
+        rng.shuffle(&mut cycles);
+        let ghosts = params.ghosts.max(1);
+        let cycles: Vec<usize> = (0..ghosts)
+            .map(|i| cycles[i % cycles.len()] as usize)
+            .collect();
+
+        // names of the starts, ends and the pairs in between
+        let mut taken = vec!["AAA".to_owned(), "ZZZ".to_owned()];
+        while taken.len() < 2 * ghosts {
+            let prefix = word(rng, LETTERS, 2);
+            let (start, end) = (prefix.clone() + "A", prefix + "Z");
+            if !taken.contains(&start) && !taken.contains(&end) {
+                taken.extend([start, end]);
+            }
+        }
+        let inner_count: usize = cycles.iter().map(|cycle| 2 * (cycle - 1)).sum();
+        let taken_refs: Vec<&str> = taken.iter().map(String::as_str).collect();
+        let mut inner = unique_words(rng, LETTERS, 3, inner_count * 2, &taken_refs)
+            .into_iter()
+            .filter(|name| !name.ends_with(['A', 'Z']))
+            .take(inner_count);
+
+        let mut nodes = vec![];
+        for (ghost, &cycle) in taken.chunks(2).zip(&cycles) {
+            let (start, end) = (&ghost[0], &ghost[1]);
+            let pairs: Vec<(String, String)> = (1..cycle)
+                .map(|_| (inner.next().unwrap(), inner.next().unwrap()))
+                .collect();
+
+            let mut leads_to = |pair: Option<&(String, String)>| match pair {
+                Some((a, b)) if rng.chance(0.5) => format!("({}, {})", a, b),
+                Some((a, b)) => format!("({}, {})", b, a),
+                None => format!("({}, {})", end, end),
+            };
+            nodes.push(format!("{} = {}", start, leads_to(pairs.first())));
+            nodes.push(format!("{} = {}", end, leads_to(pairs.first())));
+            for (i, (a, b)) in pairs.iter().enumerate() {
+                nodes.push(format!("{} = {}", a, leads_to(pairs.get(i + 1))));
+                nodes.push(format!("{} = {}", b, leads_to(pairs.get(i + 1))));
+            }
+        }
+        rng.shuffle(&mut nodes);
+
+        format!("{}\n\n{}\n", instructions, nodes.join("\n"))
+    }
+}
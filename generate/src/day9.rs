@@ -0,0 +1,45 @@
+//! OASIS report: sequences of values that follow a polynomial.
+
+use crate::{Generate, Rng};
+
+common::params! {
+    pub struct Params {
+        sequences: usize = 200,
+        length: usize = 21,
+        /// highest degree of the polynomials
+        degree: usize = 6,
+    }
+}
+
+pub struct Report;
+
+fn binomial(n: i64, k: i64) -> i64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+impl Generate for Report {
+    type Params = Params;
+
+    fn generate(rng: &mut Rng, params: &Params) -> String {
+        let mut text = String::new();
+        for _ in 0..params.sequences {
+            // in the binomial basis every integer coefficient gives integer
+            // values, and the differences end in zeros after `degree` steps
+            let degree = rng.range(0..=params.degree as i64);
+            let coefficients: Vec<i64> = (0..=degree).map(|_| rng.range(-20..=20)).collect();
+            let values: Vec<String> = (0..params.length as i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .enumerate()
+                        .map(|(k, c)| c * binomial(x, k as i64))
+                        .sum::<i64>()
+                        .to_string()
+                })
+                .collect();
+            text += &values.join(" ");
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,201 @@
+//! Random puzzle inputs in the format of every day, to test the solvers on
+//! more than the one real input and the examples.
+//!
+//! A generator turns a seed and its size parameters into puzzle text. The
+//! same seed and parameters always give the same text, and the inputs are
+//! built so the solvers can finish on them, e.g. the module network of day 20
+//! really turns on `rx` at some point:
+//!
+//! ```
+//! let generator = generate::find(8).unwrap();
+//! let input = generator.generate(42, &[("ghosts", "2"), ("cycle", "10")]).unwrap();
+//! assert_eq!(input, generator.generate(42, &[("ghosts", "2"), ("cycle", "10")]).unwrap());
+//! assert!(input.contains("AAA = ("));
+//! ```
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+mod rng;
+mod shapes;
+
+use common::{ParamError, Params};
+
+pub use rng::Rng;
+
+/// A generator of puzzle inputs for one day, with its size parameters.
+pub trait Generate {
+    type Params: Params;
+
+    fn generate(rng: &mut Rng, params: &Self::Params) -> String;
+}
+
+/// Generate the puzzle text for a seed with the given `name=value` parameter
+/// overrides.
+pub type GenerateFn = fn(u64, &[(&str, &str)]) -> Result<String, ParamError>;
+
+/// The generator of one day that the runner can dispatch to.
+pub struct Generator {
+    pub day: u8,
+    generate: GenerateFn,
+    params: fn() -> Vec<(&'static str, String)>,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, params: &[(&str, &str)]) -> Result<String, ParamError> {
+        (self.generate)(seed, params)
+    }
+
+    /// Names and default values of the parameters.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        (self.params)()
+    }
+}
+
+fn generate<G: Generate>(seed: u64, params: &[(&str, &str)]) -> Result<String, ParamError> {
+    let params = G::Params::from_pairs(params.iter().copied())?;
+    Ok(G::generate(&mut Rng::new(seed), &params))
+}
+
+fn defaults<G: Generate>() -> Vec<(&'static str, String)> {
+    G::Params::default().values()
+}
+
+macro_rules! generator {
+    ($day:literal, $generator:ty) => {
+        Generator {
+            day: $day,
+            generate: generate::<$generator>,
+            params: defaults::<$generator>,
+        }
+    };
+}
+
+/// The generators of all days, ordered by day.
+pub static GENERATORS: &[Generator] = &[
+    generator!(1, day1::Calibration),
+    generator!(2, day2::Games),
+    generator!(3, day3::Schematic),
+    generator!(4, day4::Scratchcards),
+    generator!(5, day5::Almanac),
+    generator!(6, day6::Races),
+    generator!(7, day7::Hands),
+    generator!(8, day8::Network),
+    generator!(9, day9::Report),
+    generator!(10, day10::PipeMaze),
+    generator!(11, day11::Image),
+    generator!(12, day12::Springs),
+    generator!(13, day13::Patterns),
+    generator!(14, day14::Platform),
+    generator!(15, day15::InitSequence),
+    generator!(16, day16::Contraption),
+    generator!(17, day17::HeatMap),
+    generator!(18, day18::DigPlan),
+    generator!(19, day19::Workflows),
+    generator!(20, day20::Modules),
+    generator!(21, day21::Garden),
+    generator!(22, day22::Bricks),
+    generator!(23, day23::Trails),
+    generator!(24, day24::Hailstones),
+];
+
+/// Find the generator for the given day.
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// A word of `len` random letters from `alphabet`.
+fn word(rng: &mut Rng, alphabet: &[u8], len: usize) -> String {
+    (0..len).map(|_| *rng.choose(alphabet) as char).collect()
+}
+
+/// `count` different random words of `len` letters, none of them in `taken`.
+fn unique_words(
+    rng: &mut Rng,
+    alphabet: &[u8],
+    len: usize,
+    count: usize,
+    taken: &[&str],
+) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    while words.len() < count {
+        let word = word(rng, alphabet, len);
+        if !taken.contains(&word.as_str()) && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// `count` different items of `pool` in random order, or all of them if
+/// there are fewer.
+fn sample<T: Clone>(rng: &mut Rng, pool: &[T], count: usize) -> Vec<T> {
+    let mut pool = pool.to_vec();
+    rng.shuffle(&mut pool);
+    pool.truncate(count);
+    pool
+}
+
+fn primes(limit: u64) -> Vec<u64> {
+    (2..=limit)
+        .filter(|&n| {
+            (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_for_every_day() {
+        for day in 1..=24 {
+            let generator = find(day).expect("generator for every day");
+            assert!(!generator.params().is_empty());
+        }
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn reject_unknown_params() {
+        let generator = find(1).unwrap();
+        assert_eq!(
+            generator.generate(0, &[("width", "10")]),
+            Err(ParamError::Unknown("width".to_owned()))
+        );
+    }
+
+    #[test]
+    fn pick_words_and_primes() {
+        let mut rng = Rng::new(5);
+        let words = unique_words(&mut rng, b"ab", 2, 3, &["aa"]);
+        assert_eq!(words.len(), 3);
+        assert!(!words.contains(&"aa".to_owned()));
+        assert_eq!(primes(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+        assert_eq!(sample(&mut rng, &[1, 2], 5).len(), 2);
+    }
+}
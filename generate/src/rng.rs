@@ -0,0 +1,107 @@
+use std::ops::RangeInclusive;
+
+/// SplitMix64, small and fast, and the same numbers for the same seed on
+/// every platform and build.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number in the inclusive range.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        let span = end.abs_diff(start).wrapping_add(1);
+        if span == 0 {
+            // the whole range of i64
+            return self.next_u64() as i64;
+        }
+        start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// An index into a slice of length `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "index into an empty slice");
+        self.below(len as u64) as usize
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut rng = Rng::new(0);
+        // reference values of SplitMix64 with seed 0
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.range(-5..=5)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+    }
+
+    #[test]
+    fn stay_in_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let n = rng.range(-1..=2);
+            assert!((-1..=2).contains(&n));
+            seen[(n + 1) as usize] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+        assert_eq!(rng.range(3..=3), 3);
+        rng.range(i64::MIN..=i64::MAX);
+        assert_eq!(rng.below(0), 0);
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
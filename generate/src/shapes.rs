@@ -0,0 +1,155 @@
+//! Random shapes that the grid and loop puzzles are built from.
+
+use std::collections::HashMap;
+
+use grid::{
+    Direction::{self, *},
+    Grid,
+};
+
+use crate::Rng;
+
+type Pos = (usize, usize);
+
+/// A random spanning tree of a `width` x `height` lattice as a list of edges.
+///
+/// The tree is grown depth first, which gives long winding branches like the
+/// corridors of a maze.
+pub(crate) fn spanning_tree(rng: &mut Rng, width: usize, height: usize) -> Vec<(Pos, Pos)> {
+    let mut visited = Grid::new(width, height, false);
+    let start = (rng.index(width), rng.index(height));
+    visited[start] = true;
+
+    let mut edges = vec![];
+    let mut stack = vec![start];
+    while let Some(&node) = stack.last() {
+        let unvisited: Vec<Pos> = visited
+            .neighbours(node)
+            .map(|(_, next)| next)
+            .filter(|&next| !visited[next])
+            .collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = *rng.choose(&unvisited);
+        visited[next] = true;
+        edges.push((node, next));
+        stack.push(next);
+    }
+    edges
+}
+
+/// A random closed loop that never touches itself, as the list of lattice
+/// points along it, clockwise and starting at its top left corner. The points
+/// lie within `0..=(2 * width - 1) * thickness` on the x axis, and the same
+/// with `height` on the y axis.
+///
+/// The loop is the outline of a tree drawn with cells, the nodes on the even
+/// cells and the edges on the cells between them, every cell being
+/// `thickness` points wide. Such a blob has no holes and no cells touching
+/// only by a corner, so its outline is simple. The tree is a spanning tree
+/// stopped after growing to a random size between half and all of the nodes,
+/// as the outline of a full one is equally long for every seed.
+pub(crate) fn outline(rng: &mut Rng, width: usize, height: usize, thickness: usize) -> Vec<Pos> {
+    let mut edges = spanning_tree(rng, width, height);
+    let grown = edges.len() / 2 + rng.index(edges.len() - edges.len() / 2 + 1);
+    edges.truncate(grown);
+
+    let mut tree = Grid::new(2 * width - 1, 2 * height - 1, false);
+    let (root, _) = edges.first().copied().unwrap_or(((0, 0), (0, 0)));
+    tree[(2 * root.0, 2 * root.1)] = true;
+    for ((ax, ay), (bx, by)) in edges {
+        tree[(2 * bx, 2 * by)] = true;
+        tree[(ax + bx, ay + by)] = true;
+    }
+    let mut cells = Grid::new(tree.width() * thickness, tree.height() * thickness, false);
+    for (x, y) in cells.positions().collect::<Vec<_>>() {
+        cells[(x, y)] = tree[(x / thickness, y / thickness)];
+    }
+
+    // the sides of the blob, each leading to the next point with the blob on
+    // its right
+    let mut next: HashMap<Pos, Pos> = HashMap::new();
+    for ((x, y), &filled) in cells.iter() {
+        if !filled {
+            continue;
+        }
+        let outside = |direction| cells.step((x, y), direction).is_none_or(|n| !cells[n]);
+        if outside(North) {
+            next.insert((x, y), (x + 1, y));
+        }
+        if outside(East) {
+            next.insert((x + 1, y), (x + 1, y + 1));
+        }
+        if outside(South) {
+            next.insert((x + 1, y + 1), (x, y + 1));
+        }
+        if outside(West) {
+            next.insert((x, y + 1), (x, y));
+        }
+    }
+
+    let start = *next.keys().min().expect("blob with at least one cell");
+    let mut points = vec![start];
+    let mut at = next[&start];
+    while at != start {
+        points.push(at);
+        at = next[&at];
+    }
+    debug_assert_eq!(points.len(), next.len(), "outline in a single piece");
+    points
+}
+
+/// Direction of a neighbouring point.
+pub(crate) fn towards((x, y): Pos, (to_x, to_y): Pos) -> Direction {
+    match (to_x as isize - x as isize, to_y as isize - y as isize) {
+        (0, -1) => North,
+        (1, 0) => East,
+        (0, 1) => South,
+        (-1, 0) => West,
+        step => unreachable!("{:?} is no step to a neighbour", step),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_every_node() {
+        let mut rng = Rng::new(3);
+        let edges = spanning_tree(&mut rng, 7, 5);
+        assert_eq!(edges.len(), 7 * 5 - 1);
+        let mut reached = Grid::new(7, 5, false);
+        reached[edges[0].0] = true;
+        for (from, to) in edges {
+            assert!(reached[from], "edges in the order they were grown");
+            assert_eq!(from.0.abs_diff(to.0) + from.1.abs_diff(to.1), 1);
+            reached[to] = true;
+        }
+        assert!(reached.iter().all(|(_, &reached)| reached));
+    }
+
+    #[test]
+    fn closed_simple_loop() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let points = outline(&mut rng, 6, 4, 1 + seed as usize % 2);
+            let (width, height) = if seed % 2 == 0 { (11, 7) } else { (22, 14) };
+            assert!(points.iter().all(|&(x, y)| x <= width && y <= height));
+            for (i, a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+            }
+            let mut unique = points.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), points.len());
+        }
+        assert_eq!(
+            outline(&mut Rng::new(0), 1, 1, 1),
+            vec![(0, 0), (1, 0), (1, 1), (0, 1)]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, NodeId};
+
+impl<N, W> Graph<N, W> {
+    /// The strongly connected components with Tarjan's algorithm: the sets
+    /// of nodes that can all reach each other, or the connected components
+    /// of an undirected graph.
+    ///
+    /// A component comes before the components with an edge into it, the
+    /// reverse of a topological order.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        const UNSEEN: usize = usize::MAX;
+        let count = self.node_count();
+        let mut index = vec![UNSEEN; count];
+        let mut low_link = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = vec![];
+        let mut next_index = 0;
+        let mut components = vec![];
+
+        for root in self.ids() {
+            if index[root.index()] != UNSEEN {
+                continue;
+            }
+            // the nodes being visited with the position of their next edge,
+            // instead of recursing
+            let mut visiting = vec![(root, 0)];
+            index[root.index()] = next_index;
+            low_link[root.index()] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root.index()] = true;
+
+            while let Some(&(id, edge)) = visiting.last() {
+                let v = id.index();
+                if let Some(&(next, _)) = self.edges(id).get(edge) {
+                    visiting.last_mut().expect("node being visited").1 += 1;
+                    let w = next.index();
+                    if index[w] == UNSEEN {
+                        index[w] = next_index;
+                        low_link[w] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[w] = true;
+                        visiting.push((next, 0));
+                    } else if on_stack[w] {
+                        low_link[v] = low_link[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                visiting.pop();
+                if let Some(&(parent, _)) = visiting.last() {
+                    low_link[parent.index()] = low_link[parent.index()].min(low_link[v]);
+                }
+                if low_link[v] == index[v] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member.index()] = false;
+                        component.push(member);
+                        if member == id {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// The nodes ordered so that every edge goes from an earlier node to a
+    /// later one, or `None` if there is a cycle. Ties keep the order the
+    /// nodes were added in.
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0; self.node_count()];
+        for id in self.ids() {
+            for next in self.neighbours(id) {
+                incoming[next.index()] += 1;
+            }
+        }
+        let mut ready: VecDeque<NodeId> =
+            self.ids().filter(|id| incoming[id.index()] == 0).collect();
+        let mut order = vec![];
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for next in self.neighbours(id) {
+                incoming[next.index()] -= 1;
+                if incoming[next.index()] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.node_count()).then_some(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(char, char)]) -> Graph<char> {
+        let mut graph = Graph::directed();
+        for &(from, to) in edges {
+            graph.add_edge(from, to, 1);
+        }
+        graph
+    }
+
+    fn names(graph: &Graph<char>, ids: &[NodeId]) -> String {
+        ids.iter().map(|&id| graph.node(id)).collect()
+    }
+
+    #[test]
+    fn find_components() {
+        // a <-> b -> c -> d -> e -> c, e -> f
+        let graph = graph(&[
+            ('a', 'b'),
+            ('b', 'a'),
+            ('b', 'c'),
+            ('c', 'd'),
+            ('d', 'e'),
+            ('e', 'c'),
+            ('e', 'f'),
+        ]);
+        let components: Vec<String> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| {
+                let mut names: Vec<char> = component.iter().map(|&id| *graph.node(id)).collect();
+                names.sort();
+                names.into_iter().collect()
+            })
+            .collect();
+        assert_eq!(components, ["f", "cde", "ab"]);
+
+        let mut undirected = Graph::undirected();
+        undirected.add_edge(1, 2, ());
+        undirected.add_edge(3, 4, ());
+        undirected.add_node(5);
+        assert_eq!(undirected.strongly_connected_components().len(), 3);
+    }
+
+    #[test]
+    fn sort_topologically() {
+        let dag = graph(&[('c', 'a'), ('a', 'b'), ('c', 'd'), ('d', 'b')]);
+        let order = dag.topological_order().unwrap();
+        assert_eq!(names(&dag, &order), "cadb");
+
+        let cyclic = graph(&[('a', 'b'), ('b', 'c'), ('c', 'b')]);
+        assert_eq!(cyclic.topological_order(), None);
+    }
+
+    #[test]
+    fn deep_graph() {
+        // a long chain would overflow the stack of a recursive search
+        let mut chain = Graph::directed();
+        for i in 0..100_000 {
+            chain.add_edge(i, i + 1, ());
+        }
+        chain.add_edge(100_000, 0, ());
+        assert_eq!(chain.strongly_connected_components().len(), 1);
+    }
+}
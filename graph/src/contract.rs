@@ -0,0 +1,93 @@
+use std::{hash::Hash, ops::Add};
+
+use crate::{Graph, NodeId};
+
+impl<N, W> Graph<N, W>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Add<Output = W>,
+{
+    /// The undirected graph with every chain of nodes with two edges, like
+    /// the tiles of a corridor, replaced by one edge as heavy as the chain.
+    /// The nodes `keep` is true for stay, like the ends of a path.
+    ///
+    /// The kept nodes get new ids. A ring of nodes with two edges each and
+    /// none of them kept is left out.
+    ///
+    /// # Panics
+    ///
+    /// If the graph is directed.
+    pub fn contract_chains(&self, keep: impl Fn(NodeId) -> bool) -> Self {
+        assert!(!self.directed, "only undirected graphs have chains");
+        let kept = |id| keep(id) || self.degree(id) != 2;
+
+        let mut contracted = Graph::undirected();
+        for id in self.ids().filter(|&id| kept(id)) {
+            let from = contracted.add_node(self.node(id).clone());
+            for &(first, mut weight) in self.edges(id) {
+                let (mut previous, mut next) = (id, first);
+                while !kept(next) {
+                    let edges = self.edges(next);
+                    // leave the chain's node through its other edge
+                    let (after, step) = if edges[0].0 == previous {
+                        edges[1]
+                    } else {
+                        edges[0]
+                    };
+                    weight = weight + step;
+                    (previous, next) = (next, after);
+                }
+                // the other end lists the edge when it is contracted itself
+                let to = contracted.add_node(self.node(next).clone());
+                contracted.edges[from.index()].push((to, weight));
+            }
+        }
+        contracted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapse_corridors() {
+        // a square ring of 8 tiles with a tail of 3 to the right of its
+        // corner at (2, 1)
+        let mut graph = Graph::undirected();
+        let ring = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        for (i, &tile) in ring.iter().enumerate() {
+            graph.add_edge(tile, ring[(i + 1) % ring.len()], 1);
+        }
+        for x in 3..=5 {
+            graph.add_edge((x - 1, 1), (x, 1), 1);
+        }
+
+        let start = graph.id(&(0, 0)).unwrap();
+        let contracted = graph.contract_chains(|id| id == start);
+        let edges = |tile| {
+            let id = contracted.id(&tile).unwrap();
+            let mut edges: Vec<((i32, i32), u32)> = contracted
+                .edges(id)
+                .iter()
+                .map(|&(to, weight)| (*contracted.node(to), weight))
+                .collect();
+            edges.sort();
+            edges
+        };
+        assert_eq!(contracted.node_count(), 3);
+        assert_eq!(edges((0, 0)), [((2, 1), 3), ((2, 1), 5)]);
+        assert_eq!(edges((2, 1)), [((0, 0), 3), ((0, 0), 5), ((5, 1), 3)]);
+        assert_eq!(edges((5, 1)), [((2, 1), 3)]);
+        assert_eq!(contracted.edge_count(), 3);
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::{Display, Write};
+
+use crate::Graph;
+
+impl<N: Display, W: Display> Graph<N, W> {
+    /// The graph in the DOT language of Graphviz, with the nodes labelled
+    /// with their `Display` and the edges with their weight.
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{} {{\n", kind);
+        for id in self.ids() {
+            let label = self.node(id).to_string();
+            writeln!(dot, "    {} [label=\"{}\"];", id.index(), escape(&label))
+                .expect("writing to a String");
+        }
+        for id in self.ids() {
+            for (to, weight) in self.listed_edges(id) {
+                writeln!(
+                    dot,
+                    "    {} {} {} [label=\"{}\"];",
+                    id.index(),
+                    arrow,
+                    to.index(),
+                    escape(&weight.to_string())
+                )
+                .expect("writing to a String");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed() {
+        let mut graph = Graph::directed();
+        graph.add_edge("AAA", "BBB", 'L');
+        graph.add_edge("AAA", "CCC", 'R');
+        graph.add_edge("BBB", "say \"hi\"", 'L');
+        assert_eq!(
+            graph.to_dot(),
+            r#"digraph {
+    0 [label="AAA"];
+    1 [label="BBB"];
+    2 [label="CCC"];
+    3 [label="say \"hi\""];
+    0 -> 1 [label="L"];
+    0 -> 2 [label="R"];
+    1 -> 3 [label="L"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn undirected_edges_once() {
+        let mut graph = Graph::undirected();
+        graph.add_edge(1, 2, 10);
+        graph.add_edge(2, 3, 20);
+        graph.add_edge(3, 3, 30);
+        assert_eq!(
+            graph.to_dot(),
+            r#"graph {
+    0 [label="1"];
+    1 [label="2"];
+    2 [label="3"];
+    0 -- 1 [label="10"];
+    1 -- 2 [label="20"];
+    2 -- 2 [label="30"];
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Graphs of puzzle nodes joined by weighted edges, and the usual algorithms
+//! on them.
+//!
+//! Nodes are anything hashable, like names or positions, and are interned:
+//! adding one gives it a [`NodeId`] numbered from 0, which the algorithms work
+//! with and which can index plain `Vec`s of per-node state.
+//!
+//! ```
+//! use graph::Graph;
+//!
+//! let mut graph = Graph::undirected();
+//! for (from, to, steps) in [("start", "fork", 3), ("fork", "a", 5), ("fork", "b", 2), ("a", "end", 1), ("b", "end", 4)] {
+//!     graph.add_edge(from, to, steps);
+//! }
+//! let start = graph.id(&"start").unwrap();
+//! let nearby: Vec<&str> = graph.bfs(start).into_iter().map(|(id, _)| *graph.node(id)).collect();
+//! assert_eq!(nearby, ["start", "fork", "a", "b", "end"]);
+//!
+//! // a and b only lead from the fork to the end
+//! let end = graph.id(&"end").unwrap();
+//! let contracted = graph.contract_chains(|id| id == start || id == end);
+//! assert_eq!(contracted.node_count(), 3);
+//! assert_eq!(contracted.edge_count(), 3);
+//! ```
+
+mod components;
+mod contract;
+mod dot;
+mod min_cut;
+mod traverse;
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A node of a [`Graph`], numbered from 0 in the order the nodes were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The number of the node, to index per-node state with.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Nodes of type `N` with edges between them weighing a `W` each.
+///
+/// An edge of an undirected graph is listed with both of its ends.
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = u32> {
+    directed: bool,
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    /// Edges leaving each node, in the order they were added.
+    edges: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N: Clone + Eq + Hash, W: Clone> Graph<N, W> {
+    pub fn directed() -> Self {
+        Graph {
+            directed: true,
+            nodes: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn undirected() -> Self {
+        Graph {
+            directed: false,
+            ..Self::directed()
+        }
+    }
+
+    /// The id of the node, adding it if it is new.
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Add an edge between the nodes, adding the nodes that are new, and
+    /// return their ids.
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.connect(from, to, weight);
+        (from, to)
+    }
+
+    /// Add an edge between two nodes of the graph.
+    ///
+    /// # Panics
+    ///
+    /// If either node is not in the graph.
+    pub fn connect(&mut self, from: NodeId, to: NodeId, weight: W) {
+        assert!(to.0 < self.nodes.len(), "no node {:?} in the graph", to);
+        if !self.directed && from != to {
+            self.edges[to.0].push((from, weight.clone()));
+        }
+        self.edges[from.0].push((to, weight));
+    }
+}
+
+impl<N: Eq + Hash, W> Graph<N, W> {
+    /// The id of the node, if it was added.
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+}
+
+impl<N, W> Graph<N, W> {
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges, counting an edge of an undirected graph once.
+    pub fn edge_count(&self) -> usize {
+        self.ids().map(|id| self.listed_edges(id).count()).sum()
+    }
+
+    /// All nodes in the order they were added.
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// The edges leaving the node, or all edges of it if the graph is
+    /// undirected, with the node at their other end.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.edges[id.0]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id.0].iter().map(|&(to, _)| to)
+    }
+
+    /// Number of edges of the node, leaving it if the graph is directed.
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.edges[id.0].len()
+    }
+
+    /// The edges of the node that are listed only once over the whole
+    /// graph: all of them if it is directed, the ones to a node with a
+    /// higher id or itself if not.
+    fn listed_edges(&self, id: NodeId) -> impl Iterator<Item = &(NodeId, W)> {
+        self.edges[id.0]
+            .iter()
+            .filter(move |(to, _)| self.directed || *to >= id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_nodes() {
+        let mut graph: Graph<&str, u32> = Graph::directed();
+        let (aaa, bbb) = graph.add_edge("AAA", "BBB", 1);
+        assert_eq!((aaa.index(), bbb.index()), (0, 1));
+        assert_eq!(graph.add_node("AAA"), aaa);
+        assert_eq!(graph.add_edge("BBB", "AAA", 2), (bbb, aaa));
+        assert_eq!(graph.id(&"BBB"), Some(bbb));
+        assert_eq!(graph.id(&"CCC"), None);
+        assert_eq!(*graph.node(bbb), "BBB");
+        assert_eq!(graph.edges(aaa), [(bbb, 1)]);
+        assert_eq!((graph.node_count(), graph.edge_count()), (2, 2));
+    }
+
+    #[test]
+    fn list_undirected_edges_at_both_ends() {
+        let mut graph = Graph::undirected();
+        let (a, b) = graph.add_edge('a', 'b', 1);
+        graph.add_edge('a', 'a', 2);
+        assert_eq!(graph.edges(a), [(b, 1), (a, 2)]);
+        assert_eq!(graph.edges(b), [(a, 1)]);
+        assert_eq!(graph.neighbours(a).collect::<Vec<_>>(), [b, a]);
+        assert_eq!(graph.edge_count(), 2);
+        assert!(!graph.is_directed());
+    }
+}
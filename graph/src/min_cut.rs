@@ -0,0 +1,130 @@
+use std::ops::Add;
+
+use crate::{Graph, NodeId};
+
+impl<N, W> Graph<N, W>
+where
+    W: Copy + Default + Ord + Add<Output = W>,
+{
+    /// The lightest set of edges whose removal splits the undirected graph
+    /// in two, with the Stoer-Wagner algorithm: the total weight of the
+    /// edges and the nodes on one side of the cut. `None` for fewer than two
+    /// nodes.
+    ///
+    /// Takes time cubic in the number of nodes, and `W::default()` is taken
+    /// as zero.
+    ///
+    /// # Panics
+    ///
+    /// If the graph is directed.
+    pub fn min_cut(&self) -> Option<(W, Vec<NodeId>)> {
+        assert!(!self.directed, "cuts of directed graphs are not symmetric");
+        let count = self.node_count();
+        // the weights between nodes, adding up parallel edges
+        let mut weights = vec![vec![W::default(); count]; count];
+        for id in self.ids() {
+            for &(to, weight) in self.edges(id) {
+                if to != id {
+                    let total = &mut weights[id.index()][to.index()];
+                    *total = *total + weight;
+                }
+            }
+        }
+        // the original nodes merged into each remaining one
+        let mut merged: Vec<Vec<NodeId>> = self.ids().map(|id| vec![id]).collect();
+        let mut remaining: Vec<usize> = (0..count).collect();
+        let mut best: Option<(W, Vec<NodeId>)> = None;
+
+        while remaining.len() > 1 {
+            // add the node most tightly connected to the ones added so far,
+            // until only the last one is left
+            let mut added = vec![false; count];
+            let mut connection = vec![W::default(); count];
+            let (mut previous, mut last) = (remaining[0], remaining[0]);
+            for _ in 0..remaining.len() {
+                let next = remaining
+                    .iter()
+                    .copied()
+                    .filter(|&v| !added[v])
+                    .reduce(|best, v| {
+                        if connection[v] > connection[best] {
+                            v
+                        } else {
+                            best
+                        }
+                    })
+                    .expect("a node not added yet");
+                added[next] = true;
+                (previous, last) = (last, next);
+                for &v in &remaining {
+                    if !added[v] {
+                        connection[v] = connection[v] + weights[next][v];
+                    }
+                }
+            }
+
+            // the cut between the last node and all others
+            let cut = connection[last];
+            if best.as_ref().is_none_or(|(lightest, _)| cut < *lightest) {
+                best = Some((cut, merged[last].clone()));
+            }
+
+            let nodes = std::mem::take(&mut merged[last]);
+            merged[previous].extend(nodes);
+            for &v in &remaining {
+                let weight = weights[previous][v] + weights[last][v];
+                weights[previous][v] = weight;
+                weights[v][previous] = weight;
+            }
+            weights[previous][previous] = W::default();
+            remaining.retain(|&v| v != last);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cut_between_clusters() {
+        // two squares with both diagonals, joined by two edges
+        let mut graph = Graph::undirected();
+        for square in [["a", "b", "c", "d"], ["e", "f", "g", "h"]] {
+            for (i, from) in square.iter().enumerate() {
+                for to in &square[i + 1..] {
+                    graph.add_edge(*from, *to, 1);
+                }
+            }
+        }
+        for (from, to) in [("a", "e"), ("b", "f")] {
+            graph.add_edge(from, to, 1);
+        }
+
+        let (weight, side) = graph.min_cut().unwrap();
+        assert_eq!(weight, 2);
+        let mut side: Vec<&str> = side.iter().map(|&id| *graph.node(id)).collect();
+        side.sort();
+        assert!(side == ["a", "b", "c", "d"] || side == ["e", "f", "g", "h"]);
+    }
+
+    #[test]
+    fn heavy_edges() {
+        // a path a - b - c - d where the middle edge is the lightest
+        let mut graph = Graph::undirected();
+        graph.add_edge('a', 'b', 5);
+        graph.add_edge('b', 'c', 2);
+        graph.add_edge('c', 'd', 4);
+        graph.add_edge('b', 'c', 1);
+        let (weight, side) = graph.min_cut().unwrap();
+        assert_eq!(weight, 3);
+        let mut side: Vec<char> = side.iter().map(|&id| *graph.node(id)).collect();
+        side.sort();
+        assert!(side == ['a', 'b'] || side == ['c', 'd']);
+
+        let mut single = Graph::<char, u32>::undirected();
+        single.add_node('a');
+        assert_eq!(single.min_cut(), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, NodeId};
+
+impl<N, W> Graph<N, W> {
+    /// The nodes reachable from `start` in breadth-first order, with the
+    /// number of edges on the way there.
+    pub fn bfs(&self, start: NodeId) -> Vec<(NodeId, usize)> {
+        let mut seen = vec![false; self.node_count()];
+        let mut order = vec![];
+        let mut queue = VecDeque::from([(start, 0)]);
+        seen[start.index()] = true;
+        while let Some((id, depth)) = queue.pop_front() {
+            order.push((id, depth));
+            for next in self.neighbours(id) {
+                if !seen[next.index()] {
+                    seen[next.index()] = true;
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+        order
+    }
+
+    /// The nodes reachable from `start` in depth-first order, each before
+    /// the nodes first reached through it.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.node_count()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if seen[id.index()] {
+                continue;
+            }
+            seen[id.index()] = true;
+            order.push(id);
+            // reversed to go into the first neighbour first
+            let edges = self.edges(id).iter().rev();
+            stack.extend(edges.map(|&(next, _)| next).filter(|n| !seen[n.index()]));
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d, a -> c -> d -> e, f on its own
+    fn diamond() -> Graph<char> {
+        let mut graph = Graph::directed();
+        for (from, to) in [('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('d', 'e')] {
+            graph.add_edge(from, to, 1);
+        }
+        graph.add_node('f');
+        graph
+    }
+
+    fn names(graph: &Graph<char>, ids: impl IntoIterator<Item = NodeId>) -> String {
+        ids.into_iter().map(|id| graph.node(id)).collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let graph = diamond();
+        let order = graph.bfs(graph.id(&'a').unwrap());
+        assert_eq!(names(&graph, order.iter().map(|&(id, _)| id)), "abcde");
+        let depths: Vec<usize> = order.iter().map(|&(_, depth)| depth).collect();
+        assert_eq!(depths, [0, 1, 1, 2, 3]);
+        assert_eq!(graph.bfs(graph.id(&'e').unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn depth_first() {
+        let graph = diamond();
+        assert_eq!(names(&graph, graph.dfs(graph.id(&'a').unwrap())), "abdec");
+        assert_eq!(names(&graph, graph.dfs(graph.id(&'c').unwrap())), "cde");
+    }
+}
@@ -0,0 +1,108 @@
+use Direction::*;
+
+/// One of the four directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from north.
+    pub const ALL: [Direction; 4] = [North, East, South, West];
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    /// Offset `(dx, dy)` of a single step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            North => (0, -1),
+            East => (1, 0),
+            South => (0, 1),
+            West => (-1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            North => South,
+            East => West,
+            South => North,
+            West => East,
+        }
+    }
+
+    /// Turn clockwise by 90 degrees.
+    pub fn turn_right(self) -> Direction {
+        match self {
+            North => East,
+            East => South,
+            South => West,
+            West => North,
+        }
+    }
+
+    /// Turn counter-clockwise by 90 degrees.
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, North | South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    /// A distinct bit per direction, to keep a set of directions in one `u8`.
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// Arrow character pointing this way, as used for slopes on day 23.
+    pub fn arrow(self) -> char {
+        match self {
+            North => '^',
+            East => '>',
+            South => 'v',
+            West => '<',
+        }
+    }
+
+    pub fn from_arrow(arrow: char) -> Option<Direction> {
+        Self::iter().find(|direction| direction.arrow() == arrow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::iter() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_ne!(direction.is_vertical(), direction.is_horizontal());
+        }
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+    }
+
+    #[test]
+    fn bits_are_distinct() {
+        let all = Direction::iter().fold(0, |bits, direction| bits | direction.bit());
+        assert_eq!(all, 0b1111);
+    }
+
+    #[test]
+    fn arrows() {
+        assert_eq!(Direction::from_arrow('v'), Some(South));
+        assert_eq!(Direction::from_arrow('.'), None);
+    }
+}
@@ -0,0 +1,436 @@
+use std::{
+    error::Error,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::Direction;
+
+/// Offsets of the eight surrounding tiles, clockwise starting from north.
+const OFFSETS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of tiles, indexed by `(x, y)` positions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row does not have as many tiles as the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that does not stand for any tile.
+    InvalidTile { x: usize, y: usize, tile: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} tiles, expected {}",
+                row + 1,
+                found,
+                expected
+            ),
+            GridError::InvalidTile { x, y, tile } => {
+                write!(
+                    f,
+                    "invalid tile {:?} at row {}, column {}",
+                    tile,
+                    y + 1,
+                    x + 1
+                )
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Build a grid from its rows, which all need to be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut tiles = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            tiles.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            tiles,
+        })
+    }
+
+    /// Parse one row per line of puzzle text, turning every character into
+    /// a tile with `tile`. Trailing empty lines are ignored.
+    pub fn parse_with<F>(input: &str, mut tile: F) -> Result<Self, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut lines: Vec<&str> = input.lines().collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| tile(c).ok_or(GridError::InvalidTile { x, y, tile: c }))
+                    .collect::<Result<Vec<T>, GridError>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a signed position lies on the grid.
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.tiles[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.tiles[y * self.width + x])
+    }
+
+    /// Tile at a position on the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.tiles[y * self.width + x]
+    }
+
+    fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    fn wrapping_offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> (usize, usize) {
+        (
+            (x as isize + dx).rem_euclid(self.width as isize) as usize,
+            (y as isize + dy).rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// Position one step in `direction`, if that is still on the grid.
+    pub fn step(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(position, direction.delta())
+    }
+
+    /// Position one step in `direction`, wrapping around at the edges.
+    pub fn wrapping_step(&self, position: (usize, usize), direction: Direction) -> (usize, usize) {
+        self.wrapping_offset(position, direction.delta())
+    }
+
+    /// The orthogonal neighbours on the grid and the direction to reach them.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (Direction, (usize, usize))> + '_ {
+        Direction::iter()
+            .filter_map(move |direction| Some((direction, self.step(position, direction)?)))
+    }
+
+    /// The orthogonal neighbours, wrapping around at the edges.
+    pub fn wrapping_neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (Direction, (usize, usize))> + '_ {
+        Direction::iter().map(move |direction| (direction, self.wrapping_step(position, direction)))
+    }
+
+    /// The orthogonal and diagonal neighbours on the grid.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS8
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// The orthogonal and diagonal neighbours, wrapping around at the edges.
+    pub fn wrapping_neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS8
+            .into_iter()
+            .map(move |delta| self.wrapping_offset(position, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.tiles[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // an empty grid has no tiles, so the chunk size does not matter there
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    /// The tiles of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {} outside of the grid", x);
+        self.tiles[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All tiles with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.tiles)
+    }
+
+    /// Position of the first tile matching `predicate`, searching row by row.
+    pub fn position<P>(&self, predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.tiles
+            .iter()
+            .position(predicate)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid of the given size with every tile set to `tile`.
+    pub fn new(width: usize, height: usize, tile: T) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![tile; width * height],
+        }
+    }
+
+    pub fn fill(&mut self, tile: T) {
+        self.tiles.fill(tile);
+    }
+
+    /// Mirror the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            tiles: (0..self.width)
+                .flat_map(|x| self.column(x).cloned())
+                .collect(),
+        }
+    }
+
+    /// Rotate the grid clockwise by 90 degrees.
+    pub fn rotate_right(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            tiles: (0..self.width)
+                .flat_map(|x| self.column(x).rev().cloned())
+                .collect(),
+        }
+    }
+
+    /// Rotate the grid counter-clockwise by 90 degrees.
+    pub fn rotate_left(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            tiles: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x).cloned())
+                .collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parse puzzle text into a grid of its characters.
+    pub fn parse(input: &str) -> Result<Self, GridError> {
+        Self::parse_with(input, Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} outside of {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} outside of {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+/// Prints the tiles row by row, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    const INPUT: &str = "#..\n.S#\n\n";
+
+    #[test]
+    fn parse_grid() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 'S');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.position(|&c| c == 'S'), Some((1, 1)));
+        assert_eq!(grid.to_string(), "#..\n.S#\n");
+    }
+
+    #[test]
+    fn reject_invalid_grids() {
+        assert_eq!(
+            Grid::parse("..\n...\n"),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x\n", |c| c.to_digit(10)),
+            Err(GridError::InvalidTile {
+                x: 1,
+                y: 1,
+                tile: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(East, (1, 0)), (South, (0, 1))]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+        assert_eq!(
+            grid.wrapping_neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![
+                (North, (0, 1)),
+                (East, (1, 0)),
+                (South, (0, 1)),
+                (West, (2, 0))
+            ]
+        );
+        assert_eq!(grid.wrapping_neighbours8((2, 1)).count(), 8);
+        assert_eq!(grid.step((2, 1), East), None);
+        assert_eq!(*grid.get_wrapping(-2, 3), 'S');
+        assert!(grid.in_bounds(2, 1));
+        assert!(!grid.in_bounds(-1, 0));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.row(1), &['.', 'S', '#']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), ".#");
+        assert_eq!(grid.column(2).rev().collect::<String>(), "#.");
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn rotate_and_transpose() {
+        let grid = Grid::parse("ab\ncd\nef\n").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotate_right().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_left().to_string(), "bdf\nace\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
@@ -0,0 +1,10 @@
+//! Two-dimensional grids of puzzle tiles and the directions to walk them in.
+//!
+//! Positions are `(x, y)` pairs with `x` counting columns from the left and
+//! `y` counting rows from the top, so north is towards the first row.
+
+mod direction;
+mod grid;
+
+pub use direction::Direction;
+pub use grid::{Grid, GridError};
@@ -0,0 +1,136 @@
+use num::PrimInt;
+
+use crate::Interval;
+
+/// The integer points with each coordinate in the interval of its axis, a
+/// box in `N` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HyperRect<T, const N: usize> {
+    pub sides: [Interval<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> HyperRect<T, N> {
+    pub fn new(sides: [Interval<T>; N]) -> Self {
+        HyperRect { sides }
+    }
+
+    /// Whether there is no point in the box, when one of its sides is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sides.iter().any(Interval::is_empty)
+    }
+
+    /// Number of points in the box.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        self.sides
+            .iter()
+            .fold(T::one(), |volume, side| volume * side.len())
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.sides
+            .iter()
+            .zip(point)
+            .all(|(side, &coordinate)| side.contains(coordinate))
+    }
+
+    /// The points in both boxes.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = *self;
+        for (side, other) in intersection.sides.iter_mut().zip(&other.sides) {
+            *side = side.intersection(other);
+        }
+        intersection
+    }
+
+    /// The points with their coordinate on `axis` below `at` and the ones
+    /// with it from `at` on, either of which can be empty.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not below `N`.
+    pub fn split(&self, axis: usize, at: T) -> (Self, Self) {
+        let (below, above) = self.sides[axis].split_at(at);
+        let (mut low, mut high) = (*self, *self);
+        low.sides[axis] = below;
+        high.sides[axis] = above;
+        (low, high)
+    }
+}
+
+// serde only implements its traits for arrays up to a fixed length, so the
+// sides go through a slice and a `Vec`
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for HyperRect<T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.sides.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for HyperRect<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sides: Vec<Interval<T>> = Vec::deserialize(deserializer)?;
+        let len = sides.len();
+        let sides = sides.try_into().map_err(|_| {
+            serde::de::Error::invalid_length(len, &format!("{} intervals", N).as_str())
+        })?;
+        Ok(HyperRect { sides })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_against_thresholds() {
+        let parts = HyperRect::new([Interval::closed(1_u64, 4000).unwrap(); 4]);
+        assert_eq!(parts.volume(), 4000_u64.pow(4));
+
+        // s < 1351 and then a > 2005 of the rest
+        let (low_s, rest) = parts.split(3, 1351);
+        assert_eq!(low_s.sides[3], Interval::closed(1, 1350).unwrap());
+        let (rest, high_a) = rest.split(2, 2006);
+        assert_eq!(high_a.sides[2], Interval::closed(2006, 4000).unwrap());
+        assert_eq!(
+            low_s.volume() + high_a.volume() + rest.volume(),
+            parts.volume()
+        );
+        assert!(high_a.contains(&[1, 4000, 2006, 1351]));
+        assert!(!high_a.contains(&[1, 4000, 2005, 1351]));
+
+        // splitting outside of a side leaves one of the halves empty
+        let (empty, same) = low_s.split(3, 0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.volume(), 0);
+        assert_eq!(same, low_s);
+    }
+
+    #[test]
+    fn intersect() {
+        let a = HyperRect::new([Interval::new(0_i64, 10), Interval::new(0, 10)]);
+        let b = HyperRect::new([Interval::new(5, 15), Interval::new(-5, 5)]);
+        assert_eq!(
+            a.intersection(&b),
+            HyperRect::new([Interval::new(5, 10), Interval::new(0, 5)])
+        );
+        let c = HyperRect::new([Interval::new(10, 15), Interval::new(0, 10)]);
+        assert!(a.intersection(&c).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let rect = HyperRect::new([Interval::new(1_u32, 2), Interval::new(3, 4)]);
+        let json = serde_json::to_string(&rect).unwrap();
+        assert_eq!(json, r#"[{"start":1,"end":2},{"start":3,"end":4}]"#);
+        assert_eq!(
+            serde_json::from_str::<HyperRect<u32, 2>>(&json).unwrap(),
+            rect
+        );
+        assert!(serde_json::from_str::<HyperRect<u32, 3>>(&json).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    cmp::{max, min},
+    error::Error,
+    fmt,
+    ops::{Range, RangeInclusive},
+};
+
+use num::PrimInt;
+
+/// A closed interval ending at the largest number of its type, which a
+/// half-open one can't hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the interval ends past the largest number of its type")
+    }
+}
+
+impl Error for Overflow {}
+
+/// The integers from `start` up to but not including `end`, empty unless
+/// `start < end`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /// The integers from `first` to `last`, both included, unless `last` is
+    /// the largest `T` and the end would not fit.
+    pub fn closed(first: T, last: T) -> Result<Self, Overflow> {
+        let end = last.checked_add(&T::one()).ok_or(Overflow)?;
+        Ok(Interval::new(first, end))
+    }
+
+    /// The `len` integers from `start`, cut off at the largest `T`.
+    pub fn with_len(start: T, len: T) -> Self {
+        Interval::new(start, start.saturating_add(len))
+    }
+
+    pub fn empty() -> Self {
+        Interval::new(T::zero(), T::zero())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Number of integers in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The smallest integer in the interval.
+    pub fn first(&self) -> Option<T> {
+        (!self.is_empty()).then_some(self.start)
+    }
+
+    /// The largest integer in the interval.
+    pub fn last(&self) -> Option<T> {
+        (!self.is_empty()).then(|| self.end - T::one())
+    }
+
+    /// The integers in both intervals.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Interval::new(max(self.start, other.start), min(self.end, other.end))
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The integers below `at` and the ones from `at` on, either of which
+    /// can be empty.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        (
+            Interval::new(self.start, min(at, self.end)),
+            Interval::new(max(at, self.start), self.end),
+        )
+    }
+
+    /// The interval moved by the distance from `from` to `to`.
+    ///
+    /// # Panics
+    ///
+    /// If the moved interval does not fit into `T`.
+    pub fn translate(&self, from: T, to: T) -> Self {
+        if to >= from {
+            let by = to - from;
+            Interval::new(self.start + by, self.end + by)
+        } else {
+            let by = from - to;
+            Interval::new(self.start - by, self.end - by)
+        }
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl<T: PrimInt> TryFrom<RangeInclusive<T>> for Interval<T> {
+    type Error = Overflow;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self, Overflow> {
+        Interval::closed(*range.start(), *range.end())
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let interval = Interval::closed(3_u32, 7).unwrap();
+        assert_eq!(interval, Interval::new(3, 8));
+        assert_eq!(Interval::try_from(3..=7), Ok(interval));
+        assert_eq!(interval, Interval::with_len(3, 5));
+        assert_eq!(interval.len(), 5);
+        assert_eq!((interval.first(), interval.last()), (Some(3), Some(7)));
+        assert!(interval.contains(3) && interval.contains(7));
+        assert!(!interval.contains(2) && !interval.contains(8));
+        assert_eq!(Range::from(interval), 3..8);
+        assert_eq!(interval.to_string(), "[3, 8)");
+
+        let empty = Interval::new(5_u32, 2);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.last(), None);
+        assert!(Interval::<i64>::empty().is_empty());
+
+        // cut off at the largest number instead of overflowing
+        assert_eq!(Interval::with_len(250_u8, 10), Interval::new(250, 255));
+    }
+
+    #[test]
+    fn largest_number() {
+        let below = Interval::closed(0_u8, 254).unwrap();
+        assert!(below.contains(254) && !below.contains(255));
+        assert_eq!(below.len(), 255);
+        assert_eq!(Interval::closed(0_u8, 255), Err(Overflow));
+        assert_eq!(Interval::try_from(i64::MIN..=i64::MAX), Err(Overflow));
+        assert_eq!(
+            Interval::closed(i64::MIN, i64::MAX - 1).map(|i| i.last()),
+            Ok(Some(i64::MAX - 1))
+        );
+    }
+
+    #[test]
+    fn intersect_and_split() {
+        let a = Interval::new(-5_i32, 5);
+        assert_eq!(a.intersection(&Interval::new(0, 10)), Interval::new(0, 5));
+        assert!(a.overlaps(&Interval::new(4, 6)));
+        assert!(!a.overlaps(&Interval::new(5, 6)));
+
+        assert_eq!(a.split_at(0), (Interval::new(-5, 0), Interval::new(0, 5)));
+        let (below, above) = a.split_at(-10);
+        assert!(below.is_empty());
+        assert_eq!(above, a);
+        let (below, above) = a.split_at(10);
+        assert_eq!(below, a);
+        assert!(above.is_empty());
+    }
+
+    #[test]
+    fn translate() {
+        let seeds = Interval::new(79_u64, 93);
+        assert_eq!(seeds.translate(50, 52), Interval::new(81, 95));
+        assert_eq!(seeds.translate(98, 50), Interval::new(31, 45));
+    }
+}
@@ -0,0 +1,32 @@
+//! Ranges of integers and what is left of them after cutting them up, for
+//! puzzles that push whole ranges through rules instead of single numbers.
+//!
+//! An [`Interval`] is half-open, from its `start` up to but not including its
+//! `end`, and [`Interval::closed`] builds one from its first and last number,
+//! as long as the end past the last one fits into the type.
+//! An [`IntervalSet`] keeps any set of numbers as the fewest intervals, and a
+//! [`HyperRect`] is an interval on each of `N` axes:
+//!
+//! ```
+//! use intervals::{HyperRect, Interval, IntervalSet};
+//!
+//! let seeds: IntervalSet<u64> = [Interval::new(79, 93), Interval::new(55, 68)]
+//!     .into_iter()
+//!     .collect();
+//! let mapped = seeds.intersection(&Interval::new(50, 80).into());
+//! let mapped: Vec<Interval<u64>> = mapped.iter().copied().collect();
+//! assert_eq!(mapped, [Interval::new(55, 68), Interval::new(79, 80)]);
+//!
+//! let parts = HyperRect::new([Interval::closed(1_u64, 4000).unwrap(); 4]);
+//! let (low_x, high_x) = parts.split(0, 1001);
+//! assert_eq!(low_x.volume(), 1000 * 4000 * 4000 * 4000);
+//! assert_eq!(high_x.volume() + low_x.volume(), parts.volume());
+//! ```
+
+mod hyper_rect;
+mod interval;
+mod set;
+
+pub use hyper_rect::HyperRect;
+pub use interval::{Interval, Overflow};
+pub use set::IntervalSet;
@@ -0,0 +1,278 @@
+use std::{cmp::max, slice};
+
+use num::PrimInt;
+
+use crate::Interval;
+
+/// A set of integers kept as the fewest intervals covering them: in order,
+/// not empty, and neither overlapping nor touching each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "Vec<Interval<T>>",
+        into = "Vec<Interval<T>>",
+        bound(
+            serialize = "T: Clone + serde::Serialize",
+            deserialize = "T: PrimInt + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the integers of `interval`, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let from = self.intervals.partition_point(|i| i.end < interval.start);
+        let to = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = if from < to {
+            Interval::new(
+                self.intervals[from].start.min(interval.start),
+                max(self.intervals[to - 1].end, interval.end),
+            )
+        } else {
+            interval
+        };
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    /// Take out the integers of `interval`, splitting an interval it cuts
+    /// through.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let from = self.intervals.partition_point(|i| i.end <= interval.start);
+        let to = self.intervals.partition_point(|i| i.start < interval.end);
+        if from == to {
+            return;
+        }
+        let before = Interval::new(self.intervals[from].start, interval.start);
+        let after = Interval::new(interval.end, self.intervals[to - 1].end);
+        let left = [before, after].into_iter().filter(|i| !i.is_empty());
+        self.intervals.splice(from..to, left);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter().copied());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = vec![];
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut next_a, mut next_b) = (a.next(), b.next());
+        while let (Some(x), Some(y)) = (next_a, next_b) {
+            let both = x.intersection(y);
+            if !both.is_empty() {
+                intersection.push(both);
+            }
+            // the interval ending first can't overlap anything further on
+            if x.end < y.end {
+                next_a = a.next();
+            } else {
+                next_b = b.next();
+            }
+        }
+        IntervalSet {
+            intervals: intersection,
+        }
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for &interval in other.iter() {
+            difference.remove(interval);
+        }
+        difference
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn count(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |count, interval| count + interval.len())
+    }
+
+    /// The smallest integer in the set.
+    pub fn first(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The largest integer in the set.
+    pub fn last(&self) -> Option<T> {
+        self.intervals.last().and_then(Interval::last)
+    }
+
+    /// The intervals of the set in order.
+    pub fn iter(&self) -> slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(interval);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, intervals: I) {
+        for interval in intervals {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(intervals);
+        set
+    }
+}
+
+/// The intervals sorted and merged, as [`FromIterator`] does.
+impl<T: PrimInt> From<Vec<Interval<T>>> for IntervalSet<T> {
+    fn from(intervals: Vec<Interval<T>>) -> Self {
+        intervals.into_iter().collect()
+    }
+}
+
+impl<T> From<IntervalSet<T>> for Vec<Interval<T>> {
+    fn from(set: IntervalSet<T>) -> Self {
+        set.intervals
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    fn intervals(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    /// The integers of the set one by one, to check against `BTreeSet`.
+    fn numbers(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    #[test]
+    fn merge_on_insert() {
+        let set = set(&[(5, 8), (0, 2), (2, 3), (10, 12), (7, 9), (20, 20)]);
+        assert_eq!(intervals(&set), [(0, 3), (5, 9), (10, 12)]);
+        assert_eq!(set.count(), 9);
+        assert_eq!((set.first(), set.last()), (Some(0), Some(11)));
+        assert!(set.contains(2) && set.contains(10));
+        assert!(!set.contains(3) && !set.contains(9) && !set.contains(-1));
+
+        let mut merged = set.clone();
+        merged.insert(Interval::new(1, 11));
+        assert_eq!(intervals(&merged), [(0, 12)]);
+    }
+
+    #[test]
+    fn split_on_remove() {
+        let mut set = set(&[(0, 10), (20, 30)]);
+        set.remove(Interval::new(3, 5));
+        assert_eq!(intervals(&set), [(0, 3), (5, 10), (20, 30)]);
+        set.remove(Interval::new(8, 25));
+        assert_eq!(intervals(&set), [(0, 3), (5, 8), (25, 30)]);
+        set.remove(Interval::new(-10, 40));
+        assert!(set.is_empty());
+        assert_eq!(set.first(), None);
+    }
+
+    #[test]
+    fn agree_with_sets_of_numbers() {
+        // every combination of a few intervals within 0..12
+        let pieces: Vec<(i32, i32)> = (0..12)
+            .flat_map(|start| (start..=12).map(move |end| (start, end)))
+            .collect();
+        let sets: Vec<IntervalSet<i32>> = pieces
+            .iter()
+            .enumerate()
+            .map(|(i, &piece)| set(&[piece, pieces[i * 7 % pieces.len()]]))
+            .collect();
+        for a in &sets {
+            for b in sets.iter().step_by(5) {
+                let (x, y) = (numbers(a), numbers(b));
+                assert_eq!(numbers(&a.union(b)), &x | &y);
+                assert_eq!(numbers(&a.intersection(b)), &x & &y);
+                assert_eq!(numbers(&a.difference(b)), &x - &y);
+                for result in [a.union(b), a.intersection(b), a.difference(b)] {
+                    assert!(result
+                        .iter()
+                        .zip(result.iter().skip(1))
+                        .all(|(i, j)| !i.is_empty() && i.end < j.start));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_merges() {
+        let set = set(&[(0, 3), (5, 9)]);
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, r#"[{"start":0,"end":3},{"start":5,"end":9}]"#);
+        assert_eq!(
+            serde_json::from_str::<IntervalSet<i32>>(&json).unwrap(),
+            set
+        );
+
+        // out of order, overlapping and empty intervals are merged as on insert
+        let unmerged = r#"[{"start":7,"end":9},{"start":0,"end":2},{"start":5,"end":8},{"start":2,"end":3},{"start":4,"end":4}]"#;
+        let merged: IntervalSet<i32> = serde_json::from_str(unmerged).unwrap();
+        assert_eq!(merged, set);
+    }
+}
@@ -0,0 +1,95 @@
+use std::{error::Error, fmt};
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+/// Where and why parsing the puzzle text failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending token.
+    pub line: usize,
+    /// 1-based column of the offending token, counted in characters.
+    pub column: usize,
+    /// Description of what the parser expected at that position.
+    pub expected: String,
+    /// Character found instead, `None` at the end of the input.
+    pub found: Option<char>,
+}
+
+impl ParseError {
+    /// Error of a parser run on the single `line`.
+    pub(crate) fn in_line(
+        line_number: usize,
+        line: &str,
+        error: nom::Err<VerboseError<&str>>,
+    ) -> Self {
+        let (rest, expected) = describe(error, "end of line");
+        let consumed = &line[..line.len() - rest.len()];
+        ParseError {
+            line: line_number,
+            column: consumed.chars().count() + 1,
+            expected,
+            found: rest.chars().next(),
+        }
+    }
+
+    /// Error of a parser run on the whole `text`.
+    pub(crate) fn in_text(text: &str, error: nom::Err<VerboseError<&str>>) -> Self {
+        let (rest, expected) = describe(error, "end of input");
+        let consumed = &text[..text.len() - rest.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |newline| newline + 1);
+        ParseError {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            expected,
+            found: rest.chars().next(),
+        }
+    }
+}
+
+/// Remaining input at the innermost error and a description of what was
+/// expected there.
+fn describe<'a>(error: nom::Err<VerboseError<&'a str>>, end: &str) -> (&'a str, String) {
+    let errors = match error {
+        nom::Err::Error(error) | nom::Err::Failure(error) => error.errors,
+        nom::Err::Incomplete(_) => return ("", "more input".to_owned()),
+    };
+    let Some(&(rest, ref kind)) = errors.first() else {
+        return ("", "valid input".to_owned());
+    };
+
+    // a context wrapping the failed parser at the same spot names it best
+    let context = errors.iter().find_map(|(input, kind)| match kind {
+        VerboseErrorKind::Context(context) if input.as_ptr() == rest.as_ptr() => Some(*context),
+        _ => None,
+    });
+    let expected = match (context, kind) {
+        (Some(context), _) if context.chars().all(char::is_alphanumeric) => context.to_owned(),
+        (Some(context), _) => format!("{:?}", context),
+        (None, VerboseErrorKind::Char(c)) => format!("{:?}", c),
+        (None, VerboseErrorKind::Context(context)) => context.to_string(),
+        (None, VerboseErrorKind::Nom(kind)) => match kind {
+            ErrorKind::Eof => end.to_owned(),
+            ErrorKind::Digit => "digit".to_owned(),
+            ErrorKind::Alpha => "letter".to_owned(),
+            ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_owned(),
+            kind => kind.description().to_lowercase(),
+        },
+    };
+    (rest, expected)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        match self.found {
+            Some(found) => write!(f, ", found {:?}", found),
+            None => write!(f, ", found end of input"),
+        }
+    }
+}
+
+impl Error for ParseError {}
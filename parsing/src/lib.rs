@@ -0,0 +1,111 @@
+//! Shared nom combinators for the puzzle inputs and the glue to run them
+//! with a readable diagnostic on failure.
+//!
+//! Parsers use the [`IResult`] alias of this crate so the error keeps track of
+//! what was expected where. [`parse_lines`] and [`parse_all`] turn such an
+//! error into a [`ParseError`] naming the line, column and expected token.
+
+mod error;
+mod numbers;
+
+pub use error::ParseError;
+pub use numbers::{int_parser, number_list_parser, separated_number_list};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::multispace0,
+    combinator::all_consuming,
+    error::{context, VerboseError},
+    sequence::terminated,
+    Parser,
+};
+
+/// Result of a parser over puzzle text.
+pub type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// Match the literal `token`, naming it in the diagnostic if it is missing.
+pub fn token<'a>(token: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    context(token, tag(token))
+}
+
+/// Parse every non-empty line of `input` with `parser`, which has to consume
+/// the whole line.
+pub fn parse_lines<'a, T, P>(input: &'a str, mut parser: P) -> Result<Vec<T>, ParseError>
+where
+    P: Parser<&'a str, T, VerboseError<&'a str>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            all_consuming(|i| parser.parse(i))(line)
+                .map(|(_, value)| value)
+                .map_err(|error| ParseError::in_line(index + 1, line, error))
+        })
+        .collect()
+}
+
+/// Parse all of `input` with `parser`, allowing only trailing whitespace
+/// after it.
+pub fn parse_all<'a, T, P>(input: &'a str, parser: P) -> Result<T, ParseError>
+where
+    P: Parser<&'a str, T, VerboseError<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, value)| value)
+        .map_err(|error| ParseError::in_text(input, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{character::complete::alpha1, sequence::separated_pair};
+
+    fn assignment(i: &str) -> IResult<'_, (&str, i32)> {
+        separated_pair(alpha1, token(" = "), int_parser)(i)
+    }
+
+    #[test]
+    fn parse_every_line() {
+        let values = parse_lines("a = 1\nb = -2\n\nc = 3\n", assignment).unwrap();
+        assert_eq!(values, vec![("a", 1), ("b", -2), ("c", 3)]);
+    }
+
+    #[test]
+    fn report_offending_line() {
+        let error = parse_lines("a = 1\nb = -2\nc := 3\n", assignment).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 2);
+        assert_eq!(error.expected, "\" = \"");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 2: expected \" = \", found ' '"
+        );
+
+        let error = parse_lines("a = 1x\n", assignment).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "end of line");
+    }
+
+    #[test]
+    fn report_position_in_text() {
+        let parser = separated_pair(
+            number_list_parser::<u32>,
+            token("\n---\n"),
+            number_list_parser::<u32>,
+        );
+        let (a, b) = parse_all("1 2\n---\n3 4\n\n", parser).unwrap();
+        assert_eq!((a, b), (vec![1, 2], vec![3, 4]));
+
+        let parser = separated_pair(
+            number_list_parser::<u32>,
+            token("\n---\n"),
+            number_list_parser::<u32>,
+        );
+        let error = parse_all("1 2\n---\nx 4\n", parser).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "number");
+        assert_eq!(error.found, Some('x'));
+    }
+}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1, one_of, space0, space1},
+    combinator::{map_res, opt, recognize},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, pair, terminated},
+};
+
+use crate::IResult;
+
+/// A decimal number with an optional sign, parsed into any `T: FromStr`.
+///
+/// Unsigned targets reject a leading `-` since `FromStr` does.
+pub fn int_parser<T: FromStr>(i: &str) -> IResult<'_, T> {
+    context(
+        "number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(i)
+}
+
+/// Numbers separated by spaces, ignoring extra spaces around and between them.
+pub fn number_list_parser<T: FromStr>(i: &str) -> IResult<'_, Vec<T>> {
+    delimited(space0, separated_list1(space1, int_parser), space0)(i)
+}
+
+/// Numbers separated by `separator`, with optional spaces on either side of it.
+pub fn separated_number_list<'a, T: FromStr>(
+    separator: char,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(
+        delimited(space0, char(separator), space0),
+        terminated(int_parser, space0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_and_unsigned() {
+        assert_eq!(int_parser::<i64>("-42 rest"), Ok((" rest", -42)));
+        assert_eq!(int_parser::<i8>("+7"), Ok(("", 7)));
+        assert_eq!(int_parser::<u32>("42"), Ok(("", 42)));
+        assert_eq!(int_parser::<f64>("-3"), Ok(("", -3.0)));
+        assert!(int_parser::<u32>("-42").is_err());
+        assert!(int_parser::<u8>("256").is_err());
+        assert!(int_parser::<u8>("x").is_err());
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            number_list_parser::<u32>("  1  2 3 |"),
+            Ok(("|", vec![1, 2, 3]))
+        );
+        assert_eq!(
+            separated_number_list::<i64>(',')("19, -13,30 @"),
+            Ok(("@", vec![19, -13, 30]))
+        );
+        assert_eq!(
+            separated_number_list::<usize>(',')("1,1,3"),
+            Ok(("", vec![1, 1, 3]))
+        );
+    }
+}
@@ -0,0 +1,54 @@
+/// An RGB color with eight bits per channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    /// The color `amount` of the way from `self` to `other`, with `amount`
+    /// between 0 and 1.
+    pub fn mix(self, other: Color, amount: f64) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+        Color(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    pub fn bytes(self) -> [u8; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+/// The colors all renderers share, so a wall looks the same on every day.
+pub mod palette {
+    use super::Color;
+
+    /// Empty tiles and everything outside of the interesting area.
+    pub const BACKGROUND: Color = Color(15, 15, 35);
+    /// Rocks, forest, mirrors and anything else that blocks the way.
+    pub const WALL: Color = Color(110, 110, 130);
+    /// The path, loop or rocks the answer is measured on.
+    pub const PATH: Color = Color(255, 255, 102);
+    /// Tiles reached or enclosed by the search.
+    pub const VISITED: Color = Color(0, 153, 0);
+    /// Tiles a beam of light passed through.
+    pub const ENERGIZED: Color = Color(255, 140, 0);
+    /// Where the search starts.
+    pub const START: Color = Color(220, 40, 40);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_colors() {
+        let black = Color(0, 0, 0);
+        let white = Color(255, 255, 255);
+        assert_eq!(black.mix(white, 0.0), black);
+        assert_eq!(black.mix(white, 1.0), white);
+        assert_eq!(black.mix(white, 0.5), Color(128, 128, 128));
+        assert_eq!(white.mix(Color(255, 0, 55), 2.0), Color(255, 0, 55));
+    }
+}
@@ -0,0 +1,117 @@
+//! Images of puzzle grids, written as PNG or PPM without any image library.
+//!
+//! An image is a [`Grid`] of [`Color`]s, so a renderer usually maps the
+//! puzzle grid tile by tile and then draws the solution on top:
+//!
+//! ```
+//! use grid::Grid;
+//! use raster::{palette, Image};
+//!
+//! let grid = Grid::parse("#.\n.#\n").unwrap();
+//! let image: Image = grid.map(|&tile| if tile == '#' { palette::WALL } else { palette::BACKGROUND });
+//! let mut png = vec![];
+//! raster::write(&raster::scale(&image, 4), raster::Format::Png, &mut png).unwrap();
+//! ```
+
+mod color;
+mod png;
+mod ppm;
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use common::Solution;
+use grid::Grid;
+
+pub use color::{palette, Color};
+
+/// An image with one pixel per tile.
+pub type Image = Grid<Color>;
+
+/// A solver that can draw the state its answer comes from, like the path it
+/// found or the tiles it visited.
+pub trait Render: Solution {
+    fn render(input: &Self::Input<'_>, params: &Self::Params) -> Result<Image, Self::Error>;
+}
+
+/// File format of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Ppm,
+}
+
+impl Format {
+    /// Format for the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(Format::Png),
+            "ppm" => Some(Format::Ppm),
+            _ => None,
+        }
+    }
+}
+
+/// Blow every pixel up to a `factor` x `factor` square, so single tiles of a
+/// 140x140 puzzle are still visible.
+pub fn scale(image: &Image, factor: usize) -> Image {
+    let mut scaled = Grid::new(
+        image.width() * factor,
+        image.height() * factor,
+        Color::default(),
+    );
+    for (x, y) in scaled.positions() {
+        scaled[(x, y)] = image[(x / factor, y / factor)];
+    }
+    scaled
+}
+
+pub fn write<W: Write>(image: &Image, format: Format, out: W) -> io::Result<()> {
+    match format {
+        Format::Png => png::write(image, out),
+        Format::Ppm => ppm::write(image, out),
+    }
+}
+
+/// Save the image in the format its file extension names.
+pub fn save(image: &Image, path: &Path) -> io::Result<()> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is neither .png nor .ppm", path.display()),
+        )
+    })?;
+    let mut out = BufWriter::new(File::create(path)?);
+    write(image, format, &mut out)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use palette::*;
+
+    #[test]
+    fn scale_image() {
+        let image = Grid::from_rows(vec![vec![WALL, PATH]]).unwrap();
+        let scaled = scale(&image, 2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.row(1), &[WALL, WALL, PATH, PATH]);
+        assert_eq!(scale(&image, 1), image);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(Format::from_path(Path::new("day10.PNG")), Some(Format::Png));
+        assert_eq!(
+            Format::from_path(Path::new("out/day16.ppm")),
+            Some(Format::Ppm)
+        );
+        assert_eq!(Format::from_path(Path::new("day17.jpg")), None);
+        assert_eq!(Format::from_path(Path::new("day17")), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! Truecolor PNG with the pixel data in uncompressed deflate blocks.
+//!
+//! Skipping the compression keeps the encoder to a page of code. The files
+//! are about as big as a PPM, which is no problem at puzzle sizes.
+
+use std::io::{self, Write};
+
+use crate::Image;
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// Largest payload of a single uncompressed deflate block.
+const MAX_BLOCK: usize = 0xffff;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in chunks.iter().flat_map(|chunk| chunk.iter()) {
+        crc = CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc ^ 0xffff_ffff
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_BLOCK).max(1);
+    let mut stream = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    // deflate with a 32K window and no preset dictionary
+    stream.extend([0x78, 0x01]);
+    for i in 0..blocks {
+        let block = &data[(i * MAX_BLOCK).min(data.len())..((i + 1) * MAX_BLOCK).min(data.len())];
+        let last = i + 1 == blocks;
+        let len = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+pub fn write<W: Write>(image: &Image, mut out: W) -> io::Result<()> {
+    let mut header = Vec::with_capacity(13);
+    header.extend((image.width() as u32).to_be_bytes());
+    header.extend((image.height() as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filter and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut scanlines = Vec::with_capacity(image.height() * (1 + image.width() * 3));
+    for row in image.rows() {
+        // no filter
+        scanlines.push(0);
+        scanlines.extend(row.iter().flat_map(|color| color.bytes()));
+    }
+
+    out.write_all(SIGNATURE)?;
+    write_chunk(&mut out, b"IHDR", &header)?;
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&scanlines))?;
+    write_chunk(&mut out, b"IEND", &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use grid::Grid;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(&[b"123456789"]), 0xcbf4_3926);
+        assert_eq!(crc32(&[b"1234", b"56789"]), 0xcbf4_3926);
+        assert_eq!(crc32(&[b"IEND"]), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn split_into_blocks() {
+        let data = vec![7; MAX_BLOCK + 10];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 5 + MAX_BLOCK + 5 + 10 + 4);
+        assert_eq!(&stream[2..7], &[0, 0xff, 0xff, 0, 0]);
+        assert_eq!(
+            &stream[7 + MAX_BLOCK..12 + MAX_BLOCK],
+            &[1, 10, 0, 0xf5, 0xff]
+        );
+        assert_eq!(
+            zlib_stored(&[]),
+            vec![0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn write_png() {
+        let image = Grid::from_rows(vec![vec![Color(255, 0, 0)], vec![Color(0, 0, 255)]]).unwrap();
+        let mut png = vec![];
+        write(&image, &mut png).unwrap();
+
+        assert_eq!(&png[..8], SIGNATURE);
+        assert_eq!(&png[8..16], b"\0\0\0\x0dIHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 1, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        let idat = &png[33..];
+        assert_eq!(&idat[4..8], b"IDAT");
+        // one filter byte and three color bytes per row
+        let pixels = &idat[8 + 7..8 + 7 + 8];
+        assert_eq!(pixels, &[0, 255, 0, 0, 0, 0, 0, 255]);
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+}
@@ -0,0 +1,28 @@
+//! Binary PPM (`P6`), the simplest format most image viewers still open.
+
+use std::io::{self, Write};
+
+use crate::Image;
+
+pub fn write<W: Write>(image: &Image, mut out: W) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    for (_, color) in image.iter() {
+        out.write_all(&color.bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use grid::Grid;
+
+    #[test]
+    fn write_ppm() {
+        let image = Grid::from_rows(vec![vec![Color(1, 2, 3), Color(4, 5, 6)]]).unwrap();
+        let mut ppm = vec![];
+        write(&image, &mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+}
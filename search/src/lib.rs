@@ -0,0 +1,240 @@
+//! Searches through the states of a puzzle, like a crucible at a position
+//! going in a direction, for the cheapest way to a goal or everything within
+//! reach.
+//!
+//! A puzzle describes its states by implementing [`State`], and a [`Search`]
+//! runs the `pathfinding` crate's algorithms on them, counting the states it
+//! went through:
+//!
+//! ```
+//! use search::{Search, State};
+//!
+//! /// A number that can be doubled for 1 or incremented for 3.
+//! #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+//! struct Number(u32);
+//!
+//! impl State for Number {
+//!     type Context = u32;
+//!     type Cost = u32;
+//!
+//!     fn successors(&self, target: &u32) -> Vec<(Number, u32)> {
+//!         [(self.0 * 2, 1), (self.0 + 1, 3)]
+//!             .into_iter()
+//!             .filter(|&(n, _)| n <= *target)
+//!             .map(|(n, cost)| (Number(n), cost))
+//!             .collect()
+//!     }
+//!
+//!     fn is_goal(&self, target: &u32) -> bool {
+//!         self.0 == *target
+//!     }
+//! }
+//!
+//! let mut search = Search::new(&10);
+//! let path = search.dijkstra(Number(1)).unwrap();
+//! assert_eq!(path.cost, 6);
+//! assert_eq!(path.states, [Number(1), Number(2), Number(4), Number(5), Number(10)]);
+//! assert!(search.stats().expanded > 0);
+//!
+//! // by fewest steps instead: 1, 2, 4, 8, 9, 10 is as short as 1, 2, 4, 5, 10
+//! assert_eq!(search.bfs(Number(1)).unwrap().cost, 4);
+//! ```
+
+mod reach;
+
+use std::hash::Hash;
+
+use pathfinding::{
+    directed::{astar::astar, bfs::bfs, dijkstra::dijkstra},
+    num_traits::Zero,
+};
+
+pub use reach::Reached;
+
+/// A state of a puzzle, from which it goes on to other states.
+///
+/// The search tells states apart by `Eq` and `Hash`, which must agree: derive
+/// both, and leave out of the state what does not change where it can go,
+/// like the steps taken so far.
+pub trait State: Clone + Eq + Hash {
+    /// What the states need to know to go on, like the puzzle's grid.
+    type Context: ?Sized;
+    type Cost: Zero + Ord + Copy;
+
+    /// The states this one leads to, each with the cost of getting there.
+    fn successors(&self, context: &Self::Context) -> Vec<(Self, Self::Cost)>;
+
+    /// Whether the search has arrived. No state is by default, for puzzles
+    /// that only look at what is reachable.
+    fn is_goal(&self, _context: &Self::Context) -> bool {
+        false
+    }
+
+    /// A lower bound of the cost to a goal, for A*. A bound of zero makes it
+    /// the same as Dijkstra.
+    fn heuristic(&self, _context: &Self::Context) -> Self::Cost {
+        Self::Cost::zero()
+    }
+}
+
+/// The states on the way from the start to a goal, both included, and the
+/// cost of the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// How much work the searches did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were asked for.
+    pub expanded: usize,
+    /// Successors returned, counting a state again each time it is reached.
+    pub generated: usize,
+}
+
+/// Searches through the states for one context, adding up their [`Stats`].
+pub struct Search<'a, S: State> {
+    context: &'a S::Context,
+    stats: Stats,
+}
+
+impl<'a, S: State> Search<'a, S> {
+    pub fn new(context: &'a S::Context) -> Self {
+        Search {
+            context,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Totals of all searches run so far.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    fn successors(&mut self, state: &S) -> Vec<(S, S::Cost)> {
+        let successors = state.successors(self.context);
+        self.stats.expanded += 1;
+        self.stats.generated += successors.len();
+        successors
+    }
+
+    /// The cheapest path from `start` to a goal.
+    pub fn dijkstra(&mut self, start: S) -> Option<Path<S, S::Cost>> {
+        let context = self.context;
+        let (states, cost) = dijkstra(
+            &start,
+            |state| self.successors(state),
+            |state| state.is_goal(context),
+        )?;
+        Some(Path { states, cost })
+    }
+
+    /// The cheapest path from `start` to a goal, led by the heuristic.
+    pub fn astar(&mut self, start: S) -> Option<Path<S, S::Cost>> {
+        let context = self.context;
+        let (states, cost) = astar(
+            &start,
+            |state| self.successors(state),
+            |state| state.heuristic(context),
+            |state| state.is_goal(context),
+        )?;
+        Some(Path { states, cost })
+    }
+
+    /// The path with the fewest steps from `start` to a goal, whatever their
+    /// costs, with the number of steps as its cost.
+    pub fn bfs(&mut self, start: S) -> Option<Path<S, usize>> {
+        let context = self.context;
+        let states = bfs(
+            &start,
+            |state| {
+                self.successors(state)
+                    .into_iter()
+                    .map(|(next, _)| next)
+                    .collect::<Vec<_>>()
+            },
+            |state| state.is_goal(context),
+        )?;
+        let cost = states.len() - 1;
+        Some(Path { states, cost })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A position on a grid of walls `#` and floor `.`, where stepping onto a
+    /// digit costs the digit.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub(crate) struct Tile(pub usize, pub usize);
+
+    pub(crate) const MAZE: [&str; 5] = ["..#..", ".#9#.", "..1..", "#.#.#", "....."];
+
+    impl State for Tile {
+        type Context = [&'static str];
+        type Cost = u32;
+
+        fn successors(&self, maze: &[&str]) -> Vec<(Tile, u32)> {
+            let Tile(x, y) = *self;
+            [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ]
+            .into_iter()
+            .filter_map(|(x, y)| {
+                let tile = maze.get(y)?.as_bytes().get(x)?;
+                match tile {
+                    b'#' => None,
+                    b'.' => Some((Tile(x, y), 1)),
+                    digit => Some((Tile(x, y), (digit - b'0') as u32)),
+                }
+            })
+            .collect()
+        }
+
+        fn is_goal(&self, maze: &[&str]) -> bool {
+            *self == Tile(maze[0].len() - 1, 0)
+        }
+
+        fn heuristic(&self, _maze: &[&str]) -> u32 {
+            (self.0.abs_diff(4) + self.1) as u32
+        }
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let mut search = Search::new(&MAZE[..]);
+        let path = search.dijkstra(Tile(0, 0)).unwrap();
+        // down around the left, across the 1 and up the right
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.len(), 9);
+        assert_eq!(path.states[0], Tile(0, 0));
+        assert_eq!(path.states[8], Tile(4, 0));
+        assert!(path.states.contains(&Tile(2, 2)));
+        let stats = search.stats();
+        assert!(stats.expanded <= 19 && stats.generated > stats.expanded);
+
+        let mut search = Search::new(&MAZE[..]);
+        assert_eq!(search.astar(Tile(0, 0)).unwrap().cost, 8);
+        assert!(search.stats().expanded <= stats.expanded);
+    }
+
+    #[test]
+    fn fewest_steps() {
+        let mut search = Search::new(&MAZE[..]);
+        let path = search.bfs(Tile(0, 0)).unwrap();
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.len(), 9);
+
+        let walled_in = ["..#", "###", "..."];
+        let mut search = Search::new(&walled_in[..]);
+        assert_eq!(search.dijkstra(Tile(0, 0)), None);
+        assert_eq!(search.bfs(Tile(0, 0)), None);
+        assert_eq!(search.stats().expanded, 4);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{Search, State};
+
+/// The states reachable from a start, by breadth-first search, with the
+/// fewest steps to each and the way there.
+#[derive(Debug, Clone)]
+pub struct Reached<S> {
+    /// The states in the order they were reached, with their number of
+    /// steps and the state they were first reached from.
+    states: Vec<(S, usize, Option<usize>)>,
+    indices: HashMap<S, usize>,
+}
+
+impl<S: State> Reached<S> {
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.indices.contains_key(state)
+    }
+
+    /// The fewest steps to the state, if it was reached.
+    pub fn steps(&self, state: &S) -> Option<usize> {
+        self.indices.get(state).map(|&i| self.states[i].1)
+    }
+
+    /// The states with the fewest steps to each, closest first.
+    pub fn iter(&self) -> impl Iterator<Item = (&S, usize)> {
+        self.states.iter().map(|(state, steps, _)| (state, *steps))
+    }
+
+    /// A way from the start to the state with the fewest steps, both
+    /// included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut index = *self.indices.get(state)?;
+        let mut path = vec![];
+        loop {
+            let (state, _, previous) = &self.states[index];
+            path.push(state.clone());
+            match previous {
+                Some(previous) => index = *previous,
+                None => break,
+            }
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<S: State> Search<'_, S> {
+    /// Every state `start` leads to in at most `max_steps` steps.
+    pub fn reachable_within(&mut self, start: S, max_steps: usize) -> Reached<S> {
+        let mut reached = Reached {
+            states: vec![(start.clone(), 0, None)],
+            indices: HashMap::from([(start, 0)]),
+        };
+        let mut queue = VecDeque::from([0]);
+        while let Some(index) = queue.pop_front() {
+            let (state, steps, _) = &reached.states[index];
+            if *steps == max_steps {
+                continue;
+            }
+            let steps = steps + 1;
+            for (next, _) in self.successors(&state.clone()) {
+                if !reached.indices.contains_key(&next) {
+                    reached.indices.insert(next.clone(), reached.states.len());
+                    queue.push_back(reached.states.len());
+                    reached.states.push((next, steps, Some(index)));
+                }
+            }
+        }
+        reached
+    }
+
+    /// Every state `start` leads to, which must be finitely many.
+    pub fn reachable(&mut self, start: S) -> Reached<S> {
+        self.reachable_within(start, usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{Tile, MAZE};
+
+    #[test]
+    fn within_steps() {
+        let mut search = Search::new(&MAZE[..]);
+        let near = search.reachable_within(Tile(0, 0), 3);
+        let mut tiles: Vec<(Tile, usize)> = near.iter().map(|(&t, steps)| (t, steps)).collect();
+        tiles.sort_by_key(|&(Tile(x, y), steps)| (steps, y, x));
+        assert_eq!(
+            tiles,
+            [
+                (Tile(0, 0), 0),
+                (Tile(1, 0), 1),
+                (Tile(0, 1), 1),
+                (Tile(0, 2), 2),
+                (Tile(1, 2), 3),
+            ]
+        );
+        assert_eq!(near.steps(&Tile(1, 2)), Some(3));
+        assert!(!near.contains(&Tile(2, 2)));
+        assert_eq!(search.stats().expanded, 4);
+    }
+
+    #[test]
+    fn everything() {
+        let mut search = Search::new(&MAZE[..]);
+        let all = search.reachable(Tile(0, 0));
+        assert_eq!(all.len(), 19);
+        assert_eq!(all.steps(&Tile(4, 0)), Some(8));
+        assert_eq!(all.steps(&Tile(2, 1)), Some(5));
+        assert_eq!(
+            all.path_to(&Tile(2, 1)),
+            Some(vec![
+                Tile(0, 0),
+                Tile(0, 1),
+                Tile(0, 2),
+                Tile(1, 2),
+                Tile(2, 2),
+                Tile(2, 1)
+            ])
+        );
+        assert_eq!(all.path_to(&Tile(0, 0)), Some(vec![Tile(0, 0)]));
+        assert_eq!(all.path_to(&Tile(2, 0)), None);
+    }
+}